@@ -0,0 +1,96 @@
+use polars::prelude::*;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Aggregation {
+    Count,
+    Sum,
+    Mean,
+    Min,
+    Max,
+    NUnique,
+}
+
+impl Aggregation {
+    pub const ALL: [Aggregation; 6] = [
+        Aggregation::Count,
+        Aggregation::Sum,
+        Aggregation::Mean,
+        Aggregation::Min,
+        Aggregation::Max,
+        Aggregation::NUnique,
+    ];
+
+    pub fn applies_to(self, dtype: &DataType) -> bool {
+        match self {
+            Aggregation::Count | Aggregation::NUnique => true,
+            Aggregation::Sum | Aggregation::Mean => dtype.is_primitive_numeric(),
+            Aggregation::Min | Aggregation::Max => {
+                dtype.is_primitive_numeric() || dtype.is_temporal()
+            }
+        }
+    }
+
+    /// Aggregates column `name`, keeping the column name.
+    pub fn expr(self, name: &str) -> Expr {
+        let column = col(name);
+        match self {
+            Aggregation::Count => column.count(),
+            Aggregation::Sum => column.sum(),
+            Aggregation::Mean => column.mean(),
+            Aggregation::Min => column.min(),
+            Aggregation::Max => column.max(),
+            Aggregation::NUnique => column.n_unique(),
+        }
+    }
+
+    /// Short lowercase name used in result column names, e.g. `price_sum`.
+    pub fn suffix(self) -> &'static str {
+        match self {
+            Aggregation::Count => "count",
+            Aggregation::Sum => "sum",
+            Aggregation::Mean => "mean",
+            Aggregation::Min => "min",
+            Aggregation::Max => "max",
+            Aggregation::NUnique => "n_unique",
+        }
+    }
+}
+
+impl std::fmt::Display for Aggregation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Aggregation::Count => write!(f, "Count"),
+            Aggregation::Sum => write!(f, "Sum"),
+            Aggregation::Mean => write!(f, "Mean"),
+            Aggregation::Min => write!(f, "Min"),
+            Aggregation::Max => write!(f, "Max"),
+            Aggregation::NUnique => write!(f, "Distinct"),
+        }
+    }
+}
+
+/// Applies `aggregation` to every column in `columns` it supports, returning one value per column.
+pub fn aggregate_columns(
+    df: &DataFrame,
+    columns: &[String],
+    aggregation: Aggregation,
+) -> PolarsResult<HashMap<String, AnyValue<'static>>> {
+    let exprs: Vec<Expr> = columns
+        .iter()
+        .filter(|name| {
+            df.column(name)
+                .is_ok_and(|column| aggregation.applies_to(column.dtype()))
+        })
+        .map(|name| aggregation.expr(name))
+        .collect();
+    if exprs.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let result = df.clone().lazy().select(exprs).collect()?;
+    result
+        .get_columns()
+        .iter()
+        .map(|column| Ok((column.name().to_string(), column.get(0)?.into_static())))
+        .collect()
+}
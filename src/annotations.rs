@@ -0,0 +1,118 @@
+use eframe::egui::Color32;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Colour tag for triaging rows.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Tag {
+    Red,
+    Orange,
+    Yellow,
+    Green,
+    Blue,
+    Purple,
+}
+
+impl Tag {
+    pub const ALL: [Tag; 6] = [
+        Tag::Red,
+        Tag::Orange,
+        Tag::Yellow,
+        Tag::Green,
+        Tag::Blue,
+        Tag::Purple,
+    ];
+
+    pub fn color(self) -> Color32 {
+        match self {
+            Tag::Red => Color32::from_rgb(0xe0, 0x40, 0x40),
+            Tag::Orange => Color32::from_rgb(0xe8, 0x8a, 0x20),
+            Tag::Yellow => Color32::from_rgb(0xe0, 0xc0, 0x20),
+            Tag::Green => Color32::from_rgb(0x3c, 0xb0, 0x50),
+            Tag::Blue => Color32::from_rgb(0x40, 0x80, 0xe0),
+            Tag::Purple => Color32::from_rgb(0xa0, 0x50, 0xd0),
+        }
+    }
+}
+
+impl std::fmt::Display for Tag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Tag::Red => write!(f, "Red"),
+            Tag::Orange => write!(f, "Orange"),
+            Tag::Yellow => write!(f, "Yellow"),
+            Tag::Green => write!(f, "Green"),
+            Tag::Blue => write!(f, "Blue"),
+            Tag::Purple => write!(f, "Purple"),
+        }
+    }
+}
+
+/// A note and colour tag on one row.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Annotation {
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub note: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<Tag>,
+}
+
+impl Annotation {
+    pub fn is_empty(&self) -> bool {
+        self.note.trim().is_empty() && self.tag.is_none()
+    }
+}
+
+/// Row annotations sidecar, keyed by the values of one column so they survive sorting,
+/// filtering and rewrites of the file. Rows sharing a key share their annotation.
+///
+/// Stored as JSON, e.g.
+///
+/// ```json
+/// {
+///   "key_column": "request_id",
+///   "rows": {
+///     "a81f": { "note": "Timeout, retried upstream", "tag": "orange" }
+///   }
+/// }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Annotations {
+    pub key_column: String,
+    pub rows: BTreeMap<String, Annotation>,
+}
+
+impl Annotations {
+    /// `<name>.annotations.json` next to a data file.
+    pub fn sidecar_path(data_file: &Path) -> PathBuf {
+        data_file.with_extension("annotations.json")
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Annotation> {
+        self.rows.get(key)
+    }
+
+    /// Stores `annotation` for `key`, or removes it when it is empty.
+    pub fn set(&mut self, key: String, annotation: Annotation) {
+        if annotation.is_empty() {
+            self.rows.remove(&key);
+        } else {
+            self.rows.insert(key, annotation);
+        }
+    }
+}
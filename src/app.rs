@@ -0,0 +1,3386 @@
+//! The Tablr window: file loading, the toolbar, panels and state shared by the
+//! submodules, which hold the data grid and the dialogs around it.
+
+use eframe::egui::{
+    self, Align, CentralPanel, Color32, ComboBox, Context, CursorIcon, Key, Layout, Modifiers,
+    RichText, Sense, TextStyle, Ui, Window,
+};
+use eframe::egui::{ScrollArea, TextWrapMode};
+use egui::widgets::Label;
+use egui_extras::{Column, TableBuilder};
+use polars::prelude::*;
+#[cfg(not(target_arch = "wasm32"))]
+use rfd::FileDialog;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::mpsc::Receiver;
+use std::time::Duration;
+use web_time::Instant;
+
+use crate::ROW_INDEX_COLUMN;
+use crate::aggregate::Aggregation;
+use crate::annotations::{Annotation, Annotations, Tag};
+use crate::bar_chart::ValueCounts;
+use crate::box_plot::GroupBox;
+use crate::bucket::BucketUnit;
+use crate::cast::CastTarget;
+use crate::cli::StartupView;
+use crate::derived::DerivedState;
+use crate::dictionary::DataDictionary;
+use crate::diff::{DiffStatus, FileDiff};
+use crate::epoch::EpochUnit;
+use crate::export::{Compression, CsvEncoding, CsvOptions, FileFormat};
+use crate::filter::FilterType;
+use crate::format::DisplayMode;
+use crate::histogram::Histogram;
+use crate::hooks::HookEvent;
+use crate::join::JoinKind;
+use crate::keymap::{self, Action};
+use crate::layout::ColumnLayout;
+use crate::line_chart::LineChart;
+use crate::loader::RowLimit;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::pdf;
+use crate::pii::PiiFinding;
+use crate::plot::PlotState;
+use crate::render::CellRenderers;
+use crate::scatter::ScatterData;
+use crate::schema_export::SqlDialect;
+use crate::settings::{Settings, Theme};
+use crate::table::DataFrameTable;
+#[cfg(target_arch = "wasm32")]
+use crate::web::{self, FileDialog};
+use crate::workspace::{Session, Workspace};
+use crate::{
+    aggregate, append, bar_chart, box_plot, bucket, cast, constant, correlation, describe,
+    diagnostics, diff, duplicates, edit, epoch, export, expression, filter, format, group_by,
+    histogram, hooks, html, images, instance, join, json_view, layout, line_chart, loader,
+    manifest, markdown, metadata, minichart, nested, notify, overview, panels, pii, pivot, plot,
+    profile, replace, scatter, schema_compare, schema_export, script, search, similarity, split,
+    unpivot, watch, workspace, xlsx,
+};
+
+mod charts;
+mod compare;
+mod editing;
+mod exporting;
+mod finding;
+mod grid;
+mod inspect;
+mod preferences;
+mod transforms;
+
+/// Recent operations listed in the Diagnostics window.
+const MAX_TIMINGS: usize = 50;
+
+const BOOKMARK_COLOR: Color32 = Color32::from_rgb(0xc0, 0x40, 0xd0);
+
+/// Marks cells changed in edit mode.
+const EDITED_COLOR: Color32 = Color32::from_rgb(0xe0, 0x90, 0x20);
+
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Tab {
+    Data,
+    Overview,
+}
+
+/// Kinds of line in the script console's transcript.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ScriptLine {
+    Input,
+    Printed,
+    Error,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ExportMode {
+    Masked,
+    Raw,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Density {
+    Compact,
+    Comfortable,
+}
+
+impl Density {
+    fn row_height(self) -> f32 {
+        match self {
+            Density::Compact => 20.0,
+            Density::Comfortable => 30.0,
+        }
+    }
+}
+
+impl std::fmt::Display for Density {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Density::Compact => write!(f, "Compact"),
+            Density::Comfortable => write!(f, "Comfortable"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CellWrap {
+    Extend,
+    Wrap,
+    Truncate,
+}
+
+impl CellWrap {
+    fn text_wrap_mode(self) -> TextWrapMode {
+        match self {
+            CellWrap::Extend => TextWrapMode::Extend,
+            CellWrap::Wrap => TextWrapMode::Wrap,
+            CellWrap::Truncate => TextWrapMode::Truncate,
+        }
+    }
+}
+
+impl std::fmt::Display for CellWrap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CellWrap::Extend => write!(f, "Extend"),
+            CellWrap::Wrap => write!(f, "Wrap"),
+            CellWrap::Truncate => write!(f, "Truncate"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum OrderPreset {
+    Alphabetical,
+    NumericFirst,
+    MostPopulated,
+}
+
+impl OrderPreset {
+    const ALL: [OrderPreset; 3] = [
+        OrderPreset::Alphabetical,
+        OrderPreset::NumericFirst,
+        OrderPreset::MostPopulated,
+    ];
+}
+
+impl std::fmt::Display for OrderPreset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OrderPreset::Alphabetical => write!(f, "A–Z"),
+            OrderPreset::NumericFirst => write!(f, "Numeric first"),
+            OrderPreset::MostPopulated => write!(f, "Most populated first"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CellAlign {
+    Left,
+    Center,
+    Right,
+}
+
+impl CellAlign {
+    const ALL: [CellAlign; 3] = [CellAlign::Left, CellAlign::Center, CellAlign::Right];
+
+    /// Numbers and flags line up on the right; everything else reads from the left.
+    fn default_for(dtype: &DataType) -> Self {
+        if dtype.is_primitive_numeric() || dtype == &DataType::Boolean {
+            CellAlign::Right
+        } else {
+            CellAlign::Left
+        }
+    }
+
+    fn layout(self) -> Layout {
+        match self {
+            CellAlign::Left => Layout::left_to_right(Align::Center),
+            CellAlign::Center => Layout::centered_and_justified(egui::Direction::LeftToRight),
+            CellAlign::Right => Layout::right_to_left(Align::Center),
+        }
+    }
+}
+
+impl std::fmt::Display for CellAlign {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CellAlign::Left => write!(f, "Left"),
+            CellAlign::Center => write!(f, "Center"),
+            CellAlign::Right => write!(f, "Right"),
+        }
+    }
+}
+
+/// A cell being changed in edit mode.
+struct CellEdit {
+    /// Position in the view.
+    row: usize,
+    /// The row's index in the loaded data.
+    row_id: IdxSize,
+    column: String,
+    original: String,
+    text: String,
+    error: Option<String>,
+    focus: bool,
+}
+
+/// Something that would drop unsaved edits, held until the user saves or discards them.
+enum UnsavedAction {
+    Open(Vec<PathBuf>),
+    OpenBytes(String, Arc<[u8]>),
+    SetRowLimit(RowLimit),
+    SetSourceColumns(bool),
+    Close,
+}
+
+impl UnsavedAction {
+    fn description(&self) -> &'static str {
+        match self {
+            UnsavedAction::Open(_) | UnsavedAction::OpenBytes(..) => "Opening other files",
+            UnsavedAction::SetRowLimit(_) | UnsavedAction::SetSourceColumns(_) => {
+                "Reading the files again"
+            }
+            UnsavedAction::Close => "Closing Tablr",
+        }
+    }
+}
+
+/// Count, sum and average of the numeric cells in the selected rows.
+struct SelectionStats {
+    columns: Vec<String>,
+    count: usize,
+    sum: f64,
+}
+
+struct Tablr {
+    settings: Settings,
+    settings_dialog_open: bool,
+    /// The settings file could not be loaded, so the defaults are in use and must not be
+    /// written over it.
+    settings_file_invalid: bool,
+    /// Started with `--safe-mode`: defaults only, nothing restored or run in the background.
+    safe_mode: bool,
+    /// Applied once the files given on the command line have loaded.
+    startup_view: Option<StartupView>,
+    /// The previous session, until the user chooses whether to restore it.
+    session_prompt: Option<Session>,
+    /// Applied once the files of a restored session have loaded.
+    session_restore: Option<Session>,
+
+    dataframe: Option<DataFrame>,
+    original_dataframe: Option<DataFrame>,
+    row_limit: RowLimit,
+    sample_rows: usize,
+    sample_seed: u64,
+    edge_rows: usize,
+    data_version: u64,
+    column_names: Vec<String>,
+    files_to_load: Vec<PathBuf>,
+    /// Contents of a file that has no path to read from, e.g. one opened in the browser.
+    memory_file: Option<Arc<[u8]>>,
+    renderers: CellRenderers,
+    #[cfg(target_arch = "wasm32")]
+    picked_files: Option<Receiver<(String, Vec<u8>)>>,
+    notifications: notify::Notifications,
+    files_loaded: bool,
+    tab: Tab,
+    overview: Option<overview::Overview>,
+
+    sort_column: Option<usize>,
+    sort_descending: bool,
+
+    filter_dialog_open: bool,
+    selected_filter_column: Option<usize>,
+    filter_text: String,
+    filter_type: FilterType,
+    /// Expression from `--filter` or the Filter dialog, applied before the column filter.
+    filter_expression: String,
+
+    notes_open: bool,
+    notes_detached: bool,
+    notes: HashMap<String, String>,
+    /// Key of the loaded dataset in `notes`.
+    dataset_key: Option<String>,
+
+    correlation_open: bool,
+    correlation_detached: bool,
+    /// Numeric columns left out of the correlation matrix.
+    correlation_excluded: HashSet<String>,
+    correlation: Option<correlation::CorrelationMatrix>,
+    correlation_state: DerivedState,
+    scatter_open: bool,
+    scatter_detached: bool,
+    scatter_x: Option<String>,
+    scatter_y: Option<String>,
+    /// Category column colouring the points.
+    scatter_color: Option<String>,
+    scatter: Option<ScatterData>,
+    scatter_state: DerivedState,
+    scatter_view: PlotState,
+    line_open: bool,
+    line_detached: bool,
+    /// Date or datetime column along x.
+    line_x: Option<String>,
+    line_ys: Vec<String>,
+    line_chart: Option<LineChart>,
+    line_state: DerivedState,
+    line_view: PlotState,
+    histogram_open: bool,
+    histogram_detached: bool,
+    histogram_column: Option<String>,
+    histogram_bins: usize,
+    /// Shows counts on a logarithmic scale.
+    histogram_log: bool,
+    histogram: Option<Histogram>,
+    histogram_state: DerivedState,
+    histogram_view: PlotState,
+    bar_open: bool,
+    bar_detached: bool,
+    bar_column: Option<String>,
+    /// How many of the most frequent values get a bar.
+    bar_top: usize,
+    bar_counts: Option<ValueCounts>,
+    bar_state: DerivedState,
+    box_open: bool,
+    box_detached: bool,
+    box_value: Option<String>,
+    box_group: Option<String>,
+    /// How many of the largest groups get a box.
+    box_groups: usize,
+    boxes: Vec<GroupBox>,
+    box_state: DerivedState,
+    box_view: PlotState,
+
+    /// Column being renamed from the header menu and the name typed so far.
+    column_rename: Option<(String, String)>,
+
+    replace_open: bool,
+    replace_column: Option<String>,
+    replace_find: String,
+    replace_with: String,
+    replace_regex: bool,
+    replace_preview: Option<replace::ReplacePreview>,
+
+    unpivot_open: bool,
+    unpivot_index: Vec<String>,
+    unpivot_values: Vec<String>,
+    unpivot_variable_name: String,
+    unpivot_value_name: String,
+
+    join_open: bool,
+    /// File name and contents of the file to join onto the current data.
+    join_other: Option<(String, DataFrame)>,
+    join_left_key: Option<String>,
+    join_right_key: Option<String>,
+    join_kind: JoinKind,
+
+    split_column_open: bool,
+    split_column: Option<String>,
+    split_delimiter: String,
+    split_regex: bool,
+    split_parts: usize,
+
+    computed_open: bool,
+    computed_name: String,
+    computed_expression: String,
+    computed_error: Option<String>,
+    /// Columns added from expressions, as (name, expression text).
+    computed_columns: Vec<(String, String)>,
+
+    duplicates_open: bool,
+    duplicate_keys: Vec<String>,
+    duplicates: Option<duplicates::Duplicates>,
+    duplicate_highlight: bool,
+    duplicate_keep_last: bool,
+    /// Outcome of the last "Remove duplicates", shown in the dialog.
+    duplicate_report: Option<String>,
+    duplicate_state: DerivedState,
+
+    profile_open: bool,
+    profile_detached: bool,
+    profiles: Vec<profile::ColumnProfile>,
+    profile_state: DerivedState,
+
+    describe_open: bool,
+    describe_detached: bool,
+    describe_pane: DataFrameTable,
+    describe_state: DerivedState,
+
+    group_dialog_open: bool,
+    group_detached: bool,
+    group_keys: Vec<String>,
+    group_aggregations: Vec<(String, Aggregation)>,
+    group_result_keys: Vec<String>,
+    group_pane: DataFrameTable,
+
+    pivot_open: bool,
+    pivot_detached: bool,
+    pivot_index: Vec<String>,
+    pivot_on: Option<String>,
+    pivot_values: Option<String>,
+    pivot_aggregation: Aggregation,
+    /// Index columns of the shown result, for drilling into a row.
+    pivot_result_index: Vec<String>,
+    pivot_pane: DataFrameTable,
+    pivot_state: DerivedState,
+    /// Description of a narrowed view and the view it was narrowed from.
+    drill_down: Option<(String, DataFrame)>,
+
+    similarity_dialog_open: bool,
+    similarity_detached: bool,
+    similar_columns: Vec<similarity::ColumnPair>,
+    similarity_state: DerivedState,
+
+    pii_dialog_open: bool,
+    pii_detached: bool,
+    pii_findings: Vec<PiiFinding>,
+    pii_state: DerivedState,
+    masked_columns: HashSet<String>,
+
+    json_columns: HashSet<String>,
+    column_charts: HashMap<String, Option<minichart::MiniChart>>,
+    column_charts_version: u64,
+    json_cell: Option<(String, Result<serde_json::Value, String>)>,
+    json_detached: bool,
+
+    image_columns: HashSet<String>,
+    image_cell: Option<(String, String)>,
+    forget_images: bool,
+
+    export_dialog_open: bool,
+    export_mode: ExportMode,
+    export_manifest: bool,
+    csv_options: CsvOptions,
+    parquet_compression: Compression,
+    xlsx_autofilter: bool,
+    /// Transformations applied to the loaded data, recorded for export manifests.
+    applied_steps: Vec<String>,
+
+    display_dialog_open: bool,
+    density: Density,
+    row_height: f32,
+    column_wrap: HashMap<String, CellWrap>,
+    column_align: HashMap<String, CellAlign>,
+    monospace_columns: HashSet<String>,
+    column_display: HashMap<String, DisplayMode>,
+    show_view_position: bool,
+    footer_aggregation: Option<Aggregation>,
+    footer_values: HashMap<String, AnyValue<'static>>,
+    footer_computed_for: Option<(u64, Aggregation)>,
+    transposed: bool,
+
+    goto_dialog_open: bool,
+    goto_text: String,
+    goto_by_position: bool,
+    highlighted_row: Option<usize>,
+    scroll_to_row: Option<(usize, Option<Align>)>,
+
+    focused_cell: Option<(usize, String)>,
+    selected_rows: BTreeSet<usize>,
+    selection_anchor: Option<usize>,
+    selection_stats: Option<SelectionStats>,
+    /// Cells can be changed by double-clicking them.
+    edit_mode: bool,
+    editing_cell: Option<CellEdit>,
+    /// Cells changed by hand, by row index and column, until the files are read again.
+    edited_cells: HashSet<(IdxSize, String)>,
+    /// Rows inserted in edit mode, by row index.
+    added_rows: HashSet<IdxSize>,
+    deleted_rows: usize,
+    /// Waiting for the user to save or discard the edits first.
+    unsaved_action: Option<UnsavedAction>,
+    /// The user agreed to close the window despite unsaved edits.
+    close_confirmed: bool,
+    /// The files changed on disk, and reading them again would drop the applied steps.
+    reload_pending: bool,
+    /// Durations of recent operations, newest last.
+    timings: VecDeque<(&'static str, Duration)>,
+    diagnostics_open: bool,
+    diagnostics_detached: bool,
+    /// Unoptimized and optimized plans for reading the files with the current filter and sort.
+    query_plan: Option<Result<(String, String), String>>,
+    script_open: bool,
+    script_detached: bool,
+    script_text: String,
+    /// Everything the console has shown this session: scripts, printed lines and errors.
+    script_transcript: Vec<(ScriptLine, String)>,
+    /// The last script's result, until it is applied or discarded.
+    script_result: Option<DataFrame>,
+    focus_moved: bool,
+    page_rows: usize,
+
+    search_open: bool,
+    search_focus: bool,
+    search_text: String,
+    search_matches: Vec<(usize, usize)>,
+    search_current: Option<usize>,
+    search_state: DerivedState,
+
+    bookmarks: BTreeSet<IdxSize>,
+    bookmark_positions: Vec<usize>,
+    bookmark_positions_for: Option<u64>,
+    show_minimap: bool,
+    show_null_bars: bool,
+
+    highlight_open: bool,
+    highlight_pattern: String,
+    highlight_column: Option<String>,
+    highlight_rows: bool,
+    highlight_matches: Vec<(usize, usize)>,
+    highlight_row_set: BTreeSet<usize>,
+    highlight_error: Option<String>,
+    highlight_state: DerivedState,
+
+    dictionary: DataDictionary,
+    dictionary_path: Option<PathBuf>,
+    annotations: Annotations,
+    annotations_path: Option<PathBuf>,
+    /// Key of the row whose annotation is being edited, and the annotation so far.
+    annotation_edit: Option<(String, Annotation)>,
+    schema_dialog_open: bool,
+    schema_detached: bool,
+    schema_dialect: SqlDialect,
+
+    metadata_open: bool,
+    metadata_detached: bool,
+    /// Footers of the selected files, read when the metadata panel opens.
+    file_metadata: Vec<FileMetadataRef>,
+    file_metadata_for: Vec<PathBuf>,
+    file_metadata_error: Option<String>,
+    row_group_selection: BTreeSet<usize>,
+    /// Row group whose column-chunk statistics are shown.
+    row_group_stats: Option<usize>,
+    /// Adds columns naming the file and row group of each row when several files are loaded.
+    source_columns: bool,
+    /// Set while the loaded files are watched for changes.
+    file_watch: Option<watch::FileWatch>,
+    /// Files sent by later launches while single-instance mode is on.
+    instance_requests: Option<Receiver<Vec<PathBuf>>>,
+    /// Files sent by a later launch, waiting for the user to choose where to open them.
+    forwarded_files: Option<Vec<PathBuf>>,
+
+    schema_compare_open: bool,
+    schema_compare: Vec<schema_compare::ColumnComparison>,
+    schema_compare_for: Vec<PathBuf>,
+    schema_compare_differences_only: bool,
+
+    schema_hash: Option<u64>,
+    column_order: Vec<String>,
+    hidden_columns: HashSet<String>,
+    column_widths: HashMap<String, f32>,
+
+    split_pane: DataFrameTable,
+    /// Draws `dataframe`, which it holds without a filter or sort of its own.
+    grid: DataFrameTable,
+    split_open: bool,
+    /// Files shown in the split view, remembered for the next start.
+    split_files: Vec<PathBuf>,
+    sync_scroll: bool,
+    split_scroll_to: Option<usize>,
+    first_visible_row: usize,
+    synced_rows: (usize, usize),
+
+    diff_dialog_open: bool,
+    diff_detached: bool,
+    diff_other: Option<(String, DataFrame)>,
+    diff_keys: Vec<String>,
+    diff_result: Option<FileDiff>,
+    diff_statuses: Vec<DiffStatus>,
+    diff_view: Option<DataFrame>,
+
+    epoch_columns: HashMap<String, EpochUnit>,
+    epoch_hint_dismissed: bool,
+
+    constant_columns: Vec<(String, String)>,
+    constant_hint_dismissed: bool,
+}
+
+impl Tablr {
+    fn new(files_to_load: Vec<PathBuf>, settings: Settings, safe_mode: bool) -> Self {
+        let row_height = settings.row_height;
+        Self {
+            settings,
+            settings_dialog_open: false,
+            settings_file_invalid: false,
+            safe_mode,
+            startup_view: None,
+            session_prompt: None,
+            session_restore: None,
+
+            dataframe: None,
+            original_dataframe: None,
+            row_limit: RowLimit::All,
+            sample_rows: 100_000,
+            sample_seed: 42,
+            edge_rows: 1000,
+            data_version: 0,
+            column_names: Vec::new(),
+            files_to_load,
+            memory_file: None,
+            renderers: CellRenderers::default(),
+            #[cfg(target_arch = "wasm32")]
+            picked_files: None,
+            notifications: notify::Notifications::default(),
+            files_loaded: false,
+            tab: Tab::Data,
+            overview: None,
+
+            sort_column: None,
+            sort_descending: false,
+
+            filter_dialog_open: false,
+            selected_filter_column: None,
+            filter_text: String::new(),
+            filter_expression: String::new(),
+            filter_type: FilterType::Equals,
+
+            notes_open: false,
+            notes_detached: false,
+            notes: HashMap::new(),
+            dataset_key: None,
+
+            correlation_open: false,
+            correlation_detached: false,
+            correlation_excluded: HashSet::new(),
+            correlation: None,
+            correlation_state: DerivedState::new(false),
+            scatter_open: false,
+            scatter_detached: false,
+            scatter_x: None,
+            scatter_y: None,
+            scatter_color: None,
+            scatter: None,
+            scatter_state: DerivedState::new(true),
+            scatter_view: PlotState::default(),
+            line_open: false,
+            line_detached: false,
+            line_x: None,
+            line_ys: Vec::new(),
+            line_chart: None,
+            line_state: DerivedState::new(true),
+            line_view: PlotState::default(),
+            histogram_open: false,
+            histogram_detached: false,
+            histogram_column: None,
+            histogram_bins: histogram::DEFAULT_BINS,
+            histogram_log: false,
+            histogram: None,
+            histogram_state: DerivedState::new(true),
+            histogram_view: PlotState::default(),
+            bar_open: false,
+            bar_detached: false,
+            bar_column: None,
+            bar_top: bar_chart::DEFAULT_TOP,
+            bar_counts: None,
+            bar_state: DerivedState::new(true),
+            box_open: false,
+            box_detached: false,
+            box_value: None,
+            box_group: None,
+            box_groups: box_plot::DEFAULT_GROUPS,
+            boxes: Vec::new(),
+            box_state: DerivedState::new(true),
+            box_view: PlotState::default(),
+
+            column_rename: None,
+
+            replace_open: false,
+            replace_column: None,
+            replace_find: String::new(),
+            replace_with: String::new(),
+            replace_regex: false,
+            replace_preview: None,
+
+            unpivot_open: false,
+            unpivot_index: Vec::new(),
+            unpivot_values: Vec::new(),
+            unpivot_variable_name: "variable".to_string(),
+            unpivot_value_name: "value".to_string(),
+
+            join_open: false,
+            join_other: None,
+            join_left_key: None,
+            join_right_key: None,
+            join_kind: JoinKind::Left,
+
+            split_column_open: false,
+            split_column: None,
+            split_delimiter: ",".to_string(),
+            split_regex: false,
+            split_parts: 2,
+
+            computed_open: false,
+            computed_name: String::new(),
+            computed_expression: String::new(),
+            computed_error: None,
+            computed_columns: Vec::new(),
+
+            duplicates_open: false,
+            duplicate_keys: Vec::new(),
+            duplicates: None,
+            duplicate_highlight: true,
+            duplicate_keep_last: false,
+            duplicate_report: None,
+            duplicate_state: DerivedState::new(!safe_mode),
+
+            profile_open: false,
+            profile_detached: false,
+            profiles: Vec::new(),
+            profile_state: DerivedState::new(false),
+
+            describe_open: false,
+            describe_detached: false,
+            describe_pane: DataFrameTable::default()
+                .id_salt("describe")
+                .show_filter_bar(false),
+            describe_state: DerivedState::new(!safe_mode),
+
+            group_dialog_open: false,
+            group_detached: false,
+            group_keys: Vec::new(),
+            group_aggregations: Vec::new(),
+            group_result_keys: Vec::new(),
+            group_pane: DataFrameTable::default().id_salt("group_by"),
+            pivot_open: false,
+            pivot_detached: false,
+            pivot_index: Vec::new(),
+            pivot_on: None,
+            pivot_values: None,
+            pivot_aggregation: Aggregation::Sum,
+            pivot_result_index: Vec::new(),
+            pivot_pane: DataFrameTable::default().id_salt("pivot"),
+            pivot_state: DerivedState::new(true),
+            drill_down: None,
+
+            similarity_dialog_open: false,
+            similarity_detached: false,
+            similar_columns: Vec::new(),
+            similarity_state: DerivedState::new(false),
+
+            pii_dialog_open: false,
+            pii_detached: false,
+            pii_findings: Vec::new(),
+            pii_state: DerivedState::new(!safe_mode),
+            masked_columns: HashSet::new(),
+
+            json_columns: HashSet::new(),
+            column_charts: HashMap::new(),
+            column_charts_version: 0,
+            json_cell: None,
+            json_detached: false,
+
+            image_columns: HashSet::new(),
+            image_cell: None,
+            forget_images: false,
+
+            export_dialog_open: false,
+            export_mode: ExportMode::Masked,
+            export_manifest: false,
+            csv_options: CsvOptions::default(),
+            parquet_compression: Compression::Snappy,
+            xlsx_autofilter: true,
+            applied_steps: Vec::new(),
+
+            display_dialog_open: false,
+            density: if row_height >= Density::Comfortable.row_height() {
+                Density::Comfortable
+            } else {
+                Density::Compact
+            },
+            row_height,
+            column_wrap: HashMap::new(),
+            column_align: HashMap::new(),
+            monospace_columns: HashSet::new(),
+            column_display: HashMap::new(),
+            show_view_position: false,
+            footer_aggregation: None,
+            footer_values: HashMap::new(),
+            footer_computed_for: None,
+            transposed: false,
+
+            goto_dialog_open: false,
+            goto_text: String::new(),
+            goto_by_position: false,
+            highlighted_row: None,
+            scroll_to_row: None,
+
+            focused_cell: None,
+            selected_rows: BTreeSet::new(),
+            selection_anchor: None,
+            selection_stats: None,
+            edit_mode: false,
+            editing_cell: None,
+            edited_cells: HashSet::new(),
+            added_rows: HashSet::new(),
+            deleted_rows: 0,
+            unsaved_action: None,
+            reload_pending: false,
+            close_confirmed: false,
+            timings: VecDeque::new(),
+            diagnostics_open: false,
+            diagnostics_detached: false,
+            query_plan: None,
+            script_open: false,
+            script_detached: false,
+            script_text: "df.head(100)".to_string(),
+            script_transcript: Vec::new(),
+            script_result: None,
+            focus_moved: false,
+            page_rows: 20,
+
+            search_open: false,
+            search_focus: false,
+            search_text: String::new(),
+            search_matches: Vec::new(),
+            search_current: None,
+            search_state: DerivedState::new(true),
+
+            bookmarks: BTreeSet::new(),
+            bookmark_positions: Vec::new(),
+            bookmark_positions_for: None,
+            show_minimap: true,
+            show_null_bars: true,
+
+            highlight_open: false,
+            highlight_pattern: String::new(),
+            highlight_column: None,
+            highlight_rows: false,
+            highlight_matches: Vec::new(),
+            highlight_row_set: BTreeSet::new(),
+            highlight_error: None,
+            highlight_state: DerivedState::new(true),
+
+            dictionary: DataDictionary::default(),
+            dictionary_path: None,
+            annotations: Annotations::default(),
+            annotations_path: None,
+            annotation_edit: None,
+            schema_dialog_open: false,
+            schema_detached: false,
+            schema_dialect: SqlDialect::PostgreSql,
+
+            metadata_open: false,
+            metadata_detached: false,
+            file_metadata: Vec::new(),
+            file_metadata_for: Vec::new(),
+            file_metadata_error: None,
+            row_group_selection: BTreeSet::new(),
+            row_group_stats: None,
+            source_columns: false,
+            file_watch: None,
+            instance_requests: None,
+            forwarded_files: None,
+
+            schema_compare_open: false,
+            schema_compare: Vec::new(),
+            schema_compare_for: Vec::new(),
+            schema_compare_differences_only: false,
+
+            schema_hash: None,
+            column_order: Vec::new(),
+            hidden_columns: HashSet::new(),
+            column_widths: HashMap::new(),
+
+            split_pane: DataFrameTable::default().id_salt("split_pane"),
+            grid: DataFrameTable::default().show_filter_bar(false),
+            split_open: false,
+            split_files: Vec::new(),
+            sync_scroll: true,
+            split_scroll_to: None,
+            first_visible_row: 0,
+            synced_rows: (0, 0),
+
+            diff_dialog_open: false,
+            diff_detached: false,
+            diff_other: None,
+            diff_keys: Vec::new(),
+            diff_result: None,
+            diff_statuses: vec![DiffStatus::Added, DiffStatus::Removed, DiffStatus::Changed],
+            diff_view: None,
+
+            epoch_columns: HashMap::new(),
+            epoch_hint_dismissed: false,
+
+            constant_columns: Vec::new(),
+            constant_hint_dismissed: false,
+        }
+    }
+
+    fn load_parquet_data(&mut self, paths: Vec<PathBuf>) {
+        self.save_layout();
+        self.dictionary = DataDictionary::default();
+        self.dictionary_path = paths.first().and_then(|p| DataDictionary::find_sidecar(p));
+        if let Some(path) = self.dictionary_path.clone() {
+            self.load_dictionary(&path);
+        }
+        self.load_annotations(&paths);
+        self.dataframe = None;
+        self.grid.clear();
+        self.original_dataframe = None;
+        self.column_names.clear();
+
+        let started = Instant::now();
+        let loaded = match &self.memory_file {
+            Some(bytes) => {
+                loader::read_parquet_bytes_indexed(bytes, ROW_INDEX_COLUMN, &self.row_limit)
+            }
+            None => loader::read_parquet_indexed(&paths, ROW_INDEX_COLUMN, &self.row_limit),
+        };
+        match loaded {
+            Ok(mut df_with_row_index) => {
+                if self.source_columns && paths.len() > 1 {
+                    let names: Vec<String> = paths.iter().map(|path| file_label(path)).collect();
+                    match metadata::read_metadata(&paths).and_then(|files| {
+                        metadata::with_source_columns(
+                            &df_with_row_index,
+                            ROW_INDEX_COLUMN,
+                            &names,
+                            &files,
+                        )
+                    }) {
+                        Ok(df) => df_with_row_index = df,
+                        Err(e) => self
+                            .notifications
+                            .error(format!("Source column error: {}", e)),
+                    }
+                }
+                let mut schema = df_with_row_index.schema().as_ref().clone();
+                schema.shift_remove(ROW_INDEX_COLUMN);
+                let schema_hash = layout::schema_hash(&schema);
+                self.column_names = df_with_row_index
+                    .get_column_names()
+                    .iter()
+                    .map(|s| s.to_string())
+                    .collect();
+                self.json_columns = json_view::detect_json_columns(&df_with_row_index)
+                    .into_iter()
+                    .collect();
+                self.epoch_columns =
+                    epoch::detect_epoch_columns(&df_with_row_index, ROW_INDEX_COLUMN)
+                        .into_iter()
+                        .collect();
+                self.epoch_hint_dismissed = false;
+                self.overview = Some(overview::compute(&df_with_row_index, ROW_INDEX_COLUMN));
+                self.constant_columns =
+                    constant::find_constant_columns(&df_with_row_index, ROW_INDEX_COLUMN)
+                        .unwrap_or_default();
+                self.constant_hint_dismissed = false;
+                self.bookmarks.clear();
+                self.editing_cell = None;
+                self.edited_cells.clear();
+                self.added_rows.clear();
+                self.deleted_rows = 0;
+                self.group_keys.clear();
+                self.group_aggregations.clear();
+                self.group_pane.clear();
+                self.pivot_index.clear();
+                self.pivot_on = None;
+                self.pivot_values = None;
+                self.pivot_pane.clear();
+                self.pivot_state.reset();
+                self.drill_down = None;
+                self.dataset_key = Some(workspace::dataset_key(&paths));
+                self.applied_steps.clear();
+                self.reload_pending = false;
+                self.bookmark_positions_for = None;
+                self.image_columns = images::detect_image_columns(&df_with_row_index)
+                    .into_iter()
+                    .collect();
+                self.image_cell = None;
+                self.forget_images = true;
+                self.original_dataframe = Some(df_with_row_index.clone());
+                self.set_view(df_with_row_index);
+                self.selected_filter_column = None;
+                self.filter_text.clear();
+                self.filter_expression.clear();
+                self.pii_findings.clear();
+                self.pii_state.reset();
+                self.similar_columns.clear();
+                self.similarity_state.reset();
+                self.describe_pane.clear();
+                self.describe_state.reset();
+                self.profiles.clear();
+                self.duplicate_keys.clear();
+                self.duplicates = None;
+                self.duplicate_state.reset();
+                self.duplicate_report = None;
+                self.computed_columns.clear();
+                self.split_column = None;
+                self.join_left_key = None;
+                self.unpivot_index.clear();
+                self.replace_column = None;
+                self.replace_preview = None;
+                self.unpivot_values.clear();
+                self.computed_error = None;
+                self.correlation_excluded.clear();
+                self.correlation = None;
+                self.correlation_state.reset();
+                self.scatter_x = None;
+                self.scatter_y = None;
+                self.scatter_color = None;
+                self.scatter = None;
+                self.scatter_state.reset();
+                self.line_x = None;
+                self.line_ys.clear();
+                self.line_chart = None;
+                self.line_state.reset();
+                self.histogram_column = None;
+                self.histogram = None;
+                self.histogram_state.reset();
+                self.bar_column = None;
+                self.bar_counts = None;
+                self.bar_state.reset();
+                self.box_value = None;
+                self.box_group = None;
+                self.boxes.clear();
+                self.box_state.reset();
+                self.profile_state.reset();
+                self.masked_columns.clear();
+                self.json_cell = None;
+                self.column_wrap.clear();
+                self.column_align.clear();
+                self.monospace_columns.clear();
+                self.column_display.clear();
+                self.sort_column = None;
+                self.schema_hash = Some(schema_hash);
+                if !self.safe_mode {
+                    self.apply_layout(layout::load(schema_hash).unwrap_or_default());
+                }
+                tracing::info!(
+                    files = paths.len(),
+                    rows = self
+                        .original_dataframe
+                        .as_ref()
+                        .map_or(0, DataFrame::height),
+                    columns = self.column_names.len(),
+                    "Loaded {}",
+                    paths
+                        .iter()
+                        .map(|path| path.display().to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+                self.record_timing("Load", started.elapsed());
+                self.run_hook(HookEvent::Load, &paths);
+            }
+            Err(e) => {
+                self.dataframe = None;
+                self.grid.clear();
+                self.original_dataframe = None;
+                self.column_names.clear();
+                self.notifications
+                    .error(format!("Error processing Parquet files: {}", e));
+                if paths.len() > 1 {
+                    self.refresh_schema_compare();
+                    let differing = self
+                        .schema_compare
+                        .iter()
+                        .filter(|column| !column.is_consistent())
+                        .count();
+                    if differing > 0 {
+                        self.notifications.warning(format!(
+                            "The selected files have different schemas ({} columns differ); see Compare schemas",
+                            differing
+                        ));
+                        self.schema_compare_open = true;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Replaces the visible data, invalidating views derived from it.
+    fn set_view(&mut self, df: DataFrame) {
+        self.grid.set_data(df.clone());
+        self.dataframe = Some(df);
+        self.data_version += 1;
+        self.highlighted_row = None;
+        self.focused_cell = None;
+        self.selected_rows.clear();
+        self.selection_anchor = None;
+        self.selection_stats = None;
+    }
+
+    /// Replaces the loaded data after an in-place transformation, keeping filter and sort.
+    /// New columns are appended to the column order; removed ones are dropped from it.
+    fn replace_data(&mut self, df: DataFrame, step: String) {
+        self.applied_steps.push(step);
+        let names: Vec<String> = df
+            .get_column_names()
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let remap = |idx: Option<usize>| {
+            idx.and_then(|i| self.column_names.get(i))
+                .and_then(|name| names.iter().position(|n| n == name))
+        };
+        self.sort_column = remap(self.sort_column);
+        self.selected_filter_column = remap(self.selected_filter_column);
+        self.column_order.retain(|name| names.contains(name));
+        for name in &names {
+            if !self.column_order.contains(name) {
+                self.column_order.push(name.clone());
+            }
+        }
+        self.hidden_columns.retain(|name| names.contains(name));
+        self.column_names = names;
+        self.overview = Some(overview::compute(&df, ROW_INDEX_COLUMN));
+        self.original_dataframe = Some(df.clone());
+        if self.is_filtered() {
+            self.apply_filter();
+        } else {
+            self.set_view(df);
+            self.apply_sort();
+        }
+    }
+
+    fn convert_epoch_column(&mut self, col_name: &str, unit: EpochUnit) {
+        let Some(df) = &self.original_dataframe else {
+            return;
+        };
+        match epoch::to_datetime(df, col_name, unit) {
+            Ok((df, out_of_range)) => {
+                if out_of_range > 0 {
+                    self.notifications.warning(format!(
+                        "{} values of {} are not dates between 1990 and 2100 in epoch {} and were left empty.",
+                        out_of_range, col_name, unit
+                    ));
+                }
+                self.epoch_columns.remove(col_name);
+                self.replace_data(df, format!("Convert {} from epoch {}", col_name, unit));
+            }
+            Err(e) => {
+                self.notifications.error(format!("Conversion error: {}", e));
+            }
+        }
+    }
+
+    fn cast_column(&mut self, col_name: &str, target: CastTarget) {
+        let Some(df) = &self.original_dataframe else {
+            return;
+        };
+        match cast::cast_column(df, col_name, target) {
+            Ok(df) => self.replace_data(df, format!("Cast {} to {}", col_name, target)),
+            Err(e) => self.notifications.error(format!("Cast error: {}", e)),
+        }
+    }
+
+    fn add_bucket_column(&mut self, col_name: &str, unit: BucketUnit) {
+        let Some(df) = &self.original_dataframe else {
+            return;
+        };
+        match bucket::add_bucket_column(df, col_name, unit, self.settings.fiscal_year_start_month) {
+            Ok(df) => {
+                self.replace_data(df, format!("Bucket {} by {}", col_name, unit));
+                // Place the new column next to its source rather than at the end.
+                let name = bucket::bucket_column_name(col_name, unit);
+                if let (Some(from), Some(source)) = (
+                    self.column_order.iter().position(|n| *n == name),
+                    self.column_order.iter().position(|n| n == col_name),
+                ) && from > source
+                {
+                    let moved = self.column_order.remove(from);
+                    self.column_order.insert(source + 1, moved);
+                }
+            }
+            Err(e) => {
+                self.notifications.error(format!("Bucketing error: {}", e));
+            }
+        }
+    }
+
+    fn render_constant_hint(&mut self, ui: &mut Ui) {
+        let constant: Vec<(String, String)> = self
+            .constant_columns
+            .iter()
+            .filter(|(name, _)| self.column_names.contains(name))
+            .cloned()
+            .collect();
+        if self.constant_hint_dismissed || constant.is_empty() {
+            return;
+        }
+        let all_hidden = constant
+            .iter()
+            .all(|(name, _)| self.hidden_columns.contains(name));
+        ui.horizontal_wrapped(|ui| {
+            let summary = constant::summary(&constant, 5);
+            if all_hidden {
+                ui.label(format!(
+                    "{} constant columns hidden: {}",
+                    constant.len(),
+                    summary
+                ));
+                if ui.button("Show").clicked() {
+                    for (name, _) in &constant {
+                        self.hidden_columns.remove(name);
+                    }
+                }
+            } else {
+                ui.label(format!(
+                    "{} columns have a single value: {}",
+                    constant.len(),
+                    summary
+                ));
+                if ui.button("Hide them").clicked() {
+                    self.hide_constant_columns();
+                }
+            }
+            if ui.small_button("Dismiss").clicked() {
+                self.constant_hint_dismissed = true;
+            }
+        });
+    }
+
+    fn hide_constant_columns(&mut self) {
+        for (name, _) in &self.constant_columns {
+            if self.column_names.contains(name) {
+                self.hidden_columns.insert(name.clone());
+            }
+        }
+        self.constant_hint_dismissed = false;
+    }
+
+    fn render_epoch_hint(&mut self, ui: &mut Ui) {
+        if self.epoch_hint_dismissed || self.epoch_columns.is_empty() {
+            return;
+        }
+        let mut detected: Vec<(String, EpochUnit)> = self
+            .epoch_columns
+            .iter()
+            .map(|(name, unit)| (name.clone(), *unit))
+            .collect();
+        detected.sort_by(|a, b| a.0.cmp(&b.0));
+        ui.horizontal_wrapped(|ui| {
+            ui.label("These columns look like epoch timestamps:");
+            for (name, unit) in detected {
+                if ui
+                    .button(format!("Convert {} ({})", name, unit))
+                    .on_hover_text("Convert to a UTC datetime column")
+                    .clicked()
+                {
+                    self.convert_epoch_column(&name, unit);
+                }
+            }
+            if ui.small_button("Dismiss").clicked() {
+                self.epoch_hint_dismissed = true;
+            }
+        });
+    }
+
+    /// Finds where the row with the given original index sits in the current view.
+    fn view_position_of(&self, row_index: IdxSize) -> PolarsResult<Option<usize>> {
+        let Some(df) = &self.dataframe else {
+            return Ok(None);
+        };
+        let indices = df.column(ROW_INDEX_COLUMN)?.idx()?;
+        Ok(indices.iter().position(|idx| idx == Some(row_index)))
+    }
+
+    fn go_to_row(&mut self) {
+        let cleaned: String = self
+            .goto_text
+            .chars()
+            .filter(|c| !matches!(c, ',' | '_' | ' '))
+            .collect();
+        let Ok(number) = cleaned.parse::<IdxSize>() else {
+            self.notifications
+                .error(format!("Not a row number: {}", self.goto_text));
+            return;
+        };
+        let num_rows = self.dataframe.as_ref().map_or(0, DataFrame::height);
+        let position = if self.goto_by_position {
+            // View positions are shown 1-based.
+            Ok((1..=num_rows as IdxSize)
+                .contains(&number)
+                .then(|| number as usize - 1))
+        } else {
+            self.view_position_of(number)
+        };
+        match position {
+            Ok(Some(position)) => {
+                self.highlighted_row = Some(position);
+                self.scroll_to_row = Some((position, Some(Align::Center)));
+                self.goto_dialog_open = false;
+            }
+            Ok(None) if self.goto_by_position => {
+                self.notifications.error(format!(
+                    "View position {} is out of range (1–{})",
+                    number, num_rows
+                ));
+            }
+            Ok(None) => {
+                self.notifications.error(format!(
+                    "Row {} is not in the current view; it may be filtered out",
+                    number
+                ));
+            }
+            Err(e) => self.notifications.error(format!("Go to row error: {}", e)),
+        }
+    }
+
+    fn apply_layout(&mut self, layout: ColumnLayout) {
+        self.column_order = layout
+            .order
+            .into_iter()
+            .filter(|name| self.column_names.contains(name))
+            .collect();
+        for name in &self.column_names {
+            if !self.column_order.contains(name) {
+                self.column_order.push(name.clone());
+            }
+        }
+        self.hidden_columns = layout
+            .hidden
+            .into_iter()
+            .filter(|name| self.column_names.contains(name))
+            .collect();
+        self.column_widths = layout.widths;
+    }
+
+    fn current_layout(&self) -> ColumnLayout {
+        let mut hidden: Vec<_> = self.hidden_columns.iter().cloned().collect();
+        hidden.sort();
+        ColumnLayout {
+            order: self.column_order.clone(),
+            hidden,
+            widths: self.column_widths.clone(),
+        }
+    }
+
+    fn save_layout(&mut self) {
+        // Safe mode ignores stored layouts, so it must not overwrite them either.
+        if !self.safe_mode
+            && let Some(schema_hash) = self.schema_hash
+            && let Err(e) = layout::save(schema_hash, &self.current_layout())
+        {
+            self.notifications
+                .error(format!("Could not save column layout: {}", e));
+        }
+    }
+
+    fn visible_columns(&self) -> Vec<String> {
+        self.column_order
+            .iter()
+            .filter(|name| !self.hidden_columns.contains(*name))
+            .cloned()
+            .collect()
+    }
+
+    fn move_column(&mut self, col_name: &str, offset: isize) {
+        let visible = self.visible_columns();
+        let Some(pos) = visible.iter().position(|name| name == col_name) else {
+            return;
+        };
+        let Some(neighbour) = pos
+            .checked_add_signed(offset)
+            .and_then(|target| visible.get(target))
+        else {
+            return;
+        };
+        let from = self
+            .column_order
+            .iter()
+            .position(|n| n == col_name)
+            .unwrap();
+        let to = self
+            .column_order
+            .iter()
+            .position(|n| n == neighbour)
+            .unwrap();
+        self.column_order.swap(from, to);
+    }
+
+    fn workspace(&self) -> Workspace {
+        Workspace {
+            filter_open: self.filter_dialog_open,
+            pii_open: self.pii_dialog_open,
+            pii_detached: self.pii_detached,
+            json_detached: self.json_detached,
+            export_open: self.export_dialog_open,
+            display_open: self.display_dialog_open,
+            settings_open: self.settings_dialog_open,
+            search_open: self.search_open,
+            schema_open: self.schema_dialog_open,
+            schema_detached: self.schema_detached,
+            notes_open: self.notes_open,
+            notes_detached: self.notes_detached,
+            diagnostics_open: self.diagnostics_open,
+            diagnostics_detached: self.diagnostics_detached,
+            script_open: self.script_open,
+            script_detached: self.script_detached,
+            diff_open: self.diff_dialog_open,
+            diff_detached: self.diff_detached,
+            pivot_open: self.pivot_open,
+            pivot_detached: self.pivot_detached,
+            group_open: self.group_dialog_open,
+            group_detached: self.group_detached,
+            correlation_open: self.correlation_open,
+            correlation_detached: self.correlation_detached,
+            scatter_open: self.scatter_open,
+            scatter_detached: self.scatter_detached,
+            line_open: self.line_open,
+            line_detached: self.line_detached,
+            histogram_open: self.histogram_open,
+            histogram_detached: self.histogram_detached,
+            bar_open: self.bar_open,
+            bar_detached: self.bar_detached,
+            box_open: self.box_open,
+            box_detached: self.box_detached,
+            profile_open: self.profile_open,
+            profile_detached: self.profile_detached,
+            describe_open: self.describe_open,
+            describe_detached: self.describe_detached,
+            similarity_open: self.similarity_dialog_open,
+            similarity_detached: self.similarity_detached,
+            metadata_open: self.metadata_open,
+            metadata_detached: self.metadata_detached,
+            split_files: self.split_files.clone(),
+            minimap_hidden: !self.show_minimap,
+            notes: self.notes.clone(),
+            // An unanswered prompt keeps the previous session for the next start.
+            session: self.session().or_else(|| self.session_prompt.clone()),
+        }
+    }
+
+    fn session(&self) -> Option<Session> {
+        self.original_dataframe.as_ref()?;
+        let name_of = |idx: Option<usize>| idx.map(|i| self.column_names[i].clone());
+        Some(Session {
+            files: self.files_to_load.clone(),
+            filter_column: name_of(self.selected_filter_column),
+            filter_type: self.filter_type,
+            filter_text: self.filter_text.clone(),
+            filter_expression: self.filter_expression.clone(),
+            sort: name_of(self.sort_column).map(|name| (name, self.sort_descending)),
+            masked_columns: self.masked_columns.iter().cloned().collect(),
+            first_visible_row: self.first_visible_row,
+        })
+    }
+
+    /// Restores the filter, sort, masking and scroll position of `session` on the loaded
+    /// data, skipping columns the files no longer have.
+    fn apply_session(&mut self, session: Session) {
+        let position = |name: Option<String>| {
+            name.and_then(|name| self.column_names.iter().position(|n| *n == name))
+        };
+        self.selected_filter_column = position(session.filter_column);
+        self.filter_type = session.filter_type;
+        self.filter_text = session.filter_text;
+        self.filter_expression = session.filter_expression;
+        let (sort_column, descending) = session.sort.unzip();
+        self.sort_column = position(sort_column);
+        self.sort_descending = descending.unwrap_or(false);
+        self.masked_columns = session
+            .masked_columns
+            .into_iter()
+            .filter(|name| self.column_names.contains(name))
+            .collect();
+        if self.is_filtered() {
+            self.apply_filter();
+        } else {
+            self.apply_sort();
+        }
+        self.scroll_to_row = Some((session.first_visible_row, Some(Align::TOP)));
+    }
+
+    fn render_session_prompt(&mut self, ctx: &Context) {
+        let Some(session) = &self.session_prompt else {
+            return;
+        };
+        // Files opened some other way in the meantime answer the question.
+        if !self.files_to_load.is_empty() {
+            self.session_prompt = None;
+            return;
+        }
+        let mut restore = None;
+        Window::new("Restore session?")
+            .auto_sized()
+            .collapsible(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label("These files were open when Tablr was last closed:");
+                for path in &session.files {
+                    ui.monospace(path.display().to_string());
+                }
+                ui.horizontal(|ui| {
+                    if ui.button("Restore").clicked() {
+                        restore = Some(true);
+                    }
+                    if ui.button("Start fresh").clicked() {
+                        restore = Some(false);
+                    }
+                });
+            });
+        if let Some(restore) = restore
+            && let Some(session) = self.session_prompt.take()
+            && restore
+        {
+            self.open_files(session.files.clone());
+            self.session_restore = Some(session);
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn restore_workspace(&mut self, workspace: Workspace) {
+        self.filter_dialog_open = workspace.filter_open;
+        self.pii_dialog_open = workspace.pii_open;
+        self.pii_detached = workspace.pii_detached;
+        self.json_detached = workspace.json_detached;
+        self.export_dialog_open = workspace.export_open;
+        self.display_dialog_open = workspace.display_open;
+        self.settings_dialog_open = workspace.settings_open;
+        self.search_open = workspace.search_open;
+        self.schema_dialog_open = workspace.schema_open;
+        self.schema_detached = workspace.schema_detached;
+        self.notes_open = workspace.notes_open;
+        self.notes_detached = workspace.notes_detached;
+        self.diagnostics_open = workspace.diagnostics_open;
+        self.diagnostics_detached = workspace.diagnostics_detached;
+        self.script_open = workspace.script_open;
+        self.script_detached = workspace.script_detached;
+        self.diff_dialog_open = workspace.diff_open;
+        self.diff_detached = workspace.diff_detached;
+        self.pivot_open = workspace.pivot_open;
+        self.pivot_detached = workspace.pivot_detached;
+        self.group_dialog_open = workspace.group_open;
+        self.group_detached = workspace.group_detached;
+        self.correlation_open = workspace.correlation_open;
+        self.correlation_detached = workspace.correlation_detached;
+        self.scatter_open = workspace.scatter_open;
+        self.scatter_detached = workspace.scatter_detached;
+        self.line_open = workspace.line_open;
+        self.line_detached = workspace.line_detached;
+        self.histogram_open = workspace.histogram_open;
+        self.histogram_detached = workspace.histogram_detached;
+        self.bar_open = workspace.bar_open;
+        self.bar_detached = workspace.bar_detached;
+        self.box_open = workspace.box_open;
+        self.box_detached = workspace.box_detached;
+        self.profile_open = workspace.profile_open;
+        self.profile_detached = workspace.profile_detached;
+        self.describe_open = workspace.describe_open;
+        self.describe_detached = workspace.describe_detached;
+        self.similarity_dialog_open = workspace.similarity_open;
+        self.similarity_detached = workspace.similarity_detached;
+        self.metadata_open = workspace.metadata_open;
+        self.metadata_detached = workspace.metadata_detached;
+        if workspace.split_files.iter().all(|path| path.is_file()) {
+            self.open_split_view(workspace.split_files);
+        }
+        self.show_minimap = !workspace.minimap_hidden;
+        self.notes = workspace.notes;
+        if self.files_to_load.is_empty() {
+            self.session_prompt = workspace
+                .session
+                .filter(|session| !session.files.is_empty());
+        }
+    }
+
+    /// Reads the first file's annotations sidecar, if it has one.
+    fn load_annotations(&mut self, paths: &[PathBuf]) {
+        self.annotations = Annotations::default();
+        self.annotation_edit = None;
+        self.annotations_path = paths.first().map(|path| Annotations::sidecar_path(path));
+        let Some(path) = self.annotations_path.clone().filter(|path| path.is_file()) else {
+            return;
+        };
+        match Annotations::load(&path) {
+            Ok(annotations) => self.annotations = annotations,
+            Err(e) => self.notifications.error(format!(
+                "Could not read annotations {}: {}",
+                path.display(),
+                e
+            )),
+        }
+    }
+
+    fn save_annotations(&mut self) {
+        let Some(path) = &self.annotations_path else {
+            return;
+        };
+        if let Err(e) = self.annotations.save(path) {
+            self.notifications
+                .error(format!("Could not save annotations: {}", e));
+        }
+    }
+
+    /// Whether the grid shows the annotations column, which needs a key column.
+    fn annotating(&self) -> bool {
+        !self.annotations.key_column.is_empty()
+            && self.column_names.contains(&self.annotations.key_column)
+    }
+
+    /// Columns before the data columns: the view position and the annotations.
+    fn leading_columns(&self) -> usize {
+        usize::from(self.show_view_position) + usize::from(self.annotating())
+    }
+
+    fn render_annotations_menu(&mut self, ui: &mut Ui) {
+        let has_annotations = !self.annotations.rows.is_empty();
+        ui.horizontal(|ui| {
+            ui.label("Key column");
+            ui.add_enabled_ui(!has_annotations, |ui| {
+                let selected = if self.annotations.key_column.is_empty() {
+                    "None"
+                } else {
+                    self.annotations.key_column.as_str()
+                };
+                let mut key = self.annotations.key_column.clone();
+                ComboBox::from_id_salt("annotation_key")
+                    .selected_text(selected)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut key, String::new(), "None");
+                        for name in &self.column_names {
+                            if name != ROW_INDEX_COLUMN {
+                                ui.selectable_value(&mut key, name.clone(), name);
+                            }
+                        }
+                    });
+                if key != self.annotations.key_column {
+                    self.annotations.key_column = key;
+                    self.save_annotations();
+                }
+            })
+            .response
+            .on_disabled_hover_text("Remove all annotations to key them by another column");
+        });
+        if let Some(path) = &self.annotations_path {
+            ui.weak(format!("Kept in {}", path.display()));
+        }
+        if has_annotations
+            && ui
+                .button(format!(
+                    "Remove all {} annotations",
+                    self.annotations.rows.len()
+                ))
+                .clicked()
+        {
+            self.annotations.rows.clear();
+            self.save_annotations();
+            ui.close_menu();
+        }
+    }
+
+    /// Note and tag of the row being annotated, saved to the sidecar when closed.
+    fn render_annotation_editor(&mut self, ctx: &Context) {
+        let Some((key, annotation)) = &mut self.annotation_edit else {
+            return;
+        };
+        let mut open = true;
+        let mut done = false;
+        Window::new(format!("{} = {}", self.annotations.key_column, key))
+            .id(egui::Id::new("annotation_editor"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.selectable_value(&mut annotation.tag, None, "No tag");
+                    for tag in Tag::ALL {
+                        ui.selectable_value(
+                            &mut annotation.tag,
+                            Some(tag),
+                            RichText::new("⏺").color(tag.color()),
+                        )
+                        .on_hover_text(tag.to_string());
+                    }
+                });
+                ui.add(
+                    egui::TextEdit::multiline(&mut annotation.note)
+                        .hint_text("Note")
+                        .desired_rows(4),
+                );
+                ui.horizontal(|ui| {
+                    if ui.button("Save").clicked() {
+                        done = true;
+                    }
+                    if ui.button("Remove").clicked() {
+                        *annotation = Annotation::default();
+                        done = true;
+                    }
+                });
+            });
+        if done {
+            if let Some((key, annotation)) = self.annotation_edit.take() {
+                self.annotations.set(key, annotation);
+                self.save_annotations();
+            }
+        } else if !open {
+            self.annotation_edit = None;
+        }
+    }
+
+    fn load_dictionary(&mut self, path: &std::path::Path) {
+        match DataDictionary::load(path) {
+            Ok(dictionary) => {
+                self.dictionary = dictionary;
+                self.dictionary_path = Some(path.to_path_buf());
+            }
+            Err(e) => self.notifications.error(format!(
+                "Could not read data dictionary {}: {}",
+                path.display(),
+                e
+            )),
+        }
+    }
+
+    fn process_pending_files(&mut self) {
+        if !self.files_loaded && !self.files_to_load.is_empty() {
+            self.load_parquet_data(self.files_to_load.clone());
+            self.files_loaded = true;
+            if self.original_dataframe.is_some()
+                && let Some(view) = self.startup_view.take()
+            {
+                self.apply_startup_view(view);
+            }
+            if self.original_dataframe.is_some()
+                && let Some(session) = self.session_restore.take()
+            {
+                self.apply_session(session);
+            }
+        }
+    }
+
+    fn watch_files(&mut self, ctx: &Context) {
+        let ctx = ctx.clone();
+        match watch::FileWatch::new(&self.files_to_load, move || ctx.request_repaint()) {
+            Ok(file_watch) => self.file_watch = Some(file_watch),
+            Err(e) => {
+                self.file_watch = None;
+                self.notifications
+                    .error(format!("Could not watch the files: {}", e));
+            }
+        }
+    }
+
+    fn poll_watched_files(&mut self, ctx: &Context) {
+        let Some(file_watch) = &mut self.file_watch else {
+            return;
+        };
+        if !file_watch.watches(&self.files_to_load) {
+            self.watch_files(ctx);
+            return;
+        }
+        if file_watch.poll() {
+            if self.has_edits() {
+                self.notifications.warning(
+                    "The files changed on disk. They were not read again, to keep your unsaved edits.",
+                );
+            } else if !self.applied_steps.is_empty() {
+                self.reload_pending = true;
+            } else {
+                self.reload_preserving_view();
+            }
+        } else if let Some(wait) = file_watch.settling() {
+            ctx.request_repaint_after(wait);
+        }
+    }
+
+    /// Reads the files again, keeping the filter, sort, masking and scroll position.
+    fn reload_preserving_view(&mut self) {
+        let session = self.session();
+        self.load_parquet_data(self.files_to_load.clone());
+        if self.original_dataframe.is_some()
+            && let Some(session) = session
+        {
+            self.apply_session(session);
+        }
+    }
+
+    /// Asks before a watched reload drops the steps applied to the data.
+    fn render_reload_prompt(&mut self, ctx: &Context) {
+        if !self.reload_pending {
+            return;
+        }
+        let (mut reload, mut keep) = (false, false);
+        Window::new("Files changed")
+            .auto_sized()
+            .collapsible(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label("The files changed on disk. Reading them again drops these steps:");
+                for step in &self.applied_steps {
+                    ui.label(format!("• {}", step));
+                }
+                ui.horizontal(|ui| {
+                    reload = ui.button("Reload").clicked();
+                    keep = ui
+                        .button("Keep current data")
+                        .on_hover_text("Ask again when the files next change")
+                        .clicked();
+                });
+            });
+        if reload {
+            self.reload_pending = false;
+            self.reload_preserving_view();
+        } else if keep {
+            self.reload_pending = false;
+        }
+    }
+
+    fn apply_startup_view(&mut self, view: StartupView) {
+        let missing: Vec<&String> = view
+            .columns
+            .iter()
+            .chain(view.sort.as_ref().map(|(name, _)| name))
+            .filter(|name| !self.column_names.contains(*name))
+            .collect();
+        if !missing.is_empty() {
+            let names: Vec<&str> = missing.iter().map(|name| name.as_str()).collect();
+            self.notifications
+                .error(format!("Unknown columns: {}", names.join(", ")));
+            return;
+        }
+        if let Some(text) = view.filter {
+            if let Err(e) = expression::parse(&text) {
+                self.notifications.error(format!("Filter error: {}", e));
+                return;
+            }
+            self.filter_expression = text;
+            self.apply_filter();
+        }
+        if !view.columns.is_empty() {
+            // The row index keeps its place and visibility; it is not a data column.
+            let rest: Vec<String> = self
+                .column_order
+                .iter()
+                .filter(|name| *name != ROW_INDEX_COLUMN && !view.columns.contains(name))
+                .cloned()
+                .collect();
+            self.hidden_columns.retain(|name| name == ROW_INDEX_COLUMN);
+            self.hidden_columns.extend(rest.iter().cloned());
+            let index = self
+                .column_order
+                .iter()
+                .filter(|name| *name == ROW_INDEX_COLUMN)
+                .cloned();
+            self.column_order = index.chain(view.columns).chain(rest).collect();
+        }
+        if let Some((name, descending)) = view.sort {
+            self.sort_column = self.column_names.iter().position(|n| *n == name);
+            self.sort_descending = descending;
+            self.apply_sort();
+        }
+    }
+
+    fn render_file_selector(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            if ui.button("Browse...").clicked() {
+                self.handle_browse_button_click(ui.ctx());
+            }
+
+            if self.files_to_load.is_empty() {
+                ui.label("No parquet files selected");
+            } else if self.files_to_load.len() == 1 {
+                ui.label(format!(
+                    "Selected: {}",
+                    file_label(&self.files_to_load[0])
+                ));
+            } else {
+                ui.label(format!("Selected: {} files", self.files_to_load.len()));
+                if ui
+                    .button("Compare schemas")
+                    .on_hover_text("Columns and types of each selected file")
+                    .clicked()
+                {
+                    self.schema_compare_open = true;
+                }
+            }
+            let mut watching = self.file_watch.is_some();
+            if ui
+                .add_enabled(
+                    !self.files_to_load.is_empty(),
+                    egui::Checkbox::new(&mut watching, "Watch"),
+                )
+                .on_hover_text(
+                    "Reload when the files change on disk, keeping filter, sort and scroll position; asks first if steps were applied",
+                )
+                .changed()
+            {
+                if watching {
+                    self.watch_files(&ui.ctx().clone());
+                } else {
+                    self.file_watch = None;
+                }
+            }
+
+            ui.separator();
+
+            ui.add_enabled_ui(self.dataframe.is_some(), |ui| {
+                if ui.button("Filter").clicked() {
+                    self.filter_dialog_open = true;
+                }
+                if ui.button("Schema").clicked() {
+                    self.schema_dialog_open = true;
+                }
+                if ui.button("Metadata").clicked() {
+                    self.metadata_open = true;
+                }
+                if ui.button("PII Scan").clicked() {
+                    self.pii_dialog_open = true;
+                }
+                if ui.button("Export").clicked() {
+                    self.export_dialog_open = true;
+                }
+                if ui.button("Print…").clicked() {
+                    self.handle_print();
+                }
+                if ui.button("Find").clicked() {
+                    self.search_open = true;
+                    self.search_focus = true;
+                }
+                if ui.button("Highlight").clicked() {
+                    self.highlight_open = true;
+                }
+                if ui.button("Notes").clicked() {
+                    self.notes_open = true;
+                }
+                if ui.button("Side by side…").clicked() {
+                    self.handle_compare_button_click();
+                }
+                if ui.button("Compare files…").clicked() {
+                    self.diff_dialog_open = true;
+                }
+            });
+
+            ui.add_enabled_ui(self.dataframe.is_some(), |ui| {
+                ui.menu_button("Rows", |ui| self.render_rows_menu(ui));
+                ui.menu_button("Analyze", |ui| self.render_analyze_menu(ui));
+                ui.menu_button("Columns", |ui| self.render_columns_menu(ui));
+                ui.toggle_value(&mut self.transposed, "Transpose")
+                    .on_hover_text("Show columns as rows, for wide tables with few rows");
+                if ui
+                    .toggle_value(&mut self.edit_mode, "Edit")
+                    .on_hover_text("Double-click a cell to change it")
+                    .changed()
+                    && !self.edit_mode
+                {
+                    self.editing_cell = None;
+                }
+                if self.edit_mode {
+                    if ui
+                        .button("Add row")
+                        .on_hover_text("Insert a blank row below the focused one")
+                        .clicked()
+                    {
+                        self.add_blank_row();
+                    }
+                    let rows = self.edit_target_rows().len();
+                    if ui
+                        .add_enabled(rows > 0, egui::Button::new(format!("Delete rows ({})", rows)))
+                        .on_hover_text("Delete the selected rows, or the focused one")
+                        .clicked()
+                    {
+                        self.delete_rows();
+                    }
+                }
+            });
+
+            if ui.button("Display").clicked() {
+                self.display_dialog_open = true;
+            }
+            if ui.button("Settings").clicked() {
+                self.settings_dialog_open = true;
+            }
+        });
+    }
+
+    /// Reorders all columns by a preset, keeping the row index first.
+    fn order_columns_by(&mut self, preset: OrderPreset) {
+        let Some(df) = &self.original_dataframe else {
+            return;
+        };
+        let (mut index, mut others): (Vec<String>, Vec<String>) = self
+            .column_order
+            .iter()
+            .cloned()
+            .partition(|name| name == ROW_INDEX_COLUMN);
+        match preset {
+            OrderPreset::Alphabetical => {
+                others.sort_by_key(|name| name.to_lowercase());
+            }
+            OrderPreset::NumericFirst => {
+                others.sort_by_key(|name| {
+                    df.column(name)
+                        .map(|column| !column.dtype().is_primitive_numeric())
+                        .unwrap_or(true)
+                });
+            }
+            OrderPreset::MostPopulated => {
+                others.sort_by_key(|name| {
+                    df.column(name)
+                        .map(|column| column.null_count())
+                        .unwrap_or(usize::MAX)
+                });
+            }
+        }
+        index.extend(others);
+        self.column_order = index;
+    }
+
+    /// Runs an action bound to a keyboard shortcut.
+    fn run_action(&mut self, ctx: &Context, action: Action) {
+        let has_data = self.dataframe.is_some();
+        match action {
+            Action::Open => self.handle_browse_button_click(ctx),
+            Action::Search if has_data => {
+                self.search_open = true;
+                self.search_focus = true;
+            }
+            Action::Export if has_data => {
+                let masked =
+                    !self.masked_columns.is_empty() && self.export_mode == ExportMode::Masked;
+                self.handle_export(masked, self.settings.export_format, false);
+            }
+            Action::Filter if has_data => self.filter_dialog_open = true,
+            Action::GoToRow if has_data => self.goto_dialog_open = true,
+            Action::ToggleBookmark => self.toggle_bookmarks(),
+            Action::Settings => self.settings_dialog_open = true,
+            Action::Search | Action::Export | Action::Filter | Action::GoToRow => {}
+        }
+    }
+
+    fn record_timing(&mut self, operation: &'static str, elapsed: Duration) {
+        tracing::info!("{} took {:.1?}", operation, elapsed);
+        if self.timings.len() == MAX_TIMINGS {
+            self.timings.pop_front();
+        }
+        self.timings.push_back((operation, elapsed));
+    }
+
+    fn run_script(&mut self) {
+        let Some(df) = &self.original_dataframe else {
+            return;
+        };
+        self.script_transcript
+            .push((ScriptLine::Input, self.script_text.trim_end().to_string()));
+        let started = Instant::now();
+        let result = script::run(&self.script_text, df);
+        self.record_timing("Script", started.elapsed());
+        match result {
+            Ok(output) => {
+                for line in output.printed {
+                    self.script_transcript.push((ScriptLine::Printed, line));
+                }
+                if let Some(df) = &output.frame {
+                    let (rows, columns) = df.shape();
+                    self.script_transcript.push((
+                        ScriptLine::Printed,
+                        format!("Result: {} rows × {} columns", rows, columns),
+                    ));
+                }
+                self.script_result = output.frame;
+            }
+            Err(e) => {
+                self.script_transcript.push((ScriptLine::Error, e));
+                self.script_result = None;
+            }
+        }
+    }
+
+    /// Replaces the loaded data with the script's result. Results without the row index,
+    /// such as aggregations, are numbered afresh.
+    fn apply_script_result(&mut self) {
+        let Some(df) = self.script_result.take() else {
+            return;
+        };
+        let df = if df.column(ROW_INDEX_COLUMN).is_ok() {
+            Ok(df)
+        } else {
+            df.with_row_index(ROW_INDEX_COLUMN.into(), None)
+        };
+        match df {
+            Ok(df) => {
+                let step = format!(
+                    "Run script: {}",
+                    self.script_text.lines().next().unwrap_or_default()
+                );
+                self.replace_data(df, step);
+            }
+            Err(e) => self.notifications.error(format!("Script error: {}", e)),
+        }
+    }
+
+    fn render_script_console(&mut self, ctx: &Context) {
+        let mut open = self.script_open;
+        let mut detached = self.script_detached;
+        let mut run = false;
+        let mut apply = false;
+        panels::show_panel(
+            ctx,
+            "Script console",
+            [560.0, 480.0],
+            &mut open,
+            &mut detached,
+            |ui| {
+                ui.horizontal(|ui| {
+                    ui.weak("Rhai, with the working data as df");
+                    ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                        if ui.button("Clear").clicked() {
+                            self.script_transcript.clear();
+                        }
+                    });
+                });
+                ScrollArea::vertical()
+                    .max_height(ui.available_height() - 180.0)
+                    .auto_shrink([false, false])
+                    .stick_to_bottom(true)
+                    .show(ui, |ui| {
+                        for (kind, text) in &self.script_transcript {
+                            match kind {
+                                ScriptLine::Input => {
+                                    for (i, line) in text.lines().enumerate() {
+                                        let prompt = if i == 0 { ">" } else { "." };
+                                        ui.monospace(
+                                            RichText::new(format!("{} {}", prompt, line)).strong(),
+                                        );
+                                    }
+                                }
+                                ScriptLine::Printed => {
+                                    ui.monospace(text);
+                                }
+                                ScriptLine::Error => {
+                                    ui.colored_label(
+                                        ui.visuals().error_fg_color,
+                                        RichText::new(text).monospace(),
+                                    );
+                                }
+                            }
+                        }
+                    });
+                ui.separator();
+                let response = ui.add(
+                    egui::TextEdit::multiline(&mut self.script_text)
+                        .code_editor()
+                        .desired_rows(5)
+                        .desired_width(f32::INFINITY),
+                );
+                if response.has_focus()
+                    && ui.input_mut(|i| i.consume_key(Modifiers::COMMAND, Key::Enter))
+                {
+                    run = true;
+                }
+                ui.horizontal(|ui| {
+                    run |= ui
+                        .add_enabled(self.original_dataframe.is_some(), egui::Button::new("Run"))
+                        .on_hover_text("Ctrl+Enter")
+                        .clicked();
+                    if let Some(df) = &self.script_result {
+                        let (rows, columns) = df.shape();
+                        apply = ui
+                            .button(format!("Apply {} × {} to the grid", rows, columns))
+                            .clicked();
+                        if ui.button("Discard").clicked() {
+                            self.script_result = None;
+                        }
+                    }
+                });
+                ui.collapsing("Functions", |ui| {
+                    ui.monospace(script::HELP);
+                });
+            },
+        );
+        if run {
+            self.run_script();
+        }
+        if apply {
+            self.apply_script_result();
+        }
+        self.script_open = open;
+        self.script_detached = detached;
+    }
+
+    /// Toggles a bookmark on the selected rows, or on the focused row when nothing is selected.
+    /// Bookmarks remember the original row so they survive sorting and filtering.
+    fn toggle_bookmarks(&mut self) {
+        let Some(index) = self
+            .dataframe
+            .as_ref()
+            .and_then(|df| df.column(ROW_INDEX_COLUMN).ok())
+        else {
+            return;
+        };
+        let positions: Vec<usize> = if self.selected_rows.is_empty() {
+            self.focused_cell.iter().map(|(row, _)| *row).collect()
+        } else {
+            self.selected_rows.iter().copied().collect()
+        };
+        let row_indices: Vec<IdxSize> = positions
+            .iter()
+            .filter_map(|position| index.get(*position).ok()?.extract::<IdxSize>())
+            .collect();
+        let add = row_indices.iter().any(|r| !self.bookmarks.contains(r));
+        for row_index in row_indices {
+            if add {
+                self.bookmarks.insert(row_index);
+            } else {
+                self.bookmarks.remove(&row_index);
+            }
+        }
+        self.bookmark_positions_for = None;
+    }
+
+    fn update_bookmark_positions(&mut self) {
+        if self.bookmark_positions_for == Some(self.data_version) {
+            return;
+        }
+        self.bookmark_positions = match self
+            .dataframe
+            .as_ref()
+            .and_then(|df| df.column(ROW_INDEX_COLUMN).ok())
+            .and_then(|index| index.idx().ok())
+        {
+            Some(index) if !self.bookmarks.is_empty() => index
+                .into_iter()
+                .enumerate()
+                .filter(|(_, row_index)| row_index.is_some_and(|r| self.bookmarks.contains(&r)))
+                .map(|(position, _)| position)
+                .collect(),
+            _ => Vec::new(),
+        };
+        self.bookmark_positions_for = Some(self.data_version);
+    }
+
+    /// Draws a strip beside the table with markers for search matches, highlighted rows
+    /// and bookmarks; clicking or dragging on it scrolls there.
+    fn render_minimap(&mut self, ui: &mut Ui) {
+        let Some(num_rows) = self.dataframe.as_ref().map(DataFrame::height) else {
+            return;
+        };
+        let (rect, response) = ui.allocate_exact_size(ui.available_size(), Sense::click_and_drag());
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+        if num_rows == 0 {
+            return;
+        }
+        let y_of = |row: usize| rect.top() + rect.height() * row as f32 / num_rows as f32;
+        let mark = |row: usize, color: Color32, inset: f32| {
+            let y = y_of(row);
+            painter.rect_filled(
+                egui::Rect::from_min_max(
+                    egui::pos2(rect.left() + inset, y),
+                    egui::pos2(rect.right() - inset, y + 2.0),
+                ),
+                0.0,
+                color,
+            );
+        };
+
+        let viewport = egui::Rect::from_min_max(
+            egui::pos2(rect.left(), y_of(self.first_visible_row)),
+            egui::pos2(
+                rect.right(),
+                y_of((self.first_visible_row + self.page_rows).min(num_rows))
+                    .max(y_of(self.first_visible_row) + 4.0),
+            ),
+        );
+        painter.rect_filled(viewport, 2.0, ui.visuals().widgets.inactive.bg_fill);
+
+        let mut last_row = None;
+        for (row, _) in &self.highlight_matches {
+            if last_row != Some(*row) {
+                mark(*row, Color32::from_rgb(0, 160, 255), 2.0);
+                last_row = Some(*row);
+            }
+        }
+        for (row, _) in &self.search_matches {
+            mark(*row, Color32::from_rgb(255, 200, 0), 2.0);
+        }
+        for row in &self.bookmark_positions {
+            mark(*row, BOOKMARK_COLOR, 0.0);
+        }
+
+        if (response.clicked() || response.dragged())
+            && let Some(pointer) = response.interact_pointer_pos()
+        {
+            let fraction = ((pointer.y - rect.top()) / rect.height()).clamp(0.0, 1.0);
+            let row = ((fraction * num_rows as f32) as usize).min(num_rows - 1);
+            self.scroll_to_row = Some((row, Some(Align::Center)));
+        }
+        response.on_hover_text("Search matches, highlighted rows and bookmarks (Ctrl+B)");
+    }
+
+    /// Copies the selected rows, or the rows on screen, as a Markdown table.
+    fn copy_as_markdown(&mut self, ctx: &Context) {
+        let Some(df) = &self.dataframe else {
+            return;
+        };
+        let rows: Vec<usize> = if self.selected_rows.is_empty() {
+            (self.first_visible_row..(self.first_visible_row + self.page_rows).min(df.height()))
+                .collect()
+        } else {
+            self.selected_rows.iter().copied().collect()
+        };
+        let columns: Vec<String> = self
+            .visible_columns()
+            .into_iter()
+            .filter(|name| name != ROW_INDEX_COLUMN)
+            .collect();
+        match markdown::table(df, &columns, rows, |name, value| {
+            self.format_cell(name, value)
+        }) {
+            Ok(text) => ctx.copy_text(text),
+            Err(e) => self.notifications.error(format!("Copy error: {}", e)),
+        }
+    }
+
+    fn render_rows_menu(&mut self, ui: &mut Ui) {
+        let copy_label = if self.selected_rows.is_empty() {
+            "Copy visible rows as Markdown"
+        } else {
+            "Copy selection as Markdown"
+        };
+        if ui.button(copy_label).clicked() {
+            ui.close_menu();
+            self.copy_as_markdown(ui.ctx());
+        }
+        if ui
+            .button("Append file…")
+            .on_hover_text(format!(
+                "Add the rows of other files, marking their origin in {}",
+                append::SOURCE_COLUMN
+            ))
+            .clicked()
+        {
+            ui.close_menu();
+            self.handle_append_files();
+        }
+        ui.menu_button("Annotations", |ui| self.render_annotations_menu(ui));
+        ui.separator();
+        let mut limit = None;
+        if ui
+            .radio(self.row_limit == RowLimit::All, "All rows")
+            .clicked()
+        {
+            limit = Some(RowLimit::All);
+        }
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.add(egui::DragValue::new(&mut self.edge_rows).range(1..=usize::MAX));
+            ui.label("rows");
+            if ui.button("First").clicked() {
+                limit = Some(RowLimit::Head(self.edge_rows));
+            }
+            if ui.button("Last").clicked() {
+                limit = Some(RowLimit::Tail(self.edge_rows));
+            }
+        });
+        ui.separator();
+        ui.label("Random sample")
+            .on_hover_text("Rows come from randomly chosen row groups, so only those are read");
+        egui::Grid::new("sample_options").show(ui, |ui| {
+            ui.label("Rows");
+            ui.add(egui::DragValue::new(&mut self.sample_rows).range(1..=usize::MAX));
+            ui.end_row();
+            ui.label("Seed");
+            ui.add(egui::DragValue::new(&mut self.sample_seed));
+            ui.end_row();
+        });
+        if ui.button("Load sample").clicked() {
+            limit = Some(RowLimit::Sample {
+                rows: self.sample_rows,
+                seed: self.sample_seed,
+            });
+        }
+        if let Some(limit) = limit {
+            ui.close_menu();
+            if limit != self.row_limit {
+                self.after_unsaved_edits(ui.ctx(), UnsavedAction::SetRowLimit(limit));
+            }
+        }
+    }
+
+    fn render_analyze_menu(&mut self, ui: &mut Ui) {
+        if ui.button("Correlation matrix…").clicked() {
+            self.correlation_open = true;
+            ui.close_menu();
+        }
+        if ui.button("Scatter plot…").clicked() {
+            self.scatter_open = true;
+            ui.close_menu();
+        }
+        if ui.button("Plot over time…").clicked() {
+            self.line_open = true;
+            ui.close_menu();
+        }
+        if ui.button("Histogram…").clicked() {
+            self.histogram_open = true;
+            ui.close_menu();
+        }
+        if ui.button("Value counts…").clicked() {
+            self.bar_open = true;
+            ui.close_menu();
+        }
+        if ui.button("Box plots by group…").clicked() {
+            self.box_open = true;
+            ui.close_menu();
+        }
+        if ui.button("Find duplicates…").clicked() {
+            self.duplicates_open = true;
+            ui.close_menu();
+        }
+        if ui.button("Profile dataset…").clicked() {
+            self.profile_open = true;
+            ui.close_menu();
+        }
+        if ui.button("Describe…").clicked() {
+            self.describe_open = true;
+            ui.close_menu();
+        }
+        if ui.button("Computed columns…").clicked() {
+            self.computed_open = true;
+            ui.close_menu();
+        }
+        if ui.button("Find and replace…").clicked() {
+            self.replace_open = true;
+            ui.close_menu();
+        }
+        if ui.button("Unpivot…").clicked() {
+            self.unpivot_open = true;
+            ui.close_menu();
+        }
+        if ui.button("Join…").clicked() {
+            self.join_open = true;
+            ui.close_menu();
+        }
+        if ui.button("Group by…").clicked() {
+            self.group_dialog_open = true;
+            ui.close_menu();
+        }
+        if ui.button("Pivot…").clicked() {
+            self.pivot_open = true;
+            ui.close_menu();
+        }
+        if ui.button("Duplicate columns…").clicked() {
+            self.similarity_dialog_open = true;
+            ui.close_menu();
+        }
+        if ui.button("Script console…").clicked() {
+            self.script_open = true;
+            ui.close_menu();
+        }
+        let constant_label = format!("Hide constant columns ({})", self.constant_columns.len());
+        if ui
+            .add_enabled(
+                !self.constant_columns.is_empty(),
+                egui::Button::new(constant_label),
+            )
+            .clicked()
+        {
+            self.hide_constant_columns();
+            ui.close_menu();
+        }
+    }
+
+    fn render_columns_menu(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            if ui.button("Show all").clicked() {
+                self.hidden_columns.clear();
+            }
+            if ui.button("Reset layout").clicked() {
+                self.apply_layout(ColumnLayout::default());
+            }
+            if ui
+                .add_enabled(
+                    !self.hidden_columns.is_empty(),
+                    egui::Button::new("Drop hidden"),
+                )
+                .on_hover_text("Remove the hidden columns from the data and exports")
+                .clicked()
+            {
+                self.drop_hidden_columns();
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Order:");
+            for preset in OrderPreset::ALL {
+                if ui.button(preset.to_string()).clicked() {
+                    self.order_columns_by(preset);
+                }
+            }
+        });
+        if ui
+            .add_enabled(
+                self.files_to_load.len() > 1,
+                egui::Checkbox::new(&mut self.source_columns, "Source file columns"),
+            )
+            .on_hover_text(format!(
+                "Add {} and {} naming where each row was read from",
+                append::SOURCE_COLUMN,
+                metadata::SOURCE_ROW_GROUP_COLUMN
+            ))
+            .changed()
+        {
+            // Applied once any edits are dealt with.
+            self.source_columns = !self.source_columns;
+            let action = UnsavedAction::SetSourceColumns(!self.source_columns);
+            self.after_unsaved_edits(ui.ctx(), action);
+        }
+        ui.separator();
+        ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+            for name in self.column_order.clone() {
+                let mut visible = !self.hidden_columns.contains(&name);
+                if ui.checkbox(&mut visible, &name).changed() {
+                    if visible {
+                        self.hidden_columns.remove(&name);
+                    } else {
+                        self.hidden_columns.insert(name);
+                    }
+                }
+            }
+        });
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn handle_browse_button_click(&mut self, ctx: &Context) {
+        self.picked_files = Some(web::pick_parquet(ctx));
+    }
+
+    /// Opens what the browser's file picker returned, once it has been read.
+    #[cfg(target_arch = "wasm32")]
+    fn receive_picked_files(&mut self, ctx: &Context) {
+        let Some(receiver) = &self.picked_files else {
+            return;
+        };
+        match receiver.try_recv() {
+            Ok((name, bytes)) => {
+                self.picked_files = None;
+                self.after_unsaved_edits(ctx, UnsavedAction::OpenBytes(name, bytes.into()));
+            }
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => self.picked_files = None,
+            Err(std::sync::mpsc::TryRecvError::Empty) => {}
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn handle_browse_button_click(&mut self, ctx: &Context) {
+        if let Some(paths) = FileDialog::new()
+            .add_filter("Parquet files", &["parquet"])
+            .pick_files()
+        {
+            if paths.is_empty() {
+                self.notifications
+                    .warning("No files selected. Please select at least one Parquet file.");
+            } else {
+                self.after_unsaved_edits(ctx, UnsavedAction::Open(paths));
+            }
+        }
+    }
+
+    fn open_files(&mut self, paths: Vec<PathBuf>) {
+        self.files_to_load = paths;
+        self.memory_file = None;
+        self.files_loaded = false;
+        // Row groups are chosen per file, so the choice does not carry over.
+        if matches!(self.row_limit, RowLimit::RowGroups(_)) {
+            self.row_limit = RowLimit::All;
+        }
+    }
+
+    /// Opens a file from its contents; `name` stands in for its path in titles and
+    /// saved layouts.
+    fn open_bytes(&mut self, name: String, bytes: Arc<[u8]>) {
+        self.open_files(vec![PathBuf::from(name)]);
+        self.memory_file = Some(bytes);
+    }
+
+    /// Opens Parquet files dropped onto the window. Native drops come with a path;
+    /// browser drops only with their contents.
+    fn receive_dropped_files(&mut self, ctx: &Context) {
+        let dropped = ctx.input(|i| i.raw.dropped_files.clone());
+        if dropped.is_empty() {
+            return;
+        }
+        let paths: Vec<PathBuf> = dropped
+            .iter()
+            .filter_map(|file| file.path.clone())
+            .collect();
+        if !paths.is_empty() {
+            self.after_unsaved_edits(ctx, UnsavedAction::Open(paths));
+        } else if let Some(file) = dropped.into_iter().find(|file| file.bytes.is_some()) {
+            let action = UnsavedAction::OpenBytes(file.name, file.bytes.unwrap());
+            self.after_unsaved_edits(ctx, action);
+        }
+    }
+
+    /// Starts accepting files from later launches, if not already doing so.
+    fn listen_for_instances(&mut self, ctx: &Context) {
+        if self.instance_requests.is_some() {
+            return;
+        }
+        let ctx = ctx.clone();
+        match instance::listen(move || ctx.request_repaint()) {
+            Ok(receiver) => self.instance_requests = Some(receiver),
+            Err(e) => self
+                .notifications
+                .error(format!("Single instance error: {}", e)),
+        }
+    }
+
+    fn receive_instance_requests(&mut self, ctx: &Context) {
+        let Some(receiver) = &self.instance_requests else {
+            return;
+        };
+        // Only the latest launch counts when several arrive between frames.
+        if let Some(paths) = receiver.try_iter().last() {
+            if self.files_to_load.is_empty() {
+                self.open_files(paths);
+            } else {
+                self.forwarded_files = Some(paths);
+            }
+            ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+        }
+    }
+
+    /// Asks whether files sent by another launch replace the open ones or get a window
+    /// of their own.
+    fn render_forwarded_files_prompt(&mut self, ctx: &Context) {
+        let Some(paths) = &self.forwarded_files else {
+            return;
+        };
+        let mut choice = None;
+        let mut ignore = false;
+        Window::new("Open files?")
+            .auto_sized()
+            .collapsible(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label("Another launch of Tablr asked to open:");
+                for path in paths {
+                    ui.monospace(path.display().to_string());
+                }
+                ui.horizontal(|ui| {
+                    if ui.button("Open here").clicked() {
+                        choice = Some(true);
+                    }
+                    if ui
+                        .button("Open in new window")
+                        .on_hover_text("Keep the open files and start another window")
+                        .clicked()
+                    {
+                        choice = Some(false);
+                    }
+                    ignore = ui.button("Ignore").clicked();
+                });
+            });
+        if ignore {
+            self.forwarded_files = None;
+        }
+        let Some(here) = choice else {
+            return;
+        };
+        let paths = self.forwarded_files.take().unwrap_or_default();
+        if here {
+            self.after_unsaved_edits(ctx, UnsavedAction::Open(paths));
+        } else if let Err(e) = std::env::current_exe().and_then(|exe| {
+            std::process::Command::new(exe)
+                .arg("view")
+                .arg("--new-window")
+                .args(&paths)
+                .spawn()
+        }) {
+            self.notifications
+                .error(format!("Could not open a new window: {}", e));
+        }
+    }
+
+    fn apply_sort(&mut self) {
+        if let (Some(df), Some(col_idx)) = (&self.dataframe, self.sort_column) {
+            let col_name = &self.column_names[col_idx];
+            let started = Instant::now();
+
+            match df.sort(
+                vec![PlSmallStr::from(col_name)],
+                SortMultipleOptions::new().with_order_descending(self.sort_descending),
+            ) {
+                Ok(sorted_df) => {
+                    self.set_view(sorted_df);
+                    self.record_timing("Sort", started.elapsed());
+                }
+                Err(e) => {
+                    self.notifications.error(format!("Sort error: {}", e));
+                }
+            }
+        }
+    }
+
+    fn is_filtered(&self) -> bool {
+        self.selected_filter_column.is_some() || !self.filter_expression.is_empty()
+    }
+
+    fn apply_filter(&mut self) {
+        let Some(original_df) = &self.original_dataframe else {
+            return;
+        };
+        let started = Instant::now();
+        let mut filtered = Ok(original_df.clone());
+        if !self.filter_expression.is_empty() {
+            filtered = expression::parse(&self.filter_expression)
+                .and_then(|expr| original_df.clone().lazy().filter(expr).collect());
+        }
+        if let Some(col_idx) = self.selected_filter_column {
+            let col_name = &self.column_names[col_idx];
+            filtered = filtered.and_then(|df| {
+                filter::filter_frame(&df, col_name, self.filter_type, &self.filter_text)
+            });
+        }
+
+        match filtered {
+            Ok(filtered_df) => {
+                self.drill_down = None;
+                self.set_view(filtered_df);
+            }
+            Err(e) => {
+                self.notifications.error(format!("Filter error: {}", e));
+                self.set_view(original_df.clone());
+            }
+        }
+
+        if self.sort_column.is_some() {
+            self.apply_sort();
+        }
+        self.record_timing("Filter", started.elapsed());
+    }
+
+    fn render_filter_dialog(&mut self, ctx: &Context) {
+        let mut open = self.filter_dialog_open;
+        Window::new("Filter")
+            .auto_sized()
+            .collapsible(false)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.horizontal_top(|ui| {
+                    ComboBox::from_id_salt("filter_column")
+                        .selected_text(
+                            self.selected_filter_column
+                                .map(|idx| self.column_names[idx].as_str())
+                                .unwrap_or("Select column"),
+                        )
+                        .show_ui(ui, |ui| {
+                            let column_names = self.column_names.clone();
+                            for (idx, col_name) in column_names.iter().enumerate() {
+                                if ui
+                                    .selectable_value(
+                                        &mut self.selected_filter_column,
+                                        Some(idx),
+                                        col_name,
+                                    )
+                                    .clicked()
+                                {
+                                    self.apply_filter();
+                                }
+                            }
+                        });
+
+                    ui.add_enabled_ui(self.selected_filter_column.is_some(), |ui| {
+                        if filter::filter_type_combo(ui, "filter_type", &mut self.filter_type) {
+                            self.apply_filter();
+                        }
+
+                        let response = ui.text_edit_singleline(&mut self.filter_text);
+                        if response.changed() {
+                            self.apply_filter();
+                        }
+                    });
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Expression");
+                    let response = ui
+                        .text_edit_singleline(&mut self.filter_expression)
+                        .on_hover_text(
+                            "Keep rows where this is true, e.g. col(\"x\") > 5; Enter applies",
+                        );
+                    if response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter)) {
+                        self.apply_filter();
+                    }
+                    if ui
+                        .add_enabled(self.is_filtered(), egui::Button::new("Clear Filter"))
+                        .clicked()
+                    {
+                        self.selected_filter_column = None;
+                        self.filter_text.clear();
+                        self.filter_expression.clear();
+                        self.apply_filter();
+                    }
+                });
+            });
+        self.filter_dialog_open = open;
+    }
+
+    fn render_notes_panel(&mut self, ctx: &Context) {
+        let Some(key) = self.dataset_key.clone() else {
+            return;
+        };
+        let mut text = self.notes.get(&key).cloned().unwrap_or_default();
+        let mut open = self.notes_open;
+        let mut detached = self.notes_detached;
+        panels::show_panel(
+            ctx,
+            "Notes",
+            [420.0, 360.0],
+            &mut open,
+            &mut detached,
+            |ui| {
+                ui.label("Notes are kept with this dataset across sessions.");
+                ScrollArea::vertical().show(ui, |ui| {
+                    ui.add_sized(
+                        ui.available_size(),
+                        egui::TextEdit::multiline(&mut text)
+                            .hint_text("Observations, findings, open questions…"),
+                    );
+                });
+            },
+        );
+        self.notes_open = open;
+        self.notes_detached = detached;
+        if text.is_empty() {
+            self.notes.remove(&key);
+        } else {
+            self.notes.insert(key, text);
+        }
+    }
+
+    /// Names the loaded data for report and print titles.
+    fn dataset_title(&self) -> String {
+        match self.files_to_load.as_slice() {
+            [file] => file_label(file),
+            files => format!("{} files", files.len()),
+        }
+    }
+
+    fn run_hook(&mut self, event: HookEvent, paths: &[PathBuf]) {
+        if self.safe_mode {
+            return;
+        }
+        let command = match event {
+            HookEvent::Load => &self.settings.hooks.after_load,
+            HookEvent::Export => &self.settings.hooks.after_export,
+        };
+        if let Err(e) = hooks::run(command, event, paths) {
+            self.notifications
+                .error(format!("Could not run {} hook: {}", event, e));
+        }
+    }
+
+    fn render_goto_dialog(&mut self, ctx: &Context) {
+        let mut open = self.goto_dialog_open;
+        Window::new("Go to row")
+            .auto_sized()
+            .collapsible(false)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut self.goto_by_position, false, "Row Index");
+                    ui.radio_value(&mut self.goto_by_position, true, "View position");
+                });
+                ui.horizontal(|ui| {
+                    let response = ui.text_edit_singleline(&mut self.goto_text);
+                    if !response.has_focus() && !response.lost_focus() {
+                        response.request_focus();
+                    }
+                    let submitted =
+                        response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+                    if ui.button("Go").clicked() || submitted {
+                        self.go_to_row();
+                    }
+                });
+            });
+        self.goto_dialog_open &= open;
+    }
+
+    fn render_image_dialog(&mut self, ctx: &Context) {
+        let Some((title, uri)) = &self.image_cell else {
+            return;
+        };
+        let mut open = true;
+        Window::new(title.as_str())
+            .id(egui::Id::new("image_cell"))
+            .default_size([600.0, 600.0])
+            .collapsible(false)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ScrollArea::both().show(ui, |ui| {
+                    ui.add(egui::Image::new(uri.as_str()).fit_to_original_size(1.0));
+                });
+            });
+        if !open {
+            self.image_cell = None;
+        }
+    }
+
+    fn render_json_dialog(&mut self, ctx: &Context) {
+        let Some((title, parsed)) = &self.json_cell else {
+            return;
+        };
+        let mut open = true;
+        let mut detached = self.json_detached;
+        panels::show_panel(
+            ctx,
+            "JSON Viewer",
+            [500.0, 400.0],
+            &mut open,
+            &mut detached,
+            |ui| {
+                ui.label(RichText::new(title).strong());
+                ui.separator();
+                match parsed {
+                    Ok(value) => {
+                        if ui.button("Copy formatted").clicked() {
+                            ui.ctx()
+                                .copy_text(serde_json::to_string_pretty(value).unwrap_or_default());
+                        }
+                        ScrollArea::both()
+                            .auto_shrink([false, false])
+                            .show(ui, |ui| json_view::show_json_tree(ui, value));
+                    }
+                    Err(e) => {
+                        ui.colored_label(Color32::RED, format!("Invalid JSON: {}", e));
+                    }
+                }
+            },
+        );
+        self.json_detached = detached;
+        if !open {
+            self.json_cell = None;
+        }
+    }
+
+    /// Updates the row selection for a click: plain clicks select one row, Ctrl/Cmd toggles
+    /// a row and Shift extends from the last clicked row.
+    fn select_row(&mut self, row: usize, modifiers: Modifiers) {
+        self.selection_stats = None;
+        if modifiers.shift
+            && let Some(anchor) = self.selection_anchor
+        {
+            self.selected_rows = (anchor.min(row)..=anchor.max(row)).collect();
+            return;
+        }
+        if modifiers.command {
+            if !self.selected_rows.remove(&row) {
+                self.selected_rows.insert(row);
+            }
+        } else {
+            self.selected_rows = BTreeSet::from([row]);
+        }
+        self.selection_anchor = Some(row);
+    }
+
+    /// Recomputes the selection aggregates when the selection or visible columns changed.
+    fn update_selection_stats(&mut self) {
+        let Some(df) = &self.dataframe else {
+            return;
+        };
+        if self.selected_rows.is_empty() {
+            self.selection_stats = None;
+            return;
+        }
+        let columns: Vec<String> = self
+            .visible_columns()
+            .into_iter()
+            .filter(|name| {
+                name != ROW_INDEX_COLUMN
+                    && df
+                        .column(name)
+                        .is_ok_and(|column| column.dtype().is_primitive_numeric())
+            })
+            .collect();
+        if self
+            .selection_stats
+            .as_ref()
+            .is_some_and(|stats| stats.columns == columns)
+        {
+            return;
+        }
+        let indices = IdxCa::from_vec(
+            "selected".into(),
+            self.selected_rows
+                .iter()
+                .map(|row| *row as IdxSize)
+                .collect(),
+        );
+        let (mut count, mut sum) = (0, 0.0);
+        for name in &columns {
+            let Ok(values) = df
+                .column(name)
+                .and_then(|column| column.take(&indices))
+                .and_then(|column| column.cast(&DataType::Float64))
+            else {
+                continue;
+            };
+            if let Ok(values) = values.f64() {
+                for value in values.into_iter().flatten() {
+                    count += 1;
+                    sum += value;
+                }
+            }
+        }
+        self.selection_stats = Some(SelectionStats {
+            columns,
+            count,
+            sum,
+        });
+    }
+
+    fn render_status_bar(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            let errors = self.notifications.error_count();
+            let label = if errors > 0 {
+                RichText::new(format!("⛔ {}", errors)).color(ui.visuals().error_fg_color)
+            } else {
+                RichText::new("Messages")
+            };
+            if ui
+                .small_button(label)
+                .on_hover_text("Errors, warnings and notices of this session")
+                .clicked()
+            {
+                self.notifications.log_open = true;
+            }
+            if ui
+                .small_button("Diagnostics")
+                .on_hover_text("Timings, memory use, query plans and the log")
+                .clicked()
+            {
+                self.diagnostics_open = true;
+            }
+            ui.separator();
+            if self.safe_mode {
+                ui.colored_label(Color32::YELLOW, "Safe mode")
+                    .on_hover_text(
+                        "Default settings; session, layouts, hooks and automatic analysis are off",
+                    );
+                ui.separator();
+            }
+            let (Some(original_df), Some(df)) = (&self.original_dataframe, &self.dataframe) else {
+                ui.label("No data loaded");
+                return;
+            };
+            if self.row_limit != RowLimit::All {
+                ui.colored_label(Color32::LIGHT_BLUE, self.row_limit.to_string());
+                ui.separator();
+            }
+            ui.label(format!("Total rows: {}", original_df.height()));
+            ui.separator();
+            ui.label(format!("Rows in view: {}", df.height()));
+            ui.separator();
+            ui.label(format!("Selected: {}", self.selected_rows.len()));
+            if let Some(stats) = &self.selection_stats
+                && stats.count > 0
+            {
+                ui.separator();
+                ui.label(format!(
+                    "Count: {}   Sum: {}   Average: {}",
+                    stats.count,
+                    round_for_display(stats.sum),
+                    round_for_display(stats.sum / stats.count as f64)
+                ))
+                .on_hover_text("Numeric cells in the selected rows");
+            }
+            ui.separator();
+            ui.label(format!(
+                "Columns: {} of {}",
+                self.visible_columns().len(),
+                self.column_names.len()
+            ));
+            if let Some((operation, elapsed)) = self.timings.back() {
+                ui.separator();
+                ui.label(format!("{} took {:.1?}", operation, elapsed));
+            }
+            if self.has_edits() {
+                let mut changes = Vec::new();
+                for (count, what) in [
+                    (self.edited_cells.len(), "edited cells"),
+                    (self.added_rows.len(), "added rows"),
+                    (self.deleted_rows, "deleted rows"),
+                ] {
+                    if count > 0 {
+                        changes.push(format!("{} {}", count, what));
+                    }
+                }
+                ui.separator();
+                ui.colored_label(EDITED_COLOR, format!("✏ {}", changes.join(", ")));
+                if ui
+                    .small_button("Save as…")
+                    .on_hover_text("All rows and columns, as Parquet or CSV")
+                    .clicked()
+                {
+                    self.save_edited_data();
+                }
+                if ui
+                    .small_button("Discard")
+                    .on_hover_text("Read the files again, dropping every change")
+                    .clicked()
+                {
+                    self.load_parquet_data(self.files_to_load.clone());
+                }
+            }
+        });
+    }
+}
+
+/// The file name of `path`, or the whole path when it has none, such as `..`.
+fn file_label(path: &Path) -> String {
+    path.file_name().map_or_else(
+        || path.display().to_string(),
+        |name| name.to_string_lossy().into_owned(),
+    )
+}
+
+/// Trims float noise such as `0.30000000000000004` for status bar figures.
+fn round_for_display(value: f64) -> f64 {
+    (value * 1e6).round() / 1e6
+}
+
+fn paint_cell_background(ui: &Ui, color: Option<Color32>) {
+    if let Some(color) = color {
+        ui.painter().rect_filled(ui.max_rect(), 0.0, color);
+    }
+}
+
+impl eframe::App for Tablr {
+    fn update(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
+        let font_size = self.settings.font_size;
+        ctx.style_mut(|style| {
+            style.text_styles.get_mut(&TextStyle::Body).unwrap().size = font_size;
+            style.text_styles.get_mut(&TextStyle::Button).unwrap().size = font_size;
+        });
+
+        // Keep keyboard zoom (Ctrl +/-) in sync with the persisted UI scale.
+        let zoom = ctx.zoom_factor();
+        if (zoom - self.settings.ui_scale).abs() > f32::EPSILON {
+            self.settings.ui_scale = zoom;
+            self.save_settings();
+        }
+
+        for action in Action::ALL {
+            if let Some(shortcut) = self.settings.keybindings.shortcut(action)
+                && ctx.input_mut(|i| i.consume_shortcut(&shortcut))
+            {
+                self.run_action(ctx, action);
+            }
+        }
+
+        self.intercept_close(ctx);
+        self.handle_grid_navigation(ctx);
+        self.process_pending_files();
+        self.poll_watched_files(ctx);
+        self.receive_instance_requests(ctx);
+        self.receive_dropped_files(ctx);
+        #[cfg(target_arch = "wasm32")]
+        self.receive_picked_files(ctx);
+        if std::mem::take(&mut self.forget_images) {
+            ctx.forget_all_images();
+        }
+        self.render_filter_dialog(ctx);
+        self.render_pii_dialog(ctx);
+        self.render_group_dialog(ctx);
+        self.render_pivot_dialog(ctx);
+        self.render_describe_dialog(ctx);
+        self.render_profile_dialog(ctx);
+        self.render_duplicates_dialog(ctx);
+        self.render_computed_dialog(ctx);
+        self.render_split_dialog(ctx);
+        self.render_join_dialog(ctx);
+        self.render_unpivot_dialog(ctx);
+        self.render_replace_dialog(ctx);
+        self.render_schema_compare_dialog(ctx);
+        self.render_correlation_dialog(ctx);
+        self.render_scatter_panel(ctx);
+        self.render_line_chart_panel(ctx);
+        self.render_histogram_panel(ctx);
+        self.render_bar_chart_panel(ctx);
+        self.render_box_plot_panel(ctx);
+        self.render_notes_panel(ctx);
+        self.render_annotation_editor(ctx);
+        self.render_highlight_dialog(ctx);
+        self.render_similarity_dialog(ctx);
+        self.render_schema_dialog(ctx);
+        self.render_metadata_panel(ctx);
+        self.render_json_dialog(ctx);
+        self.render_image_dialog(ctx);
+        self.render_diff_dialog(ctx);
+        self.render_goto_dialog(ctx);
+        self.render_export_dialog(ctx);
+        self.render_display_dialog(ctx);
+        self.render_settings_dialog(ctx);
+        self.render_session_prompt(ctx);
+        self.render_forwarded_files_prompt(ctx);
+        self.render_unsaved_edits_prompt(ctx);
+        self.render_reload_prompt(ctx);
+        self.notifications.show_log(ctx);
+        self.render_diagnostics_panel(ctx);
+        self.render_script_console(ctx);
+        self.update_selection_stats();
+        self.update_bookmark_positions();
+        egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| self.render_status_bar(ui));
+        if self.split_open {
+            egui::SidePanel::right("split_view")
+                .resizable(true)
+                .default_width(ctx.screen_rect().width() / 2.0)
+                .show(ctx, |ui| self.render_split_view(ui));
+        }
+        if self.show_minimap && self.dataframe.is_some() && !self.transposed {
+            egui::SidePanel::right("minimap")
+                .exact_width(14.0)
+                .resizable(false)
+                .show(ctx, |ui| self.render_minimap(ui));
+        }
+        self.notifications.show_toasts(ctx);
+        CentralPanel::default().show(ctx, |ui| {
+            self.render_file_selector(ui);
+            if self.search_open && self.dataframe.is_some() {
+                self.render_search_bar(ui);
+            }
+            ui.separator();
+            self.render_epoch_hint(ui);
+            self.render_constant_hint(ui);
+            self.render_drill_down_bar(ui);
+            if self.dataframe.is_some() {
+                ui.horizontal(|ui| {
+                    ui.selectable_value(&mut self.tab, Tab::Data, "Data");
+                    ui.selectable_value(&mut self.tab, Tab::Overview, "Overview");
+                });
+            }
+            match (self.tab, &self.overview) {
+                (Tab::Overview, Some(overview)) => {
+                    ScrollArea::vertical()
+                        .auto_shrink([false, false])
+                        .show(ui, |ui| overview::show(ui, overview));
+                }
+                _ => self.render_dataframe(ui),
+            }
+        });
+        self.sync_split_scroll();
+    }
+
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        // Safe mode did not restore the workspace, so saving would erase the stored one.
+        if self.safe_mode {
+            return;
+        }
+        eframe::set_value(storage, eframe::APP_KEY, &self.workspace());
+    }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        self.save_layout();
+    }
+}
+
+/// Hands `paths` to a window that is already open, when single-instance mode is on.
+/// Returns false when this process should open its own window.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn forward_to_instance(paths: &[PathBuf]) -> bool {
+    !paths.is_empty()
+        && Settings::load().is_ok_and(|settings| settings.single_instance)
+        && instance::forward(paths)
+}
+
+/// Opens the viewer window and blocks until it is closed.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn run_viewer(
+    paths: Vec<PathBuf>,
+    safe_mode: bool,
+    view: StartupView,
+) -> Result<(), eframe::Error> {
+    run_viewer_with_renderers(paths, safe_mode, view, CellRenderers::default())
+}
+
+/// Like [`run_viewer`], with custom display for some columns or column types.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn run_viewer_with_renderers(
+    paths: Vec<PathBuf>,
+    safe_mode: bool,
+    view: StartupView,
+    renderers: CellRenderers,
+) -> Result<(), eframe::Error> {
+    // Safe mode starts from defaults so that a broken config or session cannot crash startup.
+    let (settings, settings_error) = if safe_mode {
+        (Settings::default(), None)
+    } else {
+        match Settings::load() {
+            Ok(settings) => (settings, None),
+            Err(e) => (Settings::default(), Some(e)),
+        }
+    };
+    let options = eframe::NativeOptions {
+        viewport: egui::ViewportBuilder::default()
+            .with_app_id("tablr")
+            .with_inner_size([1200.0, 800.0]),
+        ..Default::default()
+    };
+    eframe::run_native(
+        "Tablr - Parquet Viewer",
+        options,
+        Box::new(|cc| {
+            cc.egui_ctx.set_theme(settings.theme.preference());
+            cc.egui_ctx.set_zoom_factor(settings.ui_scale);
+            egui_extras::install_image_loaders(&cc.egui_ctx);
+            let single_instance = settings.single_instance && !safe_mode;
+            let mut app = Tablr::new(paths, settings, safe_mode);
+            if let Some(e) = settings_error {
+                app.settings_load_failed(e);
+            }
+            // The compared file usually has the same columns, so draw them the same way.
+            app.split_pane = DataFrameTable::default()
+                .id_salt("split_pane")
+                .renderers(renderers.clone());
+            app.renderers = renderers;
+            app.startup_view = Some(view);
+            if single_instance {
+                app.listen_for_instances(&cc.egui_ctx);
+            }
+            if !safe_mode
+                && let Some(workspace) = cc
+                    .storage
+                    .and_then(|storage| eframe::get_value(storage, eframe::APP_KEY))
+            {
+                app.restore_workspace(workspace);
+            }
+            Ok(Box::new(app))
+        }),
+    )
+}
+
+/// Runs the viewer in a web page, drawing into the `<canvas>` with the id `canvas_id`.
+/// Files are opened with the Browse button or by dropping them onto the page.
+#[cfg(target_arch = "wasm32")]
+pub async fn start_web(canvas_id: &str) -> Result<(), wasm_bindgen::JsValue> {
+    let canvas = web::canvas(canvas_id)?;
+    let (settings, settings_error) = match Settings::load() {
+        Ok(settings) => (settings, None),
+        Err(e) => (Settings::default(), Some(e)),
+    };
+    eframe::WebRunner::new()
+        .start(
+            canvas,
+            eframe::WebOptions::default(),
+            Box::new(|cc| {
+                cc.egui_ctx.set_theme(settings.theme.preference());
+                cc.egui_ctx.set_zoom_factor(settings.ui_scale);
+                egui_extras::install_image_loaders(&cc.egui_ctx);
+                let mut app = Tablr::new(Vec::new(), settings, false);
+                if let Some(e) = settings_error {
+                    app.settings_load_failed(e);
+                }
+                Ok(Box::new(app))
+            }),
+        )
+        .await
+}
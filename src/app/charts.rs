@@ -0,0 +1,799 @@
+//! The chart panels: correlation, scatter and line plots, histograms, value counts and
+//! box plots.
+
+use super::*;
+
+impl Tablr {
+    pub(super) fn render_correlation_dialog(&mut self, ctx: &Context) {
+        let mut open = self.correlation_open;
+        let mut detached = self.correlation_detached;
+        panels::show_panel(
+            ctx,
+            "Correlation",
+            [640.0, 480.0],
+            &mut open,
+            &mut detached,
+            |ui| {
+                let Some(df) = &self.dataframe else {
+                    return;
+                };
+                let numeric: Vec<String> = df
+                    .get_columns()
+                    .iter()
+                    .filter(|column| {
+                        column.dtype().is_primitive_numeric()
+                            && column.name().as_str() != ROW_INDEX_COLUMN
+                    })
+                    .map(|column| column.name().to_string())
+                    .collect();
+                let mut changed = false;
+                ui.horizontal_wrapped(|ui| {
+                    for name in &numeric {
+                        let mut selected = !self.correlation_excluded.contains(name);
+                        if ui.checkbox(&mut selected, name).changed() {
+                            if selected {
+                                self.correlation_excluded.remove(name);
+                            } else {
+                                self.correlation_excluded.insert(name.clone());
+                            }
+                            changed = true;
+                        }
+                    }
+                });
+                let refresh = self.correlation_state.show_controls(ui, self.data_version);
+                if refresh
+                    || changed
+                    || !self.correlation_state.has_result()
+                    || self.correlation_state.needs_refresh(self.data_version)
+                {
+                    let selected: Vec<String> = numeric
+                        .into_iter()
+                        .filter(|name| !self.correlation_excluded.contains(name))
+                        .collect();
+                    match correlation::correlation_matrix(df, &selected) {
+                        Ok(matrix) => self.correlation = Some(matrix),
+                        Err(e) => self
+                            .notifications
+                            .error(format!("Correlation error: {}", e)),
+                    }
+                    self.correlation_state.mark_computed(self.data_version);
+                }
+                ui.separator();
+                match &self.correlation {
+                    Some(matrix) if matrix.columns.len() >= 2 => {
+                        ScrollArea::both()
+                            .auto_shrink([false, false])
+                            .show(ui, |ui| correlation::show(ui, matrix));
+                    }
+                    _ => {
+                        ui.label("Select at least two numeric columns.");
+                    }
+                }
+            },
+        );
+        self.correlation_open = open;
+        self.correlation_detached = detached;
+    }
+
+    pub(super) fn render_scatter_panel(&mut self, ctx: &Context) {
+        let mut open = self.scatter_open;
+        let mut detached = self.scatter_detached;
+        let mut brushed = None;
+        panels::show_panel(
+            ctx,
+            "Scatter plot",
+            [640.0, 520.0],
+            &mut open,
+            &mut detached,
+            |ui| {
+                let Some(df) = &self.dataframe else {
+                    return;
+                };
+                let columns: Vec<(String, DataType)> = df
+                    .get_columns()
+                    .iter()
+                    .filter(|column| column.name().as_str() != ROW_INDEX_COLUMN)
+                    .map(|column| (column.name().to_string(), column.dtype().clone()))
+                    .collect();
+                let axes: Vec<&String> = columns
+                    .iter()
+                    .filter(|(_, dtype)| scatter::is_plottable(dtype))
+                    .map(|(name, _)| name)
+                    .collect();
+                let mut changed = false;
+                ui.horizontal(|ui| {
+                    for (label, selected) in
+                        [("X:", &mut self.scatter_x), ("Y:", &mut self.scatter_y)]
+                    {
+                        ui.label(label);
+                        ComboBox::from_id_salt(("scatter_axis", label))
+                            .selected_text(selected.as_deref().unwrap_or("Select column"))
+                            .show_ui(ui, |ui| {
+                                for &name in &axes {
+                                    changed |= ui
+                                        .selectable_value(selected, Some(name.clone()), name)
+                                        .changed();
+                                }
+                            });
+                    }
+                    ui.label("Colour by:");
+                    ComboBox::from_id_salt("scatter_color")
+                        .selected_text(self.scatter_color.as_deref().unwrap_or("None"))
+                        .show_ui(ui, |ui| {
+                            changed |= ui
+                                .selectable_value(&mut self.scatter_color, None, "None")
+                                .changed();
+                            for (name, _) in &columns {
+                                changed |= ui
+                                    .selectable_value(
+                                        &mut self.scatter_color,
+                                        Some(name.clone()),
+                                        name,
+                                    )
+                                    .changed();
+                            }
+                        });
+                });
+                let refresh = self.scatter_state.show_controls(ui, self.data_version);
+                let (Some(x), Some(y)) = (self.scatter_x.clone(), self.scatter_y.clone()) else {
+                    ui.label("Pick the columns to plot.");
+                    return;
+                };
+                if changed {
+                    self.scatter_view.reset();
+                }
+                if refresh
+                    || changed
+                    || !self.scatter_state.has_result()
+                    || self.scatter_state.needs_refresh(self.data_version)
+                {
+                    match scatter::compute(df, &x, &y, self.scatter_color.as_deref()) {
+                        Ok(data) => self.scatter = Some(data),
+                        Err(e) => {
+                            self.scatter = None;
+                            self.notifications
+                                .error(format!("Scatter plot error: {}", e));
+                        }
+                    }
+                    self.scatter_state.mark_computed(self.data_version);
+                }
+                let Some(data) = &self.scatter else {
+                    return;
+                };
+                if data.points.is_empty() {
+                    ui.label("No rows have both values.");
+                    return;
+                }
+                ui.horizontal(|ui| {
+                    if data.points.len() < data.total {
+                        ui.weak(format!(
+                            "Showing {} of {} points.",
+                            data.points.len(),
+                            data.total
+                        ));
+                    }
+                    ui.weak("Drag to pan, Ctrl+scroll to zoom, shift-drag to select rows, double-click to reset.");
+                });
+                let dtype = |name: &str| {
+                    df.column(name)
+                        .map(|column| column.dtype().clone())
+                        .unwrap_or(DataType::Float64)
+                };
+                let x_format = plot::axis_format(&dtype(&x));
+                let y_format = plot::axis_format(&dtype(&y));
+                let shift = ui.input(|i| i.modifiers.shift);
+                let view = &mut self.scatter_view;
+                let plot = view
+                    .apply(egui_plot::Plot::new("scatter_plot"))
+                    .x_axis_label(&x)
+                    .y_axis_label(&y)
+                    .x_axis_formatter(plot::axis_formatter(&dtype(&x)))
+                    .y_axis_formatter(plot::axis_formatter(&dtype(&y)))
+                    .legend(egui_plot::Legend::default())
+                    .show_x(false)
+                    .show_y(false)
+                    .allow_drag(!shift)
+                    .show(ui, |plot_ui| {
+                        data.show(plot_ui);
+                        view.brush(plot_ui)
+                    });
+                if let Some(pointer) = plot.response.hover_pos()
+                    && let Some(point) = data.nearest(&plot.transform, pointer)
+                {
+                    let row = data.rows[point];
+                    plot.response.clone().on_hover_ui_at_pointer(|ui| {
+                        let names = [Some(&x), Some(&y), self.scatter_color.as_ref()];
+                        for name in names.into_iter().flatten() {
+                            if let Ok(value) = df.column(name).and_then(|c| c.get(row)) {
+                                ui.label(format!("{}: {}", name, value.str_value()));
+                            }
+                        }
+                    });
+                }
+                if let Some(selection) = plot.inner {
+                    let description = format!(
+                        "{} in [{}, {}] and {} in [{}, {}]",
+                        x,
+                        x_format(selection.min_x, selection.width() / 100.0),
+                        x_format(selection.max_x, selection.width() / 100.0),
+                        y,
+                        y_format(selection.min_y, selection.height() / 100.0),
+                        y_format(selection.max_y, selection.height() / 100.0),
+                    );
+                    brushed = Some((scatter::brush_filter(&x, &y, &selection), description));
+                }
+            },
+        );
+        self.scatter_open = open;
+        self.scatter_detached = detached;
+        if let Some((predicate, description)) = brushed
+            && let Some(df) = &self.dataframe
+        {
+            match df.clone().lazy().filter(predicate).collect() {
+                Ok(rows) => self.narrow_view(rows, description),
+                Err(e) => self
+                    .notifications
+                    .error(format!("Scatter plot error: {}", e)),
+            }
+        }
+    }
+
+    pub(super) fn render_line_chart_panel(&mut self, ctx: &Context) {
+        let mut open = self.line_open;
+        let mut detached = self.line_detached;
+        panels::show_panel(
+            ctx,
+            "Plot over time",
+            [720.0, 480.0],
+            &mut open,
+            &mut detached,
+            |ui| {
+                let Some(df) = &self.dataframe else {
+                    return;
+                };
+                let times: Vec<String> = df
+                    .get_columns()
+                    .iter()
+                    .filter(|column| line_chart::is_time(column.dtype()))
+                    .map(|column| column.name().to_string())
+                    .collect();
+                let numeric: Vec<String> = df
+                    .get_columns()
+                    .iter()
+                    .filter(|column| {
+                        column.dtype().is_primitive_numeric()
+                            && column.name().as_str() != ROW_INDEX_COLUMN
+                    })
+                    .map(|column| column.name().to_string())
+                    .collect();
+                if times.is_empty() {
+                    ui.label("There are no date or datetime columns.");
+                    return;
+                }
+                let mut changed = false;
+                ui.horizontal(|ui| {
+                    ui.label("X:");
+                    ComboBox::from_id_salt("line_x")
+                        .selected_text(self.line_x.as_deref().unwrap_or("Select column"))
+                        .show_ui(ui, |ui| {
+                            for name in &times {
+                                changed |= ui
+                                    .selectable_value(&mut self.line_x, Some(name.clone()), name)
+                                    .changed();
+                            }
+                        });
+                });
+                ui.horizontal_wrapped(|ui| {
+                    ui.label("Y:");
+                    for name in &numeric {
+                        let position = self.line_ys.iter().position(|y| y == name);
+                        let mut selected = position.is_some();
+                        let label = match position {
+                            Some(i) => RichText::new(name).color(plot::palette(i)),
+                            None => RichText::new(name),
+                        };
+                        if ui.checkbox(&mut selected, label).changed() {
+                            if selected {
+                                self.line_ys.push(name.clone());
+                            } else {
+                                self.line_ys.retain(|y| y != name);
+                            }
+                            changed = true;
+                        }
+                    }
+                });
+                let refresh = self.line_state.show_controls(ui, self.data_version);
+                let Some(x) = self.line_x.clone().filter(|_| !self.line_ys.is_empty()) else {
+                    ui.label("Pick a time column and at least one value column.");
+                    return;
+                };
+                if changed {
+                    self.line_view.reset();
+                }
+                if refresh
+                    || changed
+                    || !self.line_state.has_result()
+                    || self.line_state.needs_refresh(self.data_version)
+                {
+                    match line_chart::compute(df, &x, &self.line_ys) {
+                        Ok(chart) => self.line_chart = Some(chart),
+                        Err(e) => {
+                            self.line_chart = None;
+                            self.notifications.error(format!("Line chart error: {}", e));
+                        }
+                    }
+                    self.line_state.mark_computed(self.data_version);
+                }
+                let Some(chart) = &self.line_chart else {
+                    return;
+                };
+                if chart.is_empty() {
+                    ui.label("No rows have values to plot.");
+                    return;
+                }
+                ui.weak("Drag to pan, Ctrl+scroll to zoom, double-click to reset.");
+                let x_dtype = df
+                    .column(&x)
+                    .map(|column| column.dtype().clone())
+                    .unwrap_or(DataType::Float64);
+                let y_label = if self.line_ys.len() == 1 {
+                    self.line_ys[0].clone()
+                } else {
+                    String::new()
+                };
+                let hover_color = ui.visuals().widgets.hovered.fg_stroke.color;
+                // Only x zooms and pans, so that y stays fitted to the data.
+                let plot = self
+                    .line_view
+                    .apply(egui_plot::Plot::new("line_chart"))
+                    .x_axis_label(&x)
+                    .y_axis_label(y_label)
+                    .x_axis_formatter(plot::axis_formatter(&x_dtype))
+                    .legend(egui_plot::Legend::default())
+                    .show_x(false)
+                    .show_y(false)
+                    .allow_zoom([true, false])
+                    .allow_drag([true, false])
+                    .allow_scroll(false)
+                    .allow_boxed_zoom(false)
+                    .show(ui, |plot_ui| {
+                        chart.show(plot_ui);
+                        let pointer = plot_ui.pointer_coordinate()?;
+                        let values = chart.values_at(pointer.x);
+                        let (_, _, [nearest_x, _]) = values.first()?;
+                        plot_ui.vline(
+                            egui_plot::VLine::new(*nearest_x)
+                                .color(hover_color)
+                                .allow_hover(false),
+                        );
+                        Some(*nearest_x)
+                    });
+                if let Some(nearest_x) = plot.inner
+                    && plot.response.hovered()
+                {
+                    plot.response.clone().on_hover_ui_at_pointer(|ui| {
+                        ui.label(plot::format_value(&x_dtype, nearest_x));
+                        for (i, line, [_, y]) in chart.values_at(nearest_x) {
+                            let dtype = df
+                                .column(&line.name)
+                                .map(|column| column.dtype().clone())
+                                .unwrap_or(DataType::Float64);
+                            ui.colored_label(
+                                plot::palette(i),
+                                format!("{}: {}", line.name, plot::format_value(&dtype, y)),
+                            );
+                        }
+                    });
+                }
+            },
+        );
+        self.line_open = open;
+        self.line_detached = detached;
+    }
+
+    pub(super) fn render_histogram_panel(&mut self, ctx: &Context) {
+        let mut open = self.histogram_open;
+        let mut detached = self.histogram_detached;
+        let mut clicked_bin = None;
+        panels::show_panel(
+            ctx,
+            "Histogram",
+            [640.0, 440.0],
+            &mut open,
+            &mut detached,
+            |ui| {
+                let Some(df) = &self.dataframe else {
+                    return;
+                };
+                let columns: Vec<String> = df
+                    .get_columns()
+                    .iter()
+                    .filter(|column| {
+                        scatter::is_plottable(column.dtype())
+                            && column.name().as_str() != ROW_INDEX_COLUMN
+                    })
+                    .map(|column| column.name().to_string())
+                    .collect();
+                let mut changed = false;
+                ui.horizontal(|ui| {
+                    ui.label("Column:");
+                    ComboBox::from_id_salt("histogram_column")
+                        .selected_text(self.histogram_column.as_deref().unwrap_or("Select column"))
+                        .show_ui(ui, |ui| {
+                            for name in &columns {
+                                changed |= ui
+                                    .selectable_value(
+                                        &mut self.histogram_column,
+                                        Some(name.clone()),
+                                        name,
+                                    )
+                                    .changed();
+                            }
+                        });
+                    ui.label("Bins:");
+                    changed |= ui
+                        .add(
+                            egui::DragValue::new(&mut self.histogram_bins)
+                                .range(1..=histogram::MAX_BINS),
+                        )
+                        .changed();
+                    ui.checkbox(&mut self.histogram_log, "Log scale");
+                });
+                let refresh = self.histogram_state.show_controls(ui, self.data_version);
+                let Some(name) = self.histogram_column.clone() else {
+                    ui.label("Pick a column.");
+                    return;
+                };
+                let Ok(column) = df.column(&name) else {
+                    return;
+                };
+                if changed {
+                    self.histogram_view.reset();
+                }
+                if refresh
+                    || changed
+                    || !self.histogram_state.has_result()
+                    || self.histogram_state.needs_refresh(self.data_version)
+                {
+                    match histogram::compute(column, self.histogram_bins) {
+                        Ok(result) => self.histogram = result,
+                        Err(e) => {
+                            self.histogram = None;
+                            self.notifications.error(format!("Histogram error: {}", e));
+                        }
+                    }
+                    self.histogram_state.mark_computed(self.data_version);
+                }
+                let Some(chart) = &self.histogram else {
+                    ui.label("The column has no values.");
+                    return;
+                };
+                ui.weak(format!(
+                    "{} values in the current view. Drag to pan, Ctrl+scroll to zoom, click a bar to see its rows.",
+                    column.len() - column.null_count()
+                ));
+                let log = self.histogram_log;
+                let dtype = column.dtype().clone();
+                let color = ui.visuals().selection.bg_fill;
+                let floor = Histogram::floor(log);
+                // Only x zooms and pans, so that y stays fitted to the bars.
+                let plot = self
+                    .histogram_view
+                    .apply(egui_plot::Plot::new("histogram"))
+                    .x_axis_label(&name)
+                    .y_axis_label(if log { "Rows (log)" } else { "Rows" })
+                    .x_axis_formatter(plot::axis_formatter(&dtype))
+                    .y_axis_formatter(move |mark, _| {
+                        if log {
+                            plot::format_number(10f64.powf(mark.value), 1.0)
+                        } else {
+                            plot::format_number(mark.value, mark.step_size.max(1.0))
+                        }
+                    })
+                    .include_y(floor)
+                    .include_y(floor + 1.0)
+                    .show_x(false)
+                    .show_y(false)
+                    .allow_zoom([true, false])
+                    .allow_drag([true, false])
+                    .allow_scroll(false)
+                    .allow_boxed_zoom(false)
+                    .show(ui, |plot_ui| {
+                        let hovered = plot_ui
+                            .pointer_coordinate()
+                            .and_then(|pointer| chart.bin_at(pointer.x));
+                        chart.show(plot_ui, log, color, hovered);
+                        hovered
+                    });
+                let hovered = plot.inner.filter(|_| plot.response.hovered());
+                if let Some(bin) = hovered {
+                    let (start, end) = chart.bin(bin);
+                    plot.response.clone().on_hover_ui_at_pointer(|ui| {
+                        ui.label(format!(
+                            "{} to {}",
+                            plot::format_value(&dtype, start),
+                            plot::format_value(&dtype, end)
+                        ));
+                        ui.label(format!("{} rows", chart.counts[bin]));
+                    });
+                    if plot.response.clicked() {
+                        let last = bin + 1 == chart.counts.len();
+                        clicked_bin = Some((
+                            histogram::bin_filter(&name, (start, end), last),
+                            format!(
+                                "{} from {} to {}",
+                                name,
+                                plot::format_value(&dtype, start),
+                                plot::format_value(&dtype, end)
+                            ),
+                        ));
+                    }
+                }
+            },
+        );
+        self.histogram_open = open;
+        self.histogram_detached = detached;
+        if let Some((predicate, description)) = clicked_bin
+            && let Some(df) = &self.dataframe
+        {
+            match df.clone().lazy().filter(predicate).collect() {
+                Ok(rows) => self.narrow_view(rows, description),
+                Err(e) => self.notifications.error(format!("Histogram error: {}", e)),
+            }
+        }
+    }
+
+    pub(super) fn open_bar_chart(&mut self, col_name: &str) {
+        if self.bar_column.as_deref() != Some(col_name) {
+            self.bar_column = Some(col_name.to_string());
+            self.bar_state.reset();
+        }
+        self.bar_open = true;
+    }
+
+    pub(super) fn render_bar_chart_panel(&mut self, ctx: &Context) {
+        let mut open = self.bar_open;
+        let mut detached = self.bar_detached;
+        let mut clicked = None;
+        panels::show_panel(
+            ctx,
+            "Value counts",
+            [560.0, 480.0],
+            &mut open,
+            &mut detached,
+            |ui| {
+                let Some(df) = &self.dataframe else {
+                    return;
+                };
+                let mut changed = false;
+                ui.horizontal(|ui| {
+                    ui.label("Column:");
+                    ComboBox::from_id_salt("bar_column")
+                        .selected_text(self.bar_column.as_deref().unwrap_or("Select column"))
+                        .show_ui(ui, |ui| {
+                            for column in df.get_columns() {
+                                let name = column.name().to_string();
+                                if name == ROW_INDEX_COLUMN {
+                                    continue;
+                                }
+                                changed |= ui
+                                    .selectable_value(
+                                        &mut self.bar_column,
+                                        Some(name.clone()),
+                                        name,
+                                    )
+                                    .changed();
+                            }
+                        });
+                    ui.label("Top:");
+                    changed |= ui
+                        .add(egui::DragValue::new(&mut self.bar_top).range(1..=bar_chart::MAX_TOP))
+                        .changed();
+                });
+                let refresh = self.bar_state.show_controls(ui, self.data_version);
+                let Some(name) = self.bar_column.clone() else {
+                    ui.label("Pick a column.");
+                    return;
+                };
+                let Ok(column) = df.column(&name) else {
+                    return;
+                };
+                if refresh
+                    || changed
+                    || !self.bar_state.has_result()
+                    || self.bar_state.needs_refresh(self.data_version)
+                {
+                    self.bar_counts = Some(bar_chart::compute(column, self.bar_top));
+                    self.bar_state.mark_computed(self.data_version);
+                }
+                let Some(counts) = &self.bar_counts else {
+                    return;
+                };
+                ui.weak(format!(
+                    "{} rows in the current view. Drag to pan, Ctrl+scroll to zoom, click a bar to see its rows.",
+                    df.height()
+                ));
+                if let Some(value) = bar_chart::show(ui, counts) {
+                    clicked = Some((
+                        bar_chart::value_filter(&name, &value),
+                        format!("{} = {}", name, value),
+                    ));
+                }
+            },
+        );
+        self.bar_open = open;
+        self.bar_detached = detached;
+        if let Some((predicate, description)) = clicked
+            && let Some(df) = &self.dataframe
+        {
+            match df.clone().lazy().filter(predicate).collect() {
+                Ok(rows) => self.narrow_view(rows, description),
+                Err(e) => self
+                    .notifications
+                    .error(format!("Value counts error: {}", e)),
+            }
+        }
+    }
+
+    pub(super) fn render_box_plot_panel(&mut self, ctx: &Context) {
+        let mut open = self.box_open;
+        let mut detached = self.box_detached;
+        let mut clicked = None;
+        panels::show_panel(
+            ctx,
+            "Box plots by group",
+            [720.0, 500.0],
+            &mut open,
+            &mut detached,
+            |ui| {
+                let Some(df) = &self.dataframe else {
+                    return;
+                };
+                let columns: Vec<(String, bool)> = df
+                    .get_columns()
+                    .iter()
+                    .filter(|column| column.name().as_str() != ROW_INDEX_COLUMN)
+                    .map(|column| {
+                        (
+                            column.name().to_string(),
+                            scatter::is_plottable(column.dtype()),
+                        )
+                    })
+                    .collect();
+                let mut changed = false;
+                ui.horizontal(|ui| {
+                    ui.label("Values:");
+                    ComboBox::from_id_salt("box_value")
+                        .selected_text(self.box_value.as_deref().unwrap_or("Select column"))
+                        .show_ui(ui, |ui| {
+                            for (name, _) in columns.iter().filter(|(_, plottable)| *plottable) {
+                                changed |= ui
+                                    .selectable_value(&mut self.box_value, Some(name.clone()), name)
+                                    .changed();
+                            }
+                        });
+                    ui.label("Grouped by:");
+                    ComboBox::from_id_salt("box_group")
+                        .selected_text(self.box_group.as_deref().unwrap_or("Select column"))
+                        .show_ui(ui, |ui| {
+                            for (name, _) in &columns {
+                                changed |= ui
+                                    .selectable_value(&mut self.box_group, Some(name.clone()), name)
+                                    .changed();
+                            }
+                        });
+                    ui.label("Groups:");
+                    changed |= ui
+                        .add(
+                            egui::DragValue::new(&mut self.box_groups)
+                                .range(1..=box_plot::MAX_GROUPS),
+                        )
+                        .on_hover_text("The largest groups are shown")
+                        .changed();
+                });
+                let refresh = self.box_state.show_controls(ui, self.data_version);
+                let (Some(value), Some(group)) = (self.box_value.clone(), self.box_group.clone())
+                else {
+                    ui.label("Pick a value column and a column to group by.");
+                    return;
+                };
+                if changed {
+                    self.box_view.reset();
+                }
+                if refresh
+                    || changed
+                    || !self.box_state.has_result()
+                    || self.box_state.needs_refresh(self.data_version)
+                {
+                    match box_plot::compute(df, &value, &group, self.box_groups) {
+                        Ok(boxes) => self.boxes = boxes,
+                        Err(e) => {
+                            self.boxes.clear();
+                            self.notifications.error(format!("Box plot error: {}", e));
+                        }
+                    }
+                    self.box_state.mark_computed(self.data_version);
+                }
+                if self.boxes.is_empty() {
+                    ui.label("No rows have a value.");
+                    return;
+                }
+                ui.weak("Boxes span the middle half of each group, whiskers reach 1.5 times that past it, and circles are outliers. Click a box to see its rows.");
+                let dtype = df
+                    .column(&value)
+                    .map(|column| column.dtype().clone())
+                    .unwrap_or(DataType::Float64);
+                let boxes = &self.boxes;
+                let plot = self
+                    .box_view
+                    .apply(egui_plot::Plot::new("box_plot"))
+                    .x_axis_label(&group)
+                    .y_axis_label(&value)
+                    .x_axis_formatter(|mark, _| {
+                        let i = mark.value;
+                        if i.fract() == 0.0 && i >= 0.0 {
+                            boxes
+                                .get(i as usize)
+                                .map(|b| b.axis_label())
+                                .unwrap_or_default()
+                        } else {
+                            String::new()
+                        }
+                    })
+                    .x_grid_spacer(egui_plot::uniform_grid_spacer(|_| [1.0, 5.0, 10.0]))
+                    .y_axis_formatter(plot::axis_formatter(&dtype))
+                    .show_x(false)
+                    .show_y(false)
+                    .show(ui, |plot_ui| {
+                        let hovered = plot_ui
+                            .pointer_coordinate()
+                            .and_then(|pointer| box_plot::group_at(boxes, pointer.x));
+                        box_plot::show(boxes, plot_ui, hovered);
+                        hovered
+                    });
+                let hovered = plot.inner.filter(|_| plot.response.hovered());
+                if let Some(i) = hovered {
+                    let b = &boxes[i];
+                    plot.response.clone().on_hover_ui_at_pointer(|ui| {
+                        ui.strong(b.label());
+                        egui::Grid::new("box_stats").show(ui, |ui| {
+                            for (label, stat) in [
+                                ("Upper whisker", b.high_whisker),
+                                ("Upper quartile", b.q3),
+                                ("Median", b.median),
+                                ("Lower quartile", b.q1),
+                                ("Lower whisker", b.low_whisker),
+                            ] {
+                                ui.label(label);
+                                ui.label(plot::format_value(&dtype, stat));
+                                ui.end_row();
+                            }
+                            ui.label("Rows");
+                            ui.label(b.count.to_string());
+                            ui.end_row();
+                            ui.label("Outliers");
+                            ui.label(b.outlier_count.to_string());
+                            ui.end_row();
+                        });
+                    });
+                    if plot.response.clicked() {
+                        clicked = Some((
+                            box_plot::group_filter(&group, b.group.as_deref()),
+                            format!("{} = {}", group, b.label()),
+                        ));
+                    }
+                }
+            },
+        );
+        self.box_open = open;
+        self.box_detached = detached;
+        if let Some((predicate, description)) = clicked
+            && let Some(df) = &self.dataframe
+        {
+            match df.clone().lazy().filter(predicate).collect() {
+                Ok(rows) => self.narrow_view(rows, description),
+                Err(e) => self.notifications.error(format!("Box plot error: {}", e)),
+            }
+        }
+    }
+}
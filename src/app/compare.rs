@@ -0,0 +1,288 @@
+//! Comparing with other files: the diff panel and the split view.
+
+use super::*;
+
+impl Tablr {
+    pub(super) fn handle_compare_button_click(&mut self) {
+        let Some(paths) = FileDialog::new()
+            .add_filter("Parquet files", &["parquet"])
+            .pick_files()
+        else {
+            return;
+        };
+        self.open_split_view(paths);
+    }
+
+    pub(super) fn open_split_view(&mut self, paths: Vec<PathBuf>) {
+        if paths.is_empty() {
+            return;
+        }
+        match loader::read_parquet_indexed(&paths, ROW_INDEX_COLUMN, &RowLimit::All) {
+            Ok(df) => {
+                self.split_pane.set_title(if paths.len() == 1 {
+                    file_label(&paths[0])
+                } else {
+                    format!("{} files", paths.len())
+                });
+                self.split_pane.set_data(df);
+                self.split_files = paths;
+                self.split_open = true;
+                self.synced_rows = (self.first_visible_row, 0);
+            }
+            Err(e) => {
+                self.notifications
+                    .error(format!("Error processing Parquet files: {}", e));
+            }
+        }
+    }
+
+    pub(super) fn render_split_view(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.strong(self.split_pane.title());
+            ui.checkbox(&mut self.sync_scroll, "Sync scrolling");
+            if ui.button("Close").clicked() {
+                self.split_open = false;
+                self.split_files.clear();
+                self.split_pane.clear();
+            }
+        });
+        if let Some(row) = self.split_scroll_to.take() {
+            self.split_pane.scroll_to_row(row);
+        }
+        self.split_pane.set_row_height(self.row_height);
+        self.split_pane.show(ui);
+    }
+
+    /// Scrolls whichever side did not move to the first row of the side that did.
+    pub(super) fn sync_split_scroll(&mut self) {
+        if !self.split_open || !self.sync_scroll {
+            return;
+        }
+        let main_row = self.first_visible_row;
+        let split_row = self.split_pane.first_visible_row();
+        if main_row != self.synced_rows.0 {
+            self.split_scroll_to = Some(main_row);
+            self.synced_rows = (main_row, main_row);
+        } else if split_row != self.synced_rows.1 {
+            self.scroll_to_row = Some((split_row, Some(Align::TOP)));
+            self.synced_rows = (split_row, split_row);
+        }
+    }
+
+    /// The loaded data without the synthetic row index, as the left side of a diff.
+    fn diff_base(&self) -> Option<DataFrame> {
+        self.original_dataframe
+            .as_ref()
+            .map(|df| df.drop(ROW_INDEX_COLUMN).unwrap_or_else(|_| df.clone()))
+    }
+
+    fn handle_diff_pick_file(&mut self) {
+        let Some(path) = FileDialog::new()
+            .add_filter("Parquet files", &["parquet"])
+            .pick_file()
+        else {
+            return;
+        };
+        match loader::read_parquet(std::slice::from_ref(&path)) {
+            Ok(df) => {
+                let name = file_label(&path);
+                self.diff_keys
+                    .retain(|key| df.get_column_names().iter().any(|n| n.as_str() == key));
+                self.diff_other = Some((name, df));
+                self.diff_result = None;
+                self.diff_view = None;
+            }
+            Err(e) => {
+                self.notifications
+                    .error(format!("Error processing Parquet files: {}", e));
+            }
+        }
+    }
+
+    fn run_diff(&mut self) {
+        let (Some(left), Some((_, right))) = (self.diff_base(), &self.diff_other) else {
+            return;
+        };
+        let started = Instant::now();
+        match diff::diff(&left, right, &self.diff_keys) {
+            Ok(result) => {
+                self.diff_result = Some(result);
+                self.refresh_diff_view();
+                self.record_timing("Diff", started.elapsed());
+            }
+            Err(e) => {
+                self.diff_result = None;
+                self.diff_view = None;
+                self.notifications.error(format!("Diff error: {}", e));
+            }
+        }
+    }
+
+    fn refresh_diff_view(&mut self) {
+        let Some(result) = &self.diff_result else {
+            return;
+        };
+        match result.rows_with_status(&self.diff_statuses) {
+            Ok(df) => self.diff_view = Some(df),
+            Err(e) => self.notifications.error(format!("Diff error: {}", e)),
+        }
+    }
+
+    pub(super) fn render_diff_dialog(&mut self, ctx: &Context) {
+        let mut open = self.diff_dialog_open;
+        let mut detached = self.diff_detached;
+        panels::show_panel(
+            ctx,
+            "Compare files",
+            [800.0, 500.0],
+            &mut open,
+            &mut detached,
+            |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Compare loaded data with");
+                    if ui.button("Choose file…").clicked() {
+                        self.handle_diff_pick_file();
+                    }
+                    if let Some((name, _)) = &self.diff_other {
+                        ui.strong(name);
+                    }
+                });
+                let Some((_, other)) = &self.diff_other else {
+                    ui.label("Choose a second Parquet file to compare against.");
+                    return;
+                };
+                let common: Vec<String> = self
+                    .column_names
+                    .iter()
+                    .filter(|name| {
+                        other
+                            .get_column_names()
+                            .iter()
+                            .any(|n| n.as_str() == name.as_str())
+                    })
+                    .cloned()
+                    .collect();
+                ui.label("Key columns");
+                ui.horizontal_wrapped(|ui| {
+                    for name in &common {
+                        let mut selected = self.diff_keys.contains(name);
+                        if ui.checkbox(&mut selected, name).changed() {
+                            if selected {
+                                self.diff_keys.push(name.clone());
+                            } else {
+                                self.diff_keys.retain(|key| key != name);
+                            }
+                        }
+                    }
+                });
+                ui.add_enabled_ui(!self.diff_keys.is_empty(), |ui| {
+                    if ui.button("Compare").clicked() {
+                        self.run_diff();
+                    }
+                });
+
+                let Some(result) = &self.diff_result else {
+                    return;
+                };
+                ui.separator();
+                let mut statuses_changed = false;
+                ui.horizontal(|ui| {
+                    for status in DiffStatus::ALL {
+                        let mut shown = self.diff_statuses.contains(&status);
+                        let label = format!("{} ({})", status, result.count(status));
+                        if ui
+                            .checkbox(&mut shown, RichText::new(label).color(diff_color(status)))
+                            .changed()
+                        {
+                            if shown {
+                                self.diff_statuses.push(status);
+                            } else {
+                                self.diff_statuses.retain(|s| *s != status);
+                            }
+                            statuses_changed = true;
+                        }
+                    }
+                });
+                if !result.only_left.is_empty() {
+                    ui.weak(format!(
+                        "Only in loaded data: {}",
+                        result.only_left.join(", ")
+                    ));
+                }
+                if !result.only_right.is_empty() {
+                    ui.weak(format!(
+                        "Only in second file: {}",
+                        result.only_right.join(", ")
+                    ));
+                }
+                let columns = result.display_columns();
+                if statuses_changed {
+                    self.refresh_diff_view();
+                }
+                if let Some(view) = &self.diff_view {
+                    render_diff_table(ui, view, &columns, self.row_height);
+                }
+            },
+        );
+        self.diff_dialog_open = open;
+        self.diff_detached = detached;
+    }
+}
+
+fn diff_color(status: DiffStatus) -> Color32 {
+    match status {
+        DiffStatus::Added => Color32::from_rgb(0x2e, 0xa0, 0x43),
+        DiffStatus::Removed => Color32::from_rgb(0xd0, 0x3a, 0x3a),
+        DiffStatus::Changed => Color32::from_rgb(0xd0, 0x8c, 0x1a),
+        DiffStatus::Unchanged => Color32::GRAY,
+    }
+}
+
+fn render_diff_table(ui: &mut Ui, df: &DataFrame, columns: &[String], row_height: f32) {
+    let changed_fill = diff_color(DiffStatus::Changed).gamma_multiply(0.3);
+    ScrollArea::horizontal()
+        .id_salt("diff_hscroll")
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            TableBuilder::new(ui)
+                .id_salt(("diff_table", columns))
+                .striped(true)
+                .resizable(true)
+                .columns(Column::auto().resizable(true), columns.len())
+                .header(20.0, |mut header| {
+                    for name in columns {
+                        header.col(|ui| {
+                            ui.strong(name);
+                        });
+                    }
+                })
+                .body(|body| {
+                    body.rows(row_height, df.height(), |mut row| {
+                        let status = df
+                            .column(diff::STATUS_COLUMN)
+                            .ok()
+                            .and_then(|c| c.str().ok().and_then(|s| s.get(row.index())))
+                            .and_then(DiffStatus::from_label);
+                        for name in columns {
+                            let text = df
+                                .column(name)
+                                .and_then(|c| c.get(row.index()).map(|v| v.str_value().to_string()))
+                                .unwrap_or_default();
+                            let changed = diff::is_changed(df, row.index(), name);
+                            row.col(|ui| {
+                                if changed {
+                                    paint_cell_background(ui, Some(changed_fill));
+                                }
+                                let mut text = RichText::new(text);
+                                if name == diff::STATUS_COLUMN
+                                    && let Some(status) = status
+                                {
+                                    text = text.color(diff_color(status));
+                                }
+                                ui.add(Label::new(text).wrap_mode(TextWrapMode::Extend));
+                            });
+                        }
+                    });
+                });
+        });
+}
@@ -0,0 +1,257 @@
+//! Edit mode: changing cells, adding and deleting rows, and keeping unsaved edits from
+//! being dropped by accident.
+
+use super::*;
+
+impl Tablr {
+    pub(super) fn start_cell_edit(&mut self, df: &DataFrame, row: usize, column: &str) {
+        if column == ROW_INDEX_COLUMN || self.masked_columns.contains(column) {
+            return;
+        }
+        if self
+            .original_dataframe
+            .as_ref()
+            .is_none_or(|original| original.column(column).is_err())
+        {
+            self.notifications
+                .warning(format!("{} is computed and can't be edited", column));
+            return;
+        }
+        let row_id = df
+            .column(ROW_INDEX_COLUMN)
+            .and_then(|index| index.idx().map(|index| index.get(row)));
+        let value = df.column(column).and_then(|values| values.get(row));
+        if let (Ok(Some(row_id)), Ok(value)) = (row_id, value) {
+            let text = edit::edit_text(&value);
+            self.editing_cell = Some(CellEdit {
+                row,
+                row_id,
+                column: column.to_string(),
+                original: text.clone(),
+                text,
+                error: None,
+                focus: true,
+            });
+        }
+    }
+
+    /// Writes the edited value into the loaded data, or keeps the editor open with the
+    /// reason it was rejected.
+    pub(super) fn commit_cell_edit(&mut self) {
+        let (Some(cell), Some(df)) = (&mut self.editing_cell, &self.original_dataframe) else {
+            return;
+        };
+        if cell.text == cell.original {
+            self.editing_cell = None;
+            return;
+        }
+        match edit::set_cell(df, ROW_INDEX_COLUMN, cell.row_id, &cell.column, &cell.text) {
+            Ok(df) => {
+                let step = format!("Edit {} in row {}", cell.column, cell.row_id);
+                self.edited_cells.insert((cell.row_id, cell.column.clone()));
+                self.editing_cell = None;
+                self.replace_data(df, step);
+            }
+            Err(e) => {
+                cell.error = Some(e.to_string());
+                cell.focus = true;
+            }
+        }
+    }
+
+    pub(super) fn has_edits(&self) -> bool {
+        !self.edited_cells.is_empty() || !self.added_rows.is_empty() || self.deleted_rows > 0
+    }
+
+    /// Row indices of the selected rows, or of the focused row when nothing is selected.
+    pub(super) fn edit_target_rows(&self) -> HashSet<IdxSize> {
+        let Some(ids) = self
+            .dataframe
+            .as_ref()
+            .and_then(|df| df.column(ROW_INDEX_COLUMN).ok())
+            .and_then(|index| index.idx().ok())
+        else {
+            return HashSet::new();
+        };
+        let positions: Vec<usize> = if self.selected_rows.is_empty() {
+            self.focused_cell.iter().map(|(row, _)| *row).collect()
+        } else {
+            self.selected_rows.iter().copied().collect()
+        };
+        positions
+            .into_iter()
+            .filter_map(|row| ids.get(row))
+            .collect()
+    }
+
+    pub(super) fn add_blank_row(&mut self) {
+        let Some(df) = &self.original_dataframe else {
+            return;
+        };
+        let after = self.focused_cell.as_ref().and_then(|(row, _)| {
+            self.dataframe
+                .as_ref()?
+                .column(ROW_INDEX_COLUMN)
+                .ok()?
+                .idx()
+                .ok()?
+                .get(*row)
+        });
+        match edit::insert_blank_row(df, ROW_INDEX_COLUMN, after) {
+            Ok((df, id)) => {
+                self.added_rows.insert(id);
+                self.replace_data(df, "Add a blank row".to_string());
+                // Focus the new row, unless the filter hides it.
+                let position = self
+                    .dataframe
+                    .as_ref()
+                    .and_then(|df| df.column(ROW_INDEX_COLUMN).ok())
+                    .and_then(|index| index.idx().ok())
+                    .and_then(|ids| ids.iter().position(|row_id| row_id == Some(id)));
+                let column = self
+                    .visible_columns()
+                    .into_iter()
+                    .find(|name| name != ROW_INDEX_COLUMN);
+                if let (Some(position), Some(column)) = (position, column) {
+                    self.focused_cell = Some((position, column));
+                    self.scroll_to_row = Some((position, Some(Align::Center)));
+                }
+            }
+            Err(e) => self.notifications.error(format!("Edit error: {}", e)),
+        }
+    }
+
+    pub(super) fn delete_rows(&mut self) {
+        let ids = self.edit_target_rows();
+        let Some(df) = &self.original_dataframe else {
+            return;
+        };
+        if ids.is_empty() {
+            return;
+        }
+        match edit::delete_rows(df, ROW_INDEX_COLUMN, &ids) {
+            Ok(df) => {
+                // Rows added and then deleted were never in the files.
+                let added = ids.iter().filter(|id| self.added_rows.remove(id)).count();
+                self.deleted_rows += ids.len() - added;
+                self.edited_cells.retain(|(id, _)| !ids.contains(id));
+                self.editing_cell = None;
+                let step = format!("Delete {} rows", ids.len());
+                self.replace_data(df, step);
+            }
+            Err(e) => self.notifications.error(format!("Edit error: {}", e)),
+        }
+    }
+
+    /// Saves the loaded data with its edits, every row and column but the row index.
+    /// Returns whether the data was saved.
+    pub(super) fn save_edited_data(&mut self) -> bool {
+        let Some(df) = &self.original_dataframe else {
+            return false;
+        };
+        let Some(path) = FileDialog::new()
+            .add_filter("Parquet files", &["parquet"])
+            .add_filter("CSV files", &["csv"])
+            .set_file_name("edited.parquet")
+            .save_file()
+        else {
+            return false;
+        };
+        let df = df.drop(ROW_INDEX_COLUMN).unwrap_or_else(|_| df.clone());
+        let is_csv = path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("csv"));
+        let result = if is_csv {
+            export::write_csv(&df, &path, &self.csv_options)
+        } else {
+            export::write_parquet(&df, &path, self.parquet_compression)
+        };
+        match result {
+            Ok(()) => {
+                self.notifications.info(format!(
+                    "Saved {} rows to {}",
+                    df.height(),
+                    path.display()
+                ));
+                self.run_hook(HookEvent::Export, &[path]);
+                true
+            }
+            Err(e) => {
+                self.notifications.error(format!("Save error: {}", e));
+                false
+            }
+        }
+    }
+
+    /// Runs `action` now, or first asks whether to save the edits it would drop.
+    pub(super) fn after_unsaved_edits(&mut self, ctx: &Context, action: UnsavedAction) {
+        if self.has_edits() {
+            self.unsaved_action = Some(action);
+        } else {
+            self.run_unsaved_action(ctx, action);
+        }
+    }
+
+    fn run_unsaved_action(&mut self, ctx: &Context, action: UnsavedAction) {
+        match action {
+            UnsavedAction::Open(paths) => self.open_files(paths),
+            UnsavedAction::OpenBytes(name, bytes) => self.open_bytes(name, bytes),
+            UnsavedAction::SetRowLimit(limit) => {
+                self.row_limit = limit;
+                self.load_parquet_data(self.files_to_load.clone());
+            }
+            UnsavedAction::SetSourceColumns(enabled) => {
+                self.source_columns = enabled;
+                self.load_parquet_data(self.files_to_load.clone());
+            }
+            UnsavedAction::Close => {
+                self.close_confirmed = true;
+                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            }
+        }
+    }
+
+    /// Keeps the window open while there are unsaved edits, and asks about them instead.
+    pub(super) fn intercept_close(&mut self, ctx: &Context) {
+        if ctx.input(|i| i.viewport().close_requested())
+            && self.has_edits()
+            && !self.close_confirmed
+        {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            self.unsaved_action = Some(UnsavedAction::Close);
+        }
+    }
+
+    pub(super) fn render_unsaved_edits_prompt(&mut self, ctx: &Context) {
+        let Some(action) = &self.unsaved_action else {
+            return;
+        };
+        let description = action.description();
+        let (mut save, mut discard, mut cancel) = (false, false, false);
+        Window::new("Unsaved edits")
+            .auto_sized()
+            .collapsible(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "{} drops the changes made in edit mode. Save them first?",
+                    description
+                ));
+                ui.horizontal(|ui| {
+                    save = ui
+                        .button("Save as…")
+                        .on_hover_text("All rows and columns, as Parquet or CSV")
+                        .clicked();
+                    discard = ui.button("Discard").clicked();
+                    cancel = ui.button("Cancel").clicked();
+                });
+            });
+        if cancel {
+            self.unsaved_action = None;
+        } else if (discard || (save && self.save_edited_data()))
+            && let Some(action) = self.unsaved_action.take()
+        {
+            self.run_unsaved_action(ctx, action);
+        }
+    }
+}
@@ -0,0 +1,351 @@
+//! Writing the view out: the Export dialog, HTML and PDF output, and the manifest that
+//! records how an export was derived.
+
+use super::*;
+
+impl Tablr {
+    pub(super) fn render_export_dialog(&mut self, ctx: &Context) {
+        let mut open = self.export_dialog_open;
+        Window::new("Export")
+            .auto_sized()
+            .collapsible(false)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                let masking = !self.masked_columns.is_empty();
+                ui.add_enabled_ui(masking, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.radio_value(&mut self.export_mode, ExportMode::Masked, "Export masked");
+                        ui.radio_value(&mut self.export_mode, ExportMode::Raw, "Export raw");
+                    });
+                });
+                if masking {
+                    let mut columns: Vec<_> = self.masked_columns.iter().cloned().collect();
+                    columns.sort();
+                    ui.label(format!("Masked columns: {}", columns.join(", ")));
+                } else {
+                    ui.label("No columns are masked; data is exported as is.");
+                }
+                let hidden = self
+                    .hidden_columns
+                    .iter()
+                    .filter(|name| *name != ROW_INDEX_COLUMN)
+                    .count();
+                if hidden > 0 {
+                    ui.label(format!("{} hidden columns are left out.", hidden));
+                }
+                ui.separator();
+                self.render_csv_options(ui);
+                ui.separator();
+                ui.checkbox(&mut self.export_manifest, "Write provenance manifest")
+                    .on_hover_text(
+                        "Save source files, checksums and applied steps next to the export",
+                    );
+                let masked = masking && self.export_mode == ExportMode::Masked;
+                ui.horizontal(|ui| {
+                    ui.label("Parquet compression");
+                    ComboBox::from_id_salt("parquet_compression")
+                        .selected_text(self.parquet_compression.to_string())
+                        .show_ui(ui, |ui| {
+                            for compression in Compression::ALL {
+                                ui.selectable_value(
+                                    &mut self.parquet_compression,
+                                    compression,
+                                    compression.to_string(),
+                                );
+                            }
+                        });
+                });
+                ui.checkbox(
+                    &mut self.xlsx_autofilter,
+                    "Excel auto-filter on the header row",
+                );
+                let default_format = self.settings.export_format;
+                let formats = std::iter::once(default_format)
+                    .chain(FileFormat::ALL.into_iter().filter(|f| *f != default_format));
+                for format in formats {
+                    ui.horizontal(|ui| {
+                        if ui.button(format!("Save as {}…", format)).clicked() {
+                            self.handle_export(masked, format, false);
+                        }
+                        if format.can_append()
+                            && ui
+                                .button(format!("Append to {}…", format))
+                                .on_hover_text(
+                                    "Add the rows to an existing file with the same columns",
+                                )
+                                .clicked()
+                        {
+                            self.handle_export(masked, format, true);
+                        }
+                    });
+                }
+                if ui
+                    .button("Save as HTML…")
+                    .on_hover_text(
+                        "A page for sharing: cells as displayed, with masking and highlights",
+                    )
+                    .clicked()
+                {
+                    self.handle_export_html();
+                }
+            });
+        self.export_dialog_open = open;
+    }
+
+    fn render_csv_options(&mut self, ui: &mut Ui) {
+        let options = &mut self.csv_options;
+        egui::Grid::new("csv_options").show(ui, |ui| {
+            ui.label("Separator");
+            let current = export::SEPARATORS
+                .iter()
+                .find(|(separator, _)| *separator == options.separator)
+                .map_or("Custom", |(_, label)| label);
+            ComboBox::from_id_salt("csv_separator")
+                .selected_text(current)
+                .show_ui(ui, |ui| {
+                    for (separator, label) in export::SEPARATORS {
+                        ui.selectable_value(&mut options.separator, separator, label);
+                    }
+                });
+            ui.end_row();
+
+            ui.label("Encoding");
+            ComboBox::from_id_salt("csv_encoding")
+                .selected_text(options.encoding.to_string())
+                .show_ui(ui, |ui| {
+                    for encoding in CsvEncoding::ALL {
+                        ui.selectable_value(&mut options.encoding, encoding, encoding.to_string());
+                    }
+                });
+            ui.end_row();
+
+            ui.label("");
+            ui.checkbox(&mut options.header, "Header row");
+            ui.end_row();
+
+            ui.label("");
+            ui.checkbox(&mut options.decimal_comma, "Decimal comma (1,5)");
+            ui.end_row();
+
+            ui.label("");
+            ui.add_enabled_ui(options.encoding != CsvEncoding::Windows1252, |ui| {
+                ui.checkbox(&mut options.bom, "Byte order mark")
+                    .on_hover_text("Lets Excel recognise the encoding");
+            });
+            ui.end_row();
+        });
+        ui.horizontal(|ui| {
+            if ui.button("Excel (DE/FR)").clicked() {
+                self.csv_options = CsvOptions::excel_europe();
+            }
+            if ui.button("Standard").clicked() {
+                self.csv_options = CsvOptions::default();
+            }
+        });
+    }
+
+    pub(super) fn handle_export(&mut self, masked: bool, format: FileFormat, append: bool) {
+        let Some(df) = &self.dataframe else {
+            return;
+        };
+        let dialog =
+            FileDialog::new().add_filter(format!("{} files", format), &[format.extension()]);
+        let path = if append {
+            dialog.pick_file()
+        } else {
+            dialog
+                .set_file_name(format!("export.{}", format.extension()))
+                .save_file()
+        };
+        let Some(path) = path else {
+            return;
+        };
+
+        let result = self.export_columns(df).and_then(|df| {
+            let df = if masked {
+                pii::mask_dataframe(&df, &self.masked_columns)?
+            } else {
+                df
+            };
+            match (format, append) {
+                (FileFormat::Csv, false) => export::write_csv(&df, &path, &self.csv_options)?,
+                (FileFormat::Csv, true) => export::append_csv(&df, &path, &self.csv_options)?,
+                (FileFormat::NdJson, false) => export::write_ndjson(&df, &path)?,
+                (FileFormat::NdJson, true) => export::append_ndjson(&df, &path)?,
+                (FileFormat::Parquet, false) => {
+                    export::write_parquet(&df, &path, self.parquet_compression)?
+                }
+                (FileFormat::Excel, false) => xlsx::write_xlsx(&df, &path, self.xlsx_autofilter)?,
+                (FileFormat::Parquet | FileFormat::Excel, true) => {
+                    polars_bail!(InvalidOperation: "rows cannot be appended to {} files", format)
+                }
+            }
+            Ok(df)
+        });
+        match result {
+            Ok(df) => {
+                // A manifest describes a whole file, which an append does not produce.
+                if self.export_manifest && !append {
+                    self.write_manifest(&path, &df, masked);
+                }
+                self.notifications.info(format!(
+                    "{} {} rows to {}",
+                    if append { "Appended" } else { "Exported" },
+                    df.height(),
+                    path.display()
+                ));
+                self.run_hook(HookEvent::Export, &[path]);
+            }
+            Err(e) => self.notifications.error(format!("Export error: {}", e)),
+        }
+    }
+
+    /// The view as shown: visible columns in display order, without the row index.
+    fn export_columns(&self, df: &DataFrame) -> PolarsResult<DataFrame> {
+        df.select(self.export_column_names())
+    }
+
+    fn export_column_names(&self) -> Vec<String> {
+        self.visible_columns()
+            .into_iter()
+            .filter(|name| name != ROW_INDEX_COLUMN)
+            .collect()
+    }
+
+    /// Saves the view as a standalone HTML page with cells formatted and highlighted as
+    /// in the table.
+    fn handle_export_html(&mut self) {
+        let Some(df) = &self.dataframe else {
+            return;
+        };
+        let Some(path) = FileDialog::new()
+            .add_filter("HTML files", &["html"])
+            .set_file_name("export.html")
+            .save_file()
+        else {
+            return;
+        };
+
+        let title = format!("{} ({} rows)", self.dataset_title(), df.height());
+        let result = html::page(
+            df,
+            &self.export_column_names(),
+            &title,
+            |name, value| match value {
+                AnyValue::Null if df.column(name).unwrap().dtype() == &DataType::Boolean => {
+                    String::new()
+                }
+                value => self.format_cell(name, value),
+            },
+            |name| match self.column_align(name, df.column(name).unwrap().dtype()) {
+                CellAlign::Left => "left",
+                CellAlign::Center => "center",
+                CellAlign::Right => "right",
+            },
+            |row, name| {
+                let col_idx = self.column_names.iter().position(|n| n == name)?;
+                self.search_highlight(row, col_idx)
+            },
+        )
+        .and_then(|page| std::fs::write(&path, page).map_err(Into::into));
+        match result {
+            Ok(()) => {
+                self.run_hook(HookEvent::Export, &[path]);
+            }
+            Err(e) => self.notifications.error(format!("Export error: {}", e)),
+        }
+    }
+
+    /// Describes how the current view was derived from the loaded files.
+    fn export_steps(&self, masked: bool) -> Vec<String> {
+        let mut steps = Vec::new();
+        if self.row_limit != RowLimit::All {
+            steps.push(self.row_limit.to_string());
+        }
+        steps.extend(self.applied_steps.iter().cloned());
+        if !self.filter_expression.is_empty() {
+            steps.push(format!("Keep rows where {}", self.filter_expression));
+        }
+        if let Some(col_idx) = self.selected_filter_column {
+            steps.push(format!(
+                "Filter {} {} \"{}\"",
+                self.column_names[col_idx],
+                self.filter_type.to_string().to_lowercase(),
+                self.filter_text
+            ));
+        }
+        if let Some((description, _)) = &self.drill_down {
+            steps.push(description.clone());
+        }
+        if let Some(col_idx) = self.sort_column {
+            steps.push(format!(
+                "Sort by {} {}",
+                self.column_names[col_idx],
+                if self.sort_descending {
+                    "descending"
+                } else {
+                    "ascending"
+                }
+            ));
+        }
+        let hidden: Vec<&String> = self
+            .column_order
+            .iter()
+            .filter(|name| *name != ROW_INDEX_COLUMN && self.hidden_columns.contains(*name))
+            .collect();
+        if !hidden.is_empty() {
+            let names: Vec<&str> = hidden.iter().map(|name| name.as_str()).collect();
+            steps.push(format!("Leave out {}", names.join(", ")));
+        }
+        if masked && !self.masked_columns.is_empty() {
+            let mut columns: Vec<_> = self.masked_columns.iter().cloned().collect();
+            columns.sort();
+            steps.push(format!("Mask {}", columns.join(", ")));
+        }
+        steps
+    }
+
+    fn write_manifest(&mut self, output: &std::path::Path, exported: &DataFrame, masked: bool) {
+        let result = manifest::Manifest::new(
+            output,
+            exported,
+            &self.files_to_load,
+            self.export_steps(masked),
+        )
+        .and_then(|manifest| manifest.write(output));
+        if let Err(e) = result {
+            self.notifications.error(format!("Manifest error: {}", e));
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub(super) fn handle_print(&mut self) {
+        self.notifications
+            .warning("Printing to PDF is only available in the desktop app.");
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(super) fn handle_print(&mut self) {
+        let Some(df) = &self.dataframe else {
+            return;
+        };
+        let Some(path) = FileDialog::new()
+            .add_filter("PDF files", &["pdf"])
+            .set_file_name("table.pdf")
+            .save_file()
+        else {
+            return;
+        };
+
+        let title = format!("{} ({} rows)", self.dataset_title(), df.height());
+        let result = pii::mask_dataframe(df, &self.masked_columns)
+            .map_err(Into::into)
+            .and_then(|df| pdf::write_pdf(&df, &self.visible_columns(), &title, &path));
+        match result {
+            Ok(()) => {
+                self.run_hook(HookEvent::Export, &[path]);
+            }
+            Err(e) => self.notifications.error(format!("Print error: {}", e)),
+        }
+    }
+}
@@ -0,0 +1,367 @@
+//! Finding rows: the search bar, conditional highlighting and duplicate detection.
+
+use super::*;
+
+impl Tablr {
+    fn refresh_search(&mut self) {
+        let Some(df) = &self.dataframe else {
+            return;
+        };
+        let columns: Vec<String> = self
+            .visible_columns()
+            .into_iter()
+            .filter(|name| !self.masked_columns.contains(name))
+            .collect();
+        match search::find_matches(df, &columns, &self.search_text) {
+            Ok(matches) => {
+                self.search_matches = matches
+                    .into_iter()
+                    .map(|(row, pos)| {
+                        let idx = self.column_names.iter().position(|n| *n == columns[pos]);
+                        (row, idx.unwrap())
+                    })
+                    .collect();
+            }
+            Err(e) => {
+                self.search_matches.clear();
+                self.notifications.error(format!("Search error: {}", e));
+            }
+        }
+        self.search_current = None;
+        self.search_state.mark_computed(self.data_version);
+    }
+
+    fn step_search(&mut self, delta: isize) {
+        let count = self.search_matches.len();
+        if count == 0 {
+            return;
+        }
+        let next = match self.search_current {
+            None if delta < 0 => count - 1,
+            None => 0,
+            Some(current) => (current as isize + delta).rem_euclid(count as isize) as usize,
+        };
+        self.search_current = Some(next);
+        self.scroll_to_row = Some((self.search_matches[next].0, Some(Align::Center)));
+    }
+
+    pub(super) fn render_search_bar(&mut self, ui: &mut Ui) {
+        if self.search_state.needs_refresh(self.data_version) {
+            self.refresh_search();
+        }
+        ui.horizontal(|ui| {
+            ui.label("Find");
+            let response = ui.text_edit_singleline(&mut self.search_text);
+            if std::mem::take(&mut self.search_focus) {
+                response.request_focus();
+            }
+            if response.changed() {
+                self.search_state.reset();
+            }
+            if response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter)) {
+                let delta = if ui.input(|i| i.modifiers.shift) {
+                    -1
+                } else {
+                    1
+                };
+                self.step_search(delta);
+                response.request_focus();
+            }
+            if ui.button("◀ Previous").clicked() {
+                self.step_search(-1);
+            }
+            if ui.button("Next ▶").clicked() {
+                self.step_search(1);
+            }
+            if !self.search_text.is_empty() {
+                match self.search_current {
+                    Some(current) => {
+                        ui.label(format!("{} of {}", current + 1, self.search_matches.len()))
+                    }
+                    None => ui.label(format!("{} matches", self.search_matches.len())),
+                };
+            }
+            if ui.button("✖").on_hover_text("Close search").clicked() {
+                self.search_open = false;
+                self.search_text.clear();
+                self.search_state.reset();
+            }
+        });
+    }
+
+    pub(super) fn search_highlight(&self, row: usize, col_idx: usize) -> Option<Color32> {
+        let current = self.search_current.map(|i| self.search_matches[i]);
+        if current == Some((row, col_idx)) {
+            Some(Color32::from_rgba_unmultiplied(255, 170, 0, 160))
+        } else if self.search_matches.binary_search(&(row, col_idx)).is_ok() {
+            Some(Color32::from_rgba_unmultiplied(255, 220, 0, 50))
+        } else if self
+            .highlight_matches
+            .binary_search(&(row, col_idx))
+            .is_ok()
+            || (self.highlight_rows && self.highlight_row_set.contains(&row))
+        {
+            Some(Color32::from_rgba_unmultiplied(0, 160, 255, 50))
+        } else if self.duplicate_highlight
+            && self
+                .duplicates
+                .as_ref()
+                .is_some_and(|duplicates| duplicates.rows.contains(&row))
+        {
+            Some(Color32::from_rgba_unmultiplied(200, 80, 255, 50))
+        } else {
+            None
+        }
+    }
+
+    fn refresh_highlight(&mut self) {
+        let Some(df) = &self.dataframe else {
+            return;
+        };
+        let columns: Vec<String> = match &self.highlight_column {
+            Some(name) => vec![name.clone()],
+            None => self
+                .visible_columns()
+                .into_iter()
+                .filter(|name| !self.masked_columns.contains(name))
+                .collect(),
+        };
+        match search::find_regex_matches(df, &columns, &self.highlight_pattern) {
+            Ok(matches) => {
+                self.highlight_matches = matches
+                    .into_iter()
+                    .filter_map(|(row, pos)| {
+                        let idx = self.column_names.iter().position(|n| *n == columns[pos]);
+                        Some((row, idx?))
+                    })
+                    .collect();
+                self.highlight_matches.sort_unstable();
+                self.highlight_error = None;
+            }
+            Err(e) => {
+                self.highlight_matches.clear();
+                self.highlight_error = Some(e.to_string());
+            }
+        }
+        self.highlight_row_set = self.highlight_matches.iter().map(|(row, _)| *row).collect();
+        self.highlight_state.mark_computed(self.data_version);
+    }
+
+    pub(super) fn render_highlight_dialog(&mut self, ctx: &Context) {
+        let mut open = self.highlight_open;
+        Window::new("Highlight matching")
+            .auto_sized()
+            .collapsible(false)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.label("Colors cells matching a regular expression; all rows stay visible.");
+                let mut changed = false;
+                ui.horizontal(|ui| {
+                    changed |= ui
+                        .add(
+                            egui::TextEdit::singleline(&mut self.highlight_pattern)
+                                .hint_text("e.g. ^ERR|timeout")
+                                .font(TextStyle::Monospace),
+                        )
+                        .changed();
+                    ComboBox::from_id_salt("highlight_column")
+                        .selected_text(self.highlight_column.as_deref().unwrap_or("All columns"))
+                        .show_ui(ui, |ui| {
+                            changed |= ui
+                                .selectable_value(&mut self.highlight_column, None, "All columns")
+                                .clicked();
+                            for name in self.column_names.clone() {
+                                changed |= ui
+                                    .selectable_value(
+                                        &mut self.highlight_column,
+                                        Some(name.clone()),
+                                        name,
+                                    )
+                                    .clicked();
+                            }
+                        });
+                });
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.highlight_rows, "Color whole rows");
+                    if !self.highlight_pattern.is_empty() {
+                        ui.label(format!(
+                            "{} cells in {} rows",
+                            self.highlight_matches.len(),
+                            self.highlight_row_set.len()
+                        ));
+                    }
+                    if ui.button("Clear").clicked() {
+                        self.highlight_pattern.clear();
+                        changed = true;
+                    }
+                });
+                if let Some(error) = &self.highlight_error {
+                    ui.colored_label(Color32::RED, error);
+                }
+                if changed {
+                    self.highlight_state.reset();
+                }
+            });
+        self.highlight_open = open;
+        if self.highlight_state.needs_refresh(self.data_version) {
+            self.refresh_highlight();
+        }
+    }
+
+    /// Key columns for duplicate detection; without a selection whole rows are compared.
+    fn duplicate_key_columns(&self) -> Vec<String> {
+        if self.duplicate_keys.is_empty() {
+            self.column_names
+                .iter()
+                .filter(|name| *name != ROW_INDEX_COLUMN)
+                .cloned()
+                .collect()
+        } else {
+            self.duplicate_keys.clone()
+        }
+    }
+
+    fn refresh_duplicates(&mut self) {
+        let Some(df) = &self.dataframe else {
+            return;
+        };
+        match duplicates::find_duplicates(df, &self.duplicate_key_columns()) {
+            Ok(duplicates) => self.duplicates = Some(duplicates),
+            Err(e) => {
+                self.duplicates = None;
+                self.notifications
+                    .error(format!("Duplicate search error: {}", e));
+            }
+        }
+        self.duplicate_state.mark_computed(self.data_version);
+    }
+
+    fn show_only_duplicates(&mut self) {
+        let Some(df) = &self.dataframe else {
+            return;
+        };
+        let keys = self.duplicate_key_columns();
+        match duplicates::filter_duplicates(df, &keys) {
+            Ok(rows) => {
+                let description = if self.duplicate_keys.is_empty() {
+                    "Duplicated rows".to_string()
+                } else {
+                    format!("Rows with duplicate {}", keys.join(", "))
+                };
+                self.narrow_view(rows, description);
+            }
+            Err(e) => self
+                .notifications
+                .error(format!("Duplicate search error: {}", e)),
+        }
+    }
+
+    fn remove_duplicates(&mut self) {
+        let Some(df) = &self.original_dataframe else {
+            return;
+        };
+        let keys = self.duplicate_key_columns();
+        match duplicates::remove_duplicates(df, &keys, self.duplicate_keep_last) {
+            Ok(deduplicated) => {
+                let removed = df.height() - deduplicated.height();
+                let keep = if self.duplicate_keep_last {
+                    "last"
+                } else {
+                    "first"
+                };
+                self.duplicate_report = Some(format!(
+                    "Removed {} duplicate rows, keeping the {} of each group",
+                    removed, keep
+                ));
+                if removed > 0 {
+                    let step = if self.duplicate_keys.is_empty() {
+                        format!("Remove duplicate rows (keep {})", keep)
+                    } else {
+                        format!("Remove duplicates by {} (keep {})", keys.join(", "), keep)
+                    };
+                    self.replace_data(deduplicated, step);
+                }
+            }
+            Err(e) => self
+                .notifications
+                .error(format!("Duplicate removal error: {}", e)),
+        }
+    }
+
+    pub(super) fn render_duplicates_dialog(&mut self, ctx: &Context) {
+        let mut open = self.duplicates_open;
+        Window::new("Find duplicates")
+            .auto_sized()
+            .collapsible(false)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.label("Key columns (none selected compares whole rows)");
+                let mut changed = false;
+                ui.horizontal_wrapped(|ui| {
+                    for name in &self.column_names {
+                        if name == ROW_INDEX_COLUMN {
+                            continue;
+                        }
+                        let mut selected = self.duplicate_keys.contains(name);
+                        if ui.checkbox(&mut selected, name).changed() {
+                            if selected {
+                                self.duplicate_keys.push(name.clone());
+                            } else {
+                                self.duplicate_keys.retain(|key| key != name);
+                            }
+                            changed = true;
+                        }
+                    }
+                });
+                if changed {
+                    self.duplicates = None;
+                    self.duplicate_report = None;
+                }
+                ui.horizontal(|ui| {
+                    if ui.button("Find").clicked() {
+                        self.refresh_duplicates();
+                    }
+                    ui.checkbox(&mut self.duplicate_highlight, "Highlight rows");
+                    let found = self
+                        .duplicates
+                        .as_ref()
+                        .is_some_and(|duplicates| !duplicates.rows.is_empty());
+                    if ui
+                        .add_enabled(found, egui::Button::new("Show only duplicates"))
+                        .clicked()
+                    {
+                        self.show_only_duplicates();
+                    }
+                });
+                match &self.duplicates {
+                    Some(duplicates) if duplicates.rows.is_empty() => {
+                        ui.label("No duplicates found.");
+                    }
+                    Some(duplicates) => {
+                        ui.label(format!(
+                            "{} rows in {} groups share their keys with another row",
+                            duplicates.rows.len(),
+                            duplicates.groups
+                        ));
+                    }
+                    None => {}
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label("Keep");
+                    ui.radio_value(&mut self.duplicate_keep_last, false, "first");
+                    ui.radio_value(&mut self.duplicate_keep_last, true, "last");
+                    if ui.button("Remove duplicates").clicked() {
+                        self.remove_duplicates();
+                    }
+                });
+                if let Some(report) = &self.duplicate_report {
+                    ui.label(report);
+                }
+            });
+        self.duplicates_open = open;
+        if self.duplicates.is_some() && self.duplicate_state.needs_refresh(self.data_version) {
+            self.refresh_duplicates();
+        }
+    }
+}
@@ -0,0 +1,1005 @@
+//! The main grid: the loaded data drawn by the library's [`DataFrameTable`], with the
+//! viewer's formatting, editing, highlights and annotations in a [`TableDelegate`].
+
+use super::*;
+use crate::table::{GridColumn, TableDelegate};
+use eframe::egui::Response;
+
+/// Rows beyond this are not shown in the transposed view, as each one becomes a column.
+const MAX_TRANSPOSED_ROWS: usize = 500;
+
+impl Tablr {
+    pub(super) fn render_dataframe(&mut self, ui: &mut Ui) {
+        let Some(df) = self.dataframe.clone() else {
+            return;
+        };
+        if self.transposed {
+            self.render_transposed(ui, &df);
+            return;
+        }
+        self.page_rows = ((ui.available_height() / self.row_height) as usize).max(1);
+        let visible_columns = self.visible_columns();
+        // Leave room below the body for the totals row.
+        let footer_height = if self.footer_aggregation.is_some() {
+            20.0 + self.row_height + 3.0 * ui.spacing().item_spacing.y
+        } else {
+            0.0
+        };
+
+        let mut grid = std::mem::take(&mut self.grid);
+        // A new id per schema and set of leading columns picks up the restored widths.
+        grid.set_id_salt((
+            "main_grid",
+            self.schema_hash,
+            self.show_view_position,
+            self.annotating(),
+        ));
+        grid.set_row_height(self.row_height);
+        grid.set_columns(Some(visible_columns.clone()));
+        if let Some((row, align)) = self.scroll_to_row.take() {
+            grid.scroll_to_row_aligned(row, align);
+        }
+        let mut cells = MainGrid::new(self, &df, &visible_columns, footer_height);
+        grid.show_with(ui, &mut cells);
+        let (edit_started, edit_ended) = (cells.edit_started, cells.edit_ended);
+
+        for (col_name, width) in visible_columns
+            .iter()
+            .zip(grid.column_widths().iter().skip(self.leading_columns()))
+        {
+            self.column_widths.insert(col_name.clone(), *width);
+        }
+        self.first_visible_row = grid.first_visible_row();
+        self.grid = grid;
+
+        // Committing may reorder the rows, so a double-click in the same frame only ends
+        // the previous edit.
+        match edit_ended {
+            Some(true) => self.commit_cell_edit(),
+            Some(false) => self.editing_cell = None,
+            None => {
+                if let Some((row, column)) = edit_started {
+                    self.start_cell_edit(&df, row, &column);
+                }
+            }
+        }
+    }
+
+    /// Shows the chosen aggregate of each column in a row pinned below the table body.
+    fn render_table_footer(
+        &mut self,
+        ui: &mut Ui,
+        df: &DataFrame,
+        visible_columns: &[String],
+        widths: &[f32],
+    ) {
+        let Some(aggregation) = self.footer_aggregation else {
+            return;
+        };
+        if self.footer_computed_for != Some((self.data_version, aggregation)) {
+            let columns: Vec<String> = visible_columns
+                .iter()
+                .filter(|name| *name != ROW_INDEX_COLUMN)
+                .cloned()
+                .collect();
+            match aggregate::aggregate_columns(df, &columns, aggregation) {
+                Ok(values) => self.footer_values = values,
+                Err(e) => self
+                    .notifications
+                    .error(format!("Aggregation error: {}", e)),
+            }
+            self.footer_computed_for = Some((self.data_version, aggregation));
+        }
+
+        let stroke = ui.visuals().widgets.noninteractive.bg_stroke;
+        let top = ui.cursor().top();
+        ui.painter().hline(ui.max_rect().x_range(), top, stroke);
+        ui.horizontal(|ui| {
+            let mut widths = widths.iter().copied();
+            let mut label = Some(aggregation.to_string());
+            if self.show_view_position {
+                let width = widths.next().unwrap_or_default();
+                let text = label.take().unwrap_or_default();
+                ui.add_sized(
+                    [width, self.row_height],
+                    Label::new(RichText::new(text).weak()),
+                );
+            }
+            if self.annotating() {
+                let width = widths.next().unwrap_or_default();
+                ui.allocate_exact_size(egui::vec2(width, self.row_height), Sense::hover());
+            }
+            for (col_name, width) in visible_columns.iter().zip(widths) {
+                let (text, align) = match (self.footer_values.get(col_name), df.column(col_name)) {
+                    (Some(value), Ok(column)) => (
+                        RichText::new(self.format_cell(col_name, value)).strong(),
+                        self.column_align(col_name, column.dtype()),
+                    ),
+                    _ => (
+                        RichText::new(label.take().unwrap_or_default()).weak(),
+                        CellAlign::Left,
+                    ),
+                };
+                let (rect, _) =
+                    ui.allocate_exact_size(egui::vec2(width, self.row_height), Sense::hover());
+                ui.scope_builder(
+                    egui::UiBuilder::new().max_rect(rect).layout(align.layout()),
+                    |ui| {
+                        ui.add(Label::new(text).wrap_mode(TextWrapMode::Truncate));
+                    },
+                );
+            }
+        });
+    }
+
+    /// Renders each visible column as a row and each data row as a column.
+    fn render_transposed(&mut self, ui: &mut Ui, df: &DataFrame) {
+        let fields: Vec<String> = self
+            .visible_columns()
+            .into_iter()
+            .filter(|name| name != ROW_INDEX_COLUMN)
+            .collect();
+        let num_rows = df.height().min(MAX_TRANSPOSED_ROWS);
+        if df.height() > num_rows {
+            ui.weak(format!(
+                "Showing the first {} of {} rows. Filter the data to see others.",
+                num_rows,
+                df.height()
+            ));
+        }
+        let row_labels: Vec<String> = match df.column(ROW_INDEX_COLUMN) {
+            Ok(index) => (0..num_rows)
+                .map(|row| {
+                    index
+                        .get(row)
+                        .map(|v| format!("Row {}", v))
+                        .unwrap_or_default()
+                })
+                .collect(),
+            Err(_) => (0..num_rows).map(|row| format!("Row {}", row)).collect(),
+        };
+        ScrollArea::horizontal()
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                TableBuilder::new(ui)
+                    .id_salt(("transposed", self.schema_hash, num_rows))
+                    .striped(true)
+                    .resizable(true)
+                    .column(Column::auto().resizable(true))
+                    .columns(Column::auto().resizable(true), num_rows)
+                    .header(20.0, |mut header| {
+                        header.col(|ui| {
+                            ui.strong("Column");
+                        });
+                        for label in &row_labels {
+                            header.col(|ui| {
+                                ui.strong(label);
+                            });
+                        }
+                    })
+                    .body(|body| {
+                        body.rows(self.row_height, fields.len(), |mut row| {
+                            let col_name = &fields[row.index()];
+                            row.col(|ui| {
+                                ui.strong(col_name);
+                            });
+                            let Ok(column) = df.column(col_name) else {
+                                return;
+                            };
+                            for data_row in 0..num_rows {
+                                let text = column
+                                    .get(data_row)
+                                    .map(|value| self.format_cell(col_name, &value))
+                                    .unwrap_or_default();
+                                row.col(|ui| {
+                                    ui.add(Label::new(text).wrap_mode(TextWrapMode::Extend));
+                                });
+                            }
+                        });
+                    });
+            });
+    }
+
+    /// Moves the focused cell with the arrow keys, PageUp/PageDown, Home/End and
+    /// Ctrl+Home/End, scrolling the table to keep it in view.
+    pub(super) fn handle_grid_navigation(&mut self, ctx: &Context) {
+        let Some(num_rows) = self.dataframe.as_ref().map(DataFrame::height) else {
+            return;
+        };
+        if num_rows == 0 || ctx.wants_keyboard_input() {
+            return;
+        }
+        let columns = self.visible_columns();
+        if columns.is_empty() {
+            return;
+        }
+
+        let (row, col) = match &self.focused_cell {
+            Some((row, name)) => (
+                (*row).min(num_rows - 1),
+                columns.iter().position(|c| c == name).unwrap_or(0),
+            ),
+            None => (0, 0),
+        };
+        let page = self.page_rows;
+        let last_row = num_rows - 1;
+        let last_col = columns.len() - 1;
+
+        let target = ctx.input_mut(|i| {
+            if i.consume_key(Modifiers::COMMAND, Key::Home) {
+                Some((0, col))
+            } else if i.consume_key(Modifiers::COMMAND, Key::End) {
+                Some((last_row, col))
+            } else if i.consume_key(Modifiers::NONE, Key::ArrowUp) {
+                Some((row.saturating_sub(1), col))
+            } else if i.consume_key(Modifiers::NONE, Key::ArrowDown) {
+                Some(((row + 1).min(last_row), col))
+            } else if i.consume_key(Modifiers::NONE, Key::ArrowLeft) {
+                Some((row, col.saturating_sub(1)))
+            } else if i.consume_key(Modifiers::NONE, Key::ArrowRight) {
+                Some((row, (col + 1).min(last_col)))
+            } else if i.consume_key(Modifiers::NONE, Key::PageUp) {
+                Some((row.saturating_sub(page), col))
+            } else if i.consume_key(Modifiers::NONE, Key::PageDown) {
+                Some(((row + page).min(last_row), col))
+            } else if i.consume_key(Modifiers::NONE, Key::Home) {
+                Some((row, 0))
+            } else if i.consume_key(Modifiers::NONE, Key::End) {
+                Some((row, last_col))
+            } else {
+                None
+            }
+        });
+
+        if let Some((row, col)) = target {
+            self.focused_cell = Some((row, columns[col].clone()));
+            self.focus_moved = true;
+            self.scroll_to_row = Some((row, None));
+        }
+    }
+
+    /// Draws a thin bar along the bottom of a header cell whose red part is the share of
+    /// nulls in the column for the current view.
+    fn paint_null_bar(&self, ui: &mut Ui, col_name: &str) {
+        let Some(column) = self
+            .dataframe
+            .as_ref()
+            .and_then(|df| df.column(col_name).ok())
+        else {
+            return;
+        };
+        if column.is_empty() || column.null_count() == 0 {
+            return;
+        }
+        let fraction = column.null_count() as f32 / column.len() as f32;
+        let cell = ui.max_rect();
+        let track = egui::Rect::from_min_max(
+            egui::pos2(cell.left(), cell.bottom() - 3.0),
+            cell.right_bottom(),
+        );
+        let painter = ui.painter();
+        painter.rect_filled(track, 0.0, ui.visuals().faint_bg_color);
+        let mut filled = track;
+        filled.set_width(track.width() * fraction);
+        painter.rect_filled(filled, 0.0, Color32::from_rgb(220, 60, 60));
+    }
+
+    fn render_column_popover(&mut self, ui: &mut Ui, col_name: &str) {
+        if let Some(doc) = self.dictionary.get(col_name) {
+            if !doc.description.is_empty() {
+                ui.label(&doc.description);
+            }
+            if !doc.unit.is_empty() {
+                ui.label(format!("Unit: {}", doc.unit));
+            }
+        }
+        let Some(df) = &self.dataframe else {
+            return;
+        };
+        let Ok(column) = df.column(col_name) else {
+            return;
+        };
+        ui.weak(column.dtype().to_string());
+        if column.null_count() > 0 {
+            ui.weak(format!(
+                "{:.1}% null ({} rows)",
+                100.0 * column.null_count() as f64 / column.len() as f64,
+                column.null_count()
+            ));
+        }
+        if self.column_charts_version != self.data_version {
+            self.column_charts.clear();
+            self.column_charts_version = self.data_version;
+        }
+        let chart = self
+            .column_charts
+            .entry(col_name.to_string())
+            .or_insert_with(|| minichart::compute(column));
+        if let Some(chart) = chart {
+            minichart::show(ui, chart);
+            if matches!(chart, minichart::MiniChart::TopValues(_))
+                && ui
+                    .small_button("📊 Value counts")
+                    .on_hover_text("Bar chart of the most frequent values")
+                    .clicked()
+            {
+                self.open_bar_chart(col_name);
+            }
+        }
+    }
+
+    fn render_column_menu(&mut self, ui: &mut Ui, col_name: &String) {
+        ui.menu_button("Display as", |ui| {
+            let current = self
+                .column_display
+                .get(col_name)
+                .copied()
+                .unwrap_or_default();
+            for mode in DisplayMode::ALL {
+                if ui.radio(current == mode, mode.to_string()).clicked() {
+                    self.column_display.insert(col_name.clone(), mode);
+                    ui.close_menu();
+                }
+            }
+        });
+        if ui.button("Hide column").clicked() {
+            self.hidden_columns.insert(col_name.clone());
+            ui.close_menu();
+        }
+        ui.horizontal(|ui| {
+            if ui.button("⬅ Move left").clicked() {
+                self.move_column(col_name, -1);
+                ui.close_menu();
+            }
+            if ui.button("Move right ➡").clicked() {
+                self.move_column(col_name, 1);
+                ui.close_menu();
+            }
+        });
+        ui.separator();
+        ui.menu_button("Text", |ui| {
+            let current = self.column_wrap(col_name);
+            for wrap in [CellWrap::Extend, CellWrap::Wrap, CellWrap::Truncate] {
+                if ui.radio(current == wrap, wrap.to_string()).clicked() {
+                    self.column_wrap.insert(col_name.clone(), wrap);
+                    ui.close_menu();
+                }
+            }
+        });
+        ui.menu_button("Align", |ui| {
+            let Some(dtype) = self
+                .original_dataframe
+                .as_ref()
+                .and_then(|df| df.column(col_name).ok())
+                .map(|column| column.dtype().clone())
+            else {
+                return;
+            };
+            let current = self.column_align(col_name, &dtype);
+            for align in CellAlign::ALL {
+                if ui.radio(current == align, align.to_string()).clicked() {
+                    self.column_align.insert(col_name.clone(), align);
+                    ui.close_menu();
+                }
+            }
+        });
+        if col_name != ROW_INDEX_COLUMN {
+            if self
+                .column_rename
+                .as_ref()
+                .is_none_or(|(name, _)| name != col_name)
+            {
+                self.column_rename = Some((col_name.clone(), col_name.clone()));
+            }
+            let mut rename = None;
+            if let Some((_, text)) = &mut self.column_rename {
+                ui.horizontal(|ui| {
+                    let response = ui.add(egui::TextEdit::singleline(text).desired_width(140.0));
+                    let submitted =
+                        response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+                    if ui.button("Rename").clicked() || submitted {
+                        rename = Some(text.clone());
+                    }
+                });
+            }
+            if let Some(new) = rename {
+                self.column_rename = None;
+                self.rename_column(col_name, &new);
+                ui.close_menu();
+            }
+            if ui.button("Drop column").clicked() {
+                self.drop_column(col_name);
+                ui.close_menu();
+            }
+            let is_string = self
+                .original_dataframe
+                .as_ref()
+                .and_then(|df| df.column(col_name).ok())
+                .is_some_and(|column| column.dtype() == &DataType::String);
+            if is_string && ui.button("Split column…").clicked() {
+                self.split_column = Some(col_name.clone());
+                self.split_column_open = true;
+                ui.close_menu();
+            }
+            if is_string && ui.button("Find and replace…").clicked() {
+                self.replace_column = Some(col_name.clone());
+                self.replace_preview = None;
+                self.replace_open = true;
+                ui.close_menu();
+            }
+            let dtype = self
+                .original_dataframe
+                .as_ref()
+                .and_then(|df| df.column(col_name).ok())
+                .map(|column| column.dtype().clone());
+            if dtype.as_ref().is_some_and(nested::is_list)
+                && ui
+                    .button("Explode")
+                    .on_hover_text("One row per list element")
+                    .clicked()
+            {
+                self.explode_column(col_name);
+                ui.close_menu();
+            }
+            if dtype.as_ref().is_some_and(nested::is_struct)
+                && ui
+                    .button("Unnest")
+                    .on_hover_text("One column per struct field")
+                    .clicked()
+            {
+                self.unnest_column(col_name);
+                ui.close_menu();
+            }
+            if ui.button("Value counts…").clicked() {
+                self.open_bar_chart(col_name);
+                ui.close_menu();
+            }
+            if dtype.as_ref().is_some_and(scatter::is_plottable)
+                && ui.button("Histogram…").clicked()
+            {
+                if self.histogram_column.as_ref() != Some(col_name) {
+                    self.histogram_column = Some(col_name.clone());
+                    self.histogram_state.reset();
+                    self.histogram_view.reset();
+                }
+                self.histogram_open = true;
+                ui.close_menu();
+            }
+            if dtype.as_ref().is_some_and(line_chart::is_time)
+                && ui.button("Plot over time…").clicked()
+            {
+                if self.line_x.as_ref() != Some(col_name) {
+                    self.line_x = Some(col_name.clone());
+                    self.line_state.reset();
+                    self.line_view.reset();
+                }
+                self.line_open = true;
+                ui.close_menu();
+            }
+            ui.menu_button("Cast to…", |ui| {
+                for target in CastTarget::ALL {
+                    if ui.button(target.to_string()).clicked() {
+                        self.cast_column(col_name, target);
+                        ui.close_menu();
+                    }
+                }
+            });
+        }
+        let is_integer = self
+            .original_dataframe
+            .as_ref()
+            .and_then(|df| df.column(col_name).ok())
+            .is_some_and(|column| column.dtype().is_integer());
+        if is_integer && col_name != ROW_INDEX_COLUMN {
+            ui.menu_button("Convert epoch to datetime", |ui| {
+                let detected = self.epoch_columns.get(col_name.as_str()).copied();
+                for unit in EpochUnit::ALL {
+                    let label = if detected == Some(unit) {
+                        format!("From {} (detected)", unit)
+                    } else {
+                        format!("From {}", unit)
+                    };
+                    if ui.button(label).clicked() {
+                        self.convert_epoch_column(col_name, unit);
+                        ui.close_menu();
+                    }
+                }
+            });
+        }
+        let is_temporal = self
+            .original_dataframe
+            .as_ref()
+            .and_then(|df| df.column(col_name).ok())
+            .is_some_and(|column| {
+                matches!(column.dtype(), DataType::Date | DataType::Datetime(..))
+            });
+        if is_temporal {
+            ui.menu_button("Bucket by", |ui| {
+                for unit in BucketUnit::ALL {
+                    if ui.button(unit.to_string()).clicked() {
+                        self.add_bucket_column(col_name, unit);
+                        ui.close_menu();
+                    }
+                }
+                if self.settings.fiscal_year_start_month != 1 {
+                    ui.weak("Quarters and years use the fiscal calendar from Settings.");
+                }
+            });
+        }
+        let is_binary = self
+            .original_dataframe
+            .as_ref()
+            .and_then(|df| df.column(col_name).ok())
+            .is_some_and(|column| column.dtype() == &DataType::Binary);
+        if is_binary {
+            let mut is_image = self.image_columns.contains(col_name);
+            if ui.checkbox(&mut is_image, "Show as images").changed() {
+                if is_image {
+                    self.image_columns.insert(col_name.clone());
+                } else {
+                    self.image_columns.remove(col_name);
+                }
+                ui.close_menu();
+            }
+        }
+        let mut monospace = self.monospace_columns.contains(col_name);
+        if ui
+            .checkbox(&mut monospace, "Monospace")
+            .on_hover_text("Line up fixed-width IDs, hashes and numbers")
+            .changed()
+        {
+            if monospace {
+                self.monospace_columns.insert(col_name.clone());
+            } else {
+                self.monospace_columns.remove(col_name);
+            }
+            ui.close_menu();
+        }
+        let mut is_json = self.json_columns.contains(col_name);
+        if ui.checkbox(&mut is_json, "Show as JSON").changed() {
+            if is_json {
+                self.json_columns.insert(col_name.clone());
+            } else {
+                self.json_columns.remove(col_name);
+            }
+            ui.close_menu();
+        }
+    }
+
+    pub(super) fn column_align(&self, col_name: &str, dtype: &DataType) -> CellAlign {
+        self.column_align
+            .get(col_name)
+            .copied()
+            .unwrap_or_else(|| CellAlign::default_for(dtype))
+    }
+
+    fn column_wrap(&self, col_name: &str) -> CellWrap {
+        self.column_wrap
+            .get(col_name)
+            .copied()
+            .unwrap_or(CellWrap::Extend)
+    }
+
+    /// Text shown for a cell, applying masking and the data dictionary's unit formatting.
+    pub(super) fn format_cell(&self, col_name: &str, value: &AnyValue) -> String {
+        if value.is_null() {
+            return value.to_string();
+        }
+        if self.masked_columns.contains(col_name) {
+            return pii::mask_value(&value.to_string());
+        }
+        if let AnyValue::Boolean(flag) = value {
+            return if *flag { "✓" } else { "✗" }.to_string();
+        }
+        let display_mode = self
+            .column_display
+            .get(col_name)
+            .copied()
+            .unwrap_or_default();
+        display_mode
+            .format(value)
+            .or_else(|| {
+                self.dictionary
+                    .get(col_name)
+                    .filter(|doc| !doc.unit.is_empty())
+                    .and_then(|doc| format::format_with_unit(value, &doc.unit))
+            })
+            .or_else(|| {
+                format::format_temporal(
+                    value,
+                    &self.settings.date_format,
+                    &self.settings.datetime_format,
+                )
+            })
+            .unwrap_or_else(|| value.to_string())
+    }
+}
+
+/// The viewer's cells for [`DataFrameTable::show_with`], for one frame.
+struct MainGrid<'a> {
+    app: &'a mut Tablr,
+    df: &'a DataFrame,
+    columns: &'a [String],
+    /// Position of each shown column in `Tablr::column_names`, for the search highlights.
+    col_indices: HashMap<&'a str, usize>,
+    row_index: Option<&'a polars::prelude::Column>,
+    row_ids: Option<&'a IdxCa>,
+    annotation_keys: Option<&'a polars::prelude::Column>,
+    focus_moved: bool,
+    footer_height: f32,
+    edit_started: Option<(usize, String)>,
+    /// `Some(true)` keeps the edited value, `Some(false)` drops it.
+    edit_ended: Option<bool>,
+}
+
+/// The viewer's own columns in front of the data.
+enum Leading {
+    Position,
+    Annotation,
+}
+
+impl<'a> MainGrid<'a> {
+    fn new(
+        app: &'a mut Tablr,
+        df: &'a DataFrame,
+        columns: &'a [String],
+        footer_height: f32,
+    ) -> Self {
+        let col_indices = columns
+            .iter()
+            .filter_map(|name| {
+                let index = app.column_names.iter().position(|n| n == name)?;
+                Some((name.as_str(), index))
+            })
+            .collect();
+        let row_index = df.column(ROW_INDEX_COLUMN).ok();
+        let annotation_keys = app
+            .annotating()
+            .then(|| df.column(&app.annotations.key_column).ok())
+            .flatten();
+        let focus_moved = std::mem::take(&mut app.focus_moved);
+        Self {
+            app,
+            df,
+            columns,
+            col_indices,
+            row_index,
+            row_ids: row_index.and_then(|index| index.idx().ok()),
+            annotation_keys,
+            focus_moved,
+            footer_height,
+            edit_started: None,
+            edit_ended: None,
+        }
+    }
+
+    fn leading(&self, index: usize) -> Leading {
+        if self.app.show_view_position && index == 0 {
+            Leading::Position
+        } else {
+            Leading::Annotation
+        }
+    }
+
+    fn annotation_key(&self, row: usize) -> String {
+        self.annotation_keys
+            .and_then(|keys| keys.get(row).ok())
+            .map(|value| value.str_value().to_string())
+            .unwrap_or_default()
+    }
+
+    fn data_header(&mut self, ui: &mut Ui, col_name: &str) {
+        let app = &mut *self.app;
+        let Some(&i) = self.col_indices.get(col_name) else {
+            return;
+        };
+        let response = ui
+            .add(
+                Label::new(
+                    RichText::new(format!(
+                        "{} {}",
+                        col_name,
+                        if Some(i) == app.sort_column {
+                            if app.sort_descending { "⬇" } else { "⬆" }
+                        } else {
+                            ""
+                        }
+                    ))
+                    .strong(),
+                )
+                .wrap_mode(TextWrapMode::Extend),
+            )
+            .on_hover_cursor(CursorIcon::Default);
+        if app.show_null_bars {
+            app.paint_null_bar(ui, col_name);
+        }
+        let response = response.on_hover_ui(|ui| app.render_column_popover(ui, col_name));
+        if response.clicked() {
+            if Some(i) == app.sort_column {
+                app.sort_descending = !app.sort_descending;
+            } else {
+                app.sort_column = Some(i);
+                app.sort_descending = false;
+            }
+            app.apply_sort();
+        }
+        let col_name = col_name.to_string();
+        response.context_menu(|ui| app.render_column_menu(ui, &col_name));
+    }
+
+    fn data_cell(&mut self, ui: &mut Ui, row: usize, col_name: &str) {
+        let app = &mut *self.app;
+        let Ok(column) = self.df.column(col_name) else {
+            ui.add(Label::new("Col?").wrap_mode(TextWrapMode::Extend));
+            return;
+        };
+        let highlight = self
+            .col_indices
+            .get(col_name)
+            .and_then(|&col_idx| app.search_highlight(row, col_idx));
+        let bookmark_edge = self.columns.first().is_some_and(|first| first == col_name)
+            && app.bookmark_positions.binary_search(&row).is_ok();
+        let focused = app
+            .focused_cell
+            .as_ref()
+            .is_some_and(|(r, c)| *r == row && c == col_name);
+        let edited = (!app.edited_cells.is_empty() || !app.added_rows.is_empty())
+            && self.row_ids.and_then(|ids| ids.get(row)).is_some_and(|id| {
+                app.added_rows.contains(&id)
+                    || app.edited_cells.contains(&(id, col_name.to_string()))
+            });
+        let focus_moved = self.focus_moved;
+        let decorate = |ui: &mut Ui| {
+            paint_cell_background(ui, highlight);
+            if edited {
+                let corner = ui.max_rect().right_top();
+                ui.painter().add(egui::Shape::convex_polygon(
+                    vec![
+                        corner,
+                        corner + egui::vec2(0.0, 7.0),
+                        corner - egui::vec2(7.0, 0.0),
+                    ],
+                    EDITED_COLOR,
+                    egui::Stroke::NONE,
+                ));
+            }
+            if bookmark_edge {
+                let mut edge = ui.max_rect();
+                edge.set_width(3.0);
+                ui.painter().rect_filled(edge, 0.0, BOOKMARK_COLOR);
+            }
+            if focused {
+                let stroke = ui.visuals().selection.stroke;
+                ui.painter()
+                    .rect_stroke(ui.max_rect(), 0.0, stroke, egui::StrokeKind::Inside);
+                if focus_moved {
+                    ui.scroll_to_rect(ui.max_rect(), None);
+                }
+            }
+        };
+
+        let masked = app.masked_columns.contains(col_name);
+        let value = column.get(row);
+        let is_bool = column.dtype() == &DataType::Boolean;
+        let align = app.column_align(col_name, column.dtype());
+        let monospace = app.monospace_columns.contains(col_name);
+        let cell_text = match &value {
+            Ok(AnyValue::Null) if is_bool => String::new(),
+            Ok(any_value) => app.format_cell(col_name, any_value),
+            Err(_) => "Error".to_string(),
+        };
+        // Relative times show the absolute value on hover.
+        let absolute = value.as_ref().ok().filter(|v| {
+            !masked
+                && !v.is_null()
+                && app.column_display.get(col_name) == Some(&DisplayMode::RelativeTime)
+        });
+        let link = value
+            .as_ref()
+            .ok()
+            .and_then(|v| v.get_str())
+            .filter(|text| app.settings.clickable_links && !masked && is_url(text))
+            .map(str::to_string);
+        let image_bytes = match &value {
+            Ok(AnyValue::Binary(bytes)) => Some(bytes.to_vec()),
+            Ok(AnyValue::BinaryOwned(bytes)) => Some(bytes.clone()),
+            _ => None,
+        };
+        let renderer = value
+            .as_ref()
+            .ok()
+            .filter(|v| !masked && !v.is_null())
+            .and_then(|v| {
+                app.renderers
+                    .get(col_name, column.dtype())
+                    .map(|renderer| (renderer, v))
+            });
+        let editing = app
+            .editing_cell
+            .as_mut()
+            .filter(|cell| cell.row == row && cell.column == col_name);
+        if let Some(cell) = editing {
+            let mut text_edit =
+                egui::TextEdit::singleline(&mut cell.text).desired_width(f32::INFINITY);
+            if cell.error.is_some() {
+                text_edit = text_edit.text_color(ui.visuals().error_fg_color);
+            }
+            let mut response = ui.add(text_edit);
+            if std::mem::take(&mut cell.focus) {
+                response.request_focus();
+            }
+            if let Some(error) = &cell.error {
+                response = response.on_hover_text(error);
+            }
+            // Enter or clicking elsewhere keeps the value; Escape drops it.
+            if response.lost_focus() {
+                self.edit_ended = Some(!ui.input(|i| i.key_pressed(Key::Escape)));
+            }
+        } else if let Some((renderer, value)) = renderer {
+            decorate(ui);
+            renderer.render(ui, column.dtype(), value);
+        } else if app.image_columns.contains(col_name)
+            && !masked
+            && let Some(bytes) = image_bytes
+        {
+            // Image URIs are keyed by the original row so they stay cached across sorts.
+            let row_id = self
+                .row_index
+                .and_then(|index| index.get(row).ok())
+                .map(|value| value.to_string())
+                .unwrap_or_else(|| row.to_string());
+            let uri = format!("bytes://tablr/{}/{}", col_name, row_id);
+            let thumbnail_height = app.row_height - 2.0;
+            decorate(ui);
+            let response = ui
+                .add(
+                    egui::Image::from_bytes(uri.clone(), bytes)
+                        .max_height(thumbnail_height)
+                        .max_width(thumbnail_height * 4.0)
+                        .sense(Sense::click()),
+                )
+                .on_hover_cursor(CursorIcon::PointingHand);
+            if response.clicked() {
+                let title = format!("{} · row {}", col_name, row_id);
+                app.image_cell = Some((title, uri));
+            }
+        } else if let Some(url) = link {
+            decorate(ui);
+            ui.hyperlink_to(&cell_text, url);
+        } else if app.json_columns.contains(col_name) && !masked {
+            decorate(ui);
+            let response = ui
+                .add(
+                    Label::new(&cell_text)
+                        .wrap_mode(TextWrapMode::Truncate)
+                        .sense(Sense::click()),
+                )
+                .on_hover_cursor(CursorIcon::PointingHand);
+            if response.clicked() {
+                let title = format!("{} · row {}", col_name, row);
+                let parsed = serde_json::from_str(&cell_text).map_err(|e| e.to_string());
+                app.json_cell = Some((title, parsed));
+            }
+        } else {
+            let wrap_mode = app.column_wrap(col_name).text_wrap_mode();
+            decorate(ui);
+            let mut text = RichText::new(&cell_text);
+            if monospace {
+                text = text.monospace();
+            }
+            let label = Label::new(text).wrap_mode(wrap_mode);
+            let response = ui.with_layout(align.layout(), |ui| ui.add(label)).inner;
+            if let Some(absolute) = &absolute {
+                response.on_hover_text(absolute.to_string());
+            }
+        }
+    }
+}
+
+impl TableDelegate for MainGrid<'_> {
+    fn leading_columns(&self) -> usize {
+        self.app.leading_columns()
+    }
+
+    fn initial_width(&self, column: &str) -> Option<f32> {
+        self.app.column_widths.get(column).copied()
+    }
+
+    fn clip(&self, column: &str) -> bool {
+        self.app.column_wrap(column) != CellWrap::Extend
+    }
+
+    fn header(&mut self, ui: &mut Ui, column: GridColumn<'_>) {
+        match column {
+            GridColumn::Data(name) => self.data_header(ui, name),
+            GridColumn::Leading(index) => match self.leading(index) {
+                Leading::Position => {
+                    ui.add(Label::new(RichText::new("#").strong()).wrap_mode(TextWrapMode::Extend))
+                        .on_hover_text("Position in the current filtered and sorted view");
+                }
+                Leading::Annotation => {
+                    ui.add(
+                        Label::new(RichText::new("📝").strong()).wrap_mode(TextWrapMode::Extend),
+                    )
+                    .on_hover_text(format!(
+                        "Notes and tags by {}; click a row's cell to edit",
+                        self.app.annotations.key_column
+                    ));
+                }
+            },
+        }
+    }
+
+    fn cell(&mut self, ui: &mut Ui, row: usize, column: GridColumn<'_>) {
+        match column {
+            GridColumn::Data(name) => self.data_cell(ui, row, name),
+            GridColumn::Leading(index) => match self.leading(index) {
+                Leading::Position => {
+                    ui.add(Label::new(RichText::new((row + 1).to_string()).weak()));
+                }
+                Leading::Annotation => {
+                    let annotation = self.app.annotations.get(&self.annotation_key(row));
+                    ui.horizontal(|ui| {
+                        if let Some(tag) = annotation.and_then(|a| a.tag) {
+                            ui.colored_label(tag.color(), "⏺");
+                        }
+                        if annotation.is_some_and(|a| !a.note.trim().is_empty()) {
+                            ui.label("📝");
+                        }
+                    });
+                }
+            },
+        }
+    }
+
+    fn cell_response(&mut self, row: usize, column: GridColumn<'_>, response: &Response) {
+        match column {
+            GridColumn::Data(name) => {
+                if response.clicked() {
+                    self.app.focused_cell = Some((row, name.to_string()));
+                    let modifiers = response.ctx.input(|i| i.modifiers);
+                    self.app.select_row(row, modifiers);
+                }
+                if self.app.edit_mode && response.double_clicked() {
+                    self.edit_started = Some((row, name.to_string()));
+                }
+            }
+            GridColumn::Leading(index) => {
+                if !matches!(self.leading(index), Leading::Annotation) {
+                    return;
+                }
+                let key = self.annotation_key(row);
+                let annotation = self.app.annotations.get(&key);
+                let response = response.clone().on_hover_cursor(CursorIcon::PointingHand);
+                let response = match annotation {
+                    Some(annotation) if !annotation.note.trim().is_empty() => {
+                        response.on_hover_text(&annotation.note)
+                    }
+                    _ => response.on_hover_text("Add a note or tag"),
+                };
+                if response.clicked() {
+                    let annotation = annotation.cloned().unwrap_or_default();
+                    self.app.annotation_edit = Some((key, annotation));
+                }
+            }
+        }
+    }
+
+    fn is_row_selected(&self, row: usize) -> bool {
+        self.app.highlighted_row == Some(row) || self.app.selected_rows.contains(&row)
+    }
+
+    fn footer_height(&self) -> f32 {
+        self.footer_height
+    }
+
+    fn footer(&mut self, ui: &mut Ui, widths: &[f32]) {
+        self.app
+            .render_table_footer(ui, self.df, self.columns, widths);
+    }
+}
+
+fn is_url(text: &str) -> bool {
+    (text.starts_with("https://") || text.starts_with("http://"))
+        && !text.contains(char::is_whitespace)
+}
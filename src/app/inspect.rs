@@ -0,0 +1,776 @@
+//! Panels that describe the data rather than change it: profile, describe, schema and
+//! Parquet metadata, similar columns, the PII scan and diagnostics.
+
+use super::*;
+
+impl Tablr {
+    pub(super) fn refresh_schema_compare(&mut self) {
+        match schema_compare::compare(&self.files_to_load) {
+            Ok(columns) => self.schema_compare = columns,
+            Err(e) => {
+                self.schema_compare.clear();
+                self.notifications
+                    .error(format!("Schema comparison error: {}", e));
+            }
+        }
+        self.schema_compare_for = self.files_to_load.clone();
+    }
+
+    pub(super) fn render_schema_compare_dialog(&mut self, ctx: &Context) {
+        if self.schema_compare_open && self.schema_compare_for != self.files_to_load {
+            self.refresh_schema_compare();
+        }
+        let mut open = self.schema_compare_open;
+        Window::new("Compare schemas")
+            .default_size([640.0, 400.0])
+            .collapsible(false)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                let differing = self
+                    .schema_compare
+                    .iter()
+                    .filter(|column| !column.is_consistent())
+                    .count();
+                ui.horizontal(|ui| {
+                    ui.label(format!(
+                        "{} columns across {} files, {} differ",
+                        self.schema_compare.len(),
+                        self.schema_compare_for.len(),
+                        differing
+                    ));
+                    ui.checkbox(
+                        &mut self.schema_compare_differences_only,
+                        "Only differences",
+                    );
+                });
+                ui.separator();
+                ScrollArea::both()
+                    .auto_shrink([false, false])
+                    .show(ui, |ui| {
+                        egui::Grid::new("schema_compare")
+                            .striped(true)
+                            .show(ui, |ui| {
+                                ui.strong("Column");
+                                for path in &self.schema_compare_for {
+                                    ui.strong(file_label(path));
+                                }
+                                ui.end_row();
+                                for column in &self.schema_compare {
+                                    if self.schema_compare_differences_only
+                                        && column.is_consistent()
+                                    {
+                                        continue;
+                                    }
+                                    ui.label(&column.name);
+                                    for (file, dtype) in column.types.iter().enumerate() {
+                                        match dtype {
+                                            None => {
+                                                ui.colored_label(Color32::RED, "missing");
+                                            }
+                                            Some(dtype) if column.is_mismatched(file) => {
+                                                ui.colored_label(
+                                                    Color32::ORANGE,
+                                                    dtype.to_string(),
+                                                )
+                                                .on_hover_text("Differs from the first file");
+                                            }
+                                            Some(dtype) => {
+                                                ui.label(dtype.to_string());
+                                            }
+                                        }
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            });
+        self.schema_compare_open = open;
+    }
+
+    /// Plans reading the files with the current filter and sort, as Polars would run them
+    /// lazily. Other edits to the data happen in memory and are not part of the plan.
+    fn explain_view(&self) -> PolarsResult<(String, String)> {
+        let mut plan = loader::scan(&self.files_to_load)?.with_row_index(ROW_INDEX_COLUMN, None);
+        if !self.filter_expression.is_empty() {
+            plan = plan.filter(expression::parse(&self.filter_expression)?);
+        }
+        if let Some(col_idx) = self.selected_filter_column
+            && !self.filter_text.is_empty()
+        {
+            plan = plan.filter(filter::filter_expr(
+                &self.column_names[col_idx],
+                self.filter_type,
+                &self.filter_text,
+            ));
+        }
+        if let Some(col_idx) = self.sort_column {
+            plan = plan.sort(
+                [self.column_names[col_idx].as_str()],
+                SortMultipleOptions::new().with_order_descending(self.sort_descending),
+            );
+        }
+        Ok((plan.explain(false)?, plan.explain(true)?))
+    }
+
+    pub(super) fn render_diagnostics_panel(&mut self, ctx: &Context) {
+        let mut open = self.diagnostics_open;
+        let mut detached = self.diagnostics_detached;
+        panels::show_panel(
+            ctx,
+            "Diagnostics",
+            [560.0, 480.0],
+            &mut open,
+            &mut detached,
+            |ui| {
+                ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+                    egui::CollapsingHeader::new("Timings")
+                        .default_open(true)
+                        .show(ui, |ui| {
+                            if self.timings.is_empty() {
+                                ui.weak("Nothing timed yet");
+                            }
+                            egui::Grid::new("timings").striped(true).show(ui, |ui| {
+                                for (operation, elapsed) in self.timings.iter().rev() {
+                                    ui.label(*operation);
+                                    ui.label(format!("{:.1?}", elapsed));
+                                    ui.end_row();
+                                }
+                            });
+                        });
+                    egui::CollapsingHeader::new("Memory")
+                        .default_open(true)
+                        .show(ui, |ui| {
+                            egui::Grid::new("memory").show(ui, |ui| {
+                                if let Some(bytes) = diagnostics::resident_memory() {
+                                    ui.label("Process");
+                                    ui.label(format::format_bytes(bytes as f64));
+                                    ui.end_row();
+                                }
+                                for (label, df) in [
+                                    ("Loaded data", &self.original_dataframe),
+                                    ("Current view", &self.dataframe),
+                                ] {
+                                    if let Some(df) = df {
+                                        ui.label(label);
+                                        ui.label(format::format_bytes(df.estimated_size() as f64))
+                                            .on_hover_text(
+                                                "Estimated by Polars; views share buffers with the loaded data",
+                                            );
+                                        ui.end_row();
+                                    }
+                                }
+                            });
+                            if let Some(df) = &self.original_dataframe {
+                                let mut columns: Vec<(String, usize)> = df
+                                    .get_columns()
+                                    .iter()
+                                    .map(|column| {
+                                        (
+                                            column.name().to_string(),
+                                            column.as_materialized_series().estimated_size(),
+                                        )
+                                    })
+                                    .collect();
+                                columns.sort_by_key(|(_, bytes)| std::cmp::Reverse(*bytes));
+                                ui.label("Largest columns");
+                                egui::Grid::new("column_memory").striped(true).show(ui, |ui| {
+                                    for (name, bytes) in columns.iter().take(10) {
+                                        ui.label(name);
+                                        ui.label(format::format_bytes(*bytes as f64));
+                                        ui.end_row();
+                                    }
+                                });
+                            }
+                        });
+                    egui::CollapsingHeader::new("Query plan")
+                        .default_open(true)
+                        .show(ui, |ui| {
+                            if ui
+                                .add_enabled(
+                                    !self.files_to_load.is_empty(),
+                                    egui::Button::new("Explain current view"),
+                                )
+                                .on_hover_text(
+                                    "How Polars would read the files with the current filter and sort",
+                                )
+                                .clicked()
+                            {
+                                self.query_plan = Some(self.explain_view().map_err(|e| e.to_string()));
+                            }
+                            match &self.query_plan {
+                                Some(Ok((plan, optimized))) => {
+                                    for (label, text) in [("Plan", plan), ("Optimized", optimized)] {
+                                        ui.strong(label);
+                                        let mut text = text.as_str();
+                                        ui.add(
+                                            egui::TextEdit::multiline(&mut text)
+                                                .code_editor()
+                                                .desired_width(f32::INFINITY),
+                                        );
+                                    }
+                                }
+                                Some(Err(e)) => {
+                                    ui.colored_label(ui.visuals().error_fg_color, e);
+                                }
+                                None => {}
+                            }
+                        });
+                    egui::CollapsingHeader::new("Log").show(ui, |ui| {
+                        let lines = diagnostics::recent_log();
+                        if lines.is_empty() {
+                            ui.weak("No log lines yet");
+                        }
+                        for line in lines.iter().rev() {
+                            ui.monospace(line);
+                        }
+                    });
+                });
+            },
+        );
+        self.diagnostics_open = open;
+        self.diagnostics_detached = detached;
+    }
+
+    pub(super) fn render_pii_dialog(&mut self, ctx: &Context) {
+        let mut open = self.pii_dialog_open;
+        let mut detached = self.pii_detached;
+        panels::show_panel(
+            ctx,
+            "PII Scan",
+            [420.0, 300.0],
+            &mut open,
+            &mut detached,
+            |ui| {
+                let refresh = self.pii_state.show_controls(ui, self.data_version);
+                if (refresh || self.pii_state.needs_refresh(self.data_version))
+                    && let Some(df) = &self.dataframe
+                {
+                    self.pii_findings = pii::scan(df);
+                    self.pii_state.mark_computed(self.data_version);
+                }
+                ui.horizontal(|ui| {
+                    ui.add_enabled_ui(!self.pii_findings.is_empty(), |ui| {
+                        if ui.button("Mask all").clicked() {
+                            self.masked_columns
+                                .extend(self.pii_findings.iter().map(|f| f.column.clone()));
+                        }
+                    });
+                    ui.add_enabled_ui(!self.masked_columns.is_empty(), |ui| {
+                        if ui.button("Unmask all").clicked() {
+                            self.masked_columns.clear();
+                        }
+                    });
+                });
+
+                if self.pii_findings.is_empty() {
+                    ui.label("No columns flagged.");
+                    return;
+                }
+
+                ui.label(
+                    "These columns look like personal data. Consider masking them before sharing.",
+                );
+                egui::Grid::new("pii_findings")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong("Column");
+                        ui.strong("Looks like");
+                        ui.strong("Matches");
+                        ui.strong("Mask");
+                        ui.end_row();
+                        for finding in &self.pii_findings {
+                            ui.label(&finding.column);
+                            ui.label(finding.kind.to_string());
+                            ui.label(format!("{:.0}%", finding.match_ratio * 100.0));
+                            let mut masked = self.masked_columns.contains(&finding.column);
+                            if ui.checkbox(&mut masked, "").changed() {
+                                if masked {
+                                    self.masked_columns.insert(finding.column.clone());
+                                } else {
+                                    self.masked_columns.remove(&finding.column);
+                                }
+                            }
+                            ui.end_row();
+                        }
+                    });
+            },
+        );
+        self.pii_dialog_open = open;
+        self.pii_detached = detached;
+    }
+
+    pub(super) fn render_profile_dialog(&mut self, ctx: &Context) {
+        let mut open = self.profile_open;
+        let mut detached = self.profile_detached;
+        panels::show_panel(
+            ctx,
+            "Profile",
+            [900.0, 600.0],
+            &mut open,
+            &mut detached,
+            |ui| {
+                let refresh = self.profile_state.show_controls(ui, self.data_version);
+                if (refresh
+                    || !self.profile_state.has_result()
+                    || self.profile_state.needs_refresh(self.data_version))
+                    && let Some(df) = &self.dataframe
+                {
+                    self.profiles = profile::profile(df, ROW_INDEX_COLUMN);
+                    self.profile_state.mark_computed(self.data_version);
+                }
+                if ui.button("Export HTML…").clicked() {
+                    self.handle_export_profile();
+                }
+                ui.separator();
+                ScrollArea::both()
+                    .auto_shrink([false, false])
+                    .show(ui, |ui| profile::show(ui, &self.profiles));
+            },
+        );
+        self.profile_open = open;
+        self.profile_detached = detached;
+    }
+
+    fn handle_export_profile(&mut self) {
+        let Some(df) = &self.dataframe else {
+            return;
+        };
+        let Some(path) = FileDialog::new()
+            .add_filter("HTML files", &["html"])
+            .set_file_name("profile.html")
+            .save_file()
+        else {
+            return;
+        };
+        let title = format!("Profile of {}", self.dataset_title());
+        let html = profile::to_html(&title, df.height(), &self.profiles);
+        match std::fs::write(&path, html) {
+            Ok(()) => {
+                self.run_hook(HookEvent::Export, &[path]);
+            }
+            Err(e) => self.notifications.error(format!("Export error: {}", e)),
+        }
+    }
+
+    pub(super) fn render_describe_dialog(&mut self, ctx: &Context) {
+        let mut open = self.describe_open;
+        let mut detached = self.describe_detached;
+        panels::show_panel(
+            ctx,
+            "Describe",
+            [760.0, 420.0],
+            &mut open,
+            &mut detached,
+            |ui| {
+                let refresh = self.describe_state.show_controls(ui, self.data_version);
+                if (refresh
+                    || !self.describe_state.has_result()
+                    || self.describe_state.needs_refresh(self.data_version))
+                    && let Some(df) = &self.dataframe
+                {
+                    match describe::describe(df, ROW_INDEX_COLUMN) {
+                        Ok(summary) => {
+                            self.describe_pane
+                                .set_title(format!("{} rows", df.height()));
+                            self.describe_pane.set_data(summary);
+                        }
+                        Err(e) => self.notifications.error(format!("Describe error: {}", e)),
+                    }
+                    self.describe_state.mark_computed(self.data_version);
+                }
+                ui.label(format!(
+                    "Summary of the current view ({})",
+                    self.describe_pane.title()
+                ));
+                self.describe_pane.set_row_height(self.row_height);
+                self.describe_pane.show(ui);
+            },
+        );
+        self.describe_open = open;
+        self.describe_detached = detached;
+    }
+
+    pub(super) fn render_similarity_dialog(&mut self, ctx: &Context) {
+        let mut open = self.similarity_dialog_open;
+        let mut detached = self.similarity_detached;
+        panels::show_panel(
+            ctx,
+            "Duplicate columns",
+            [520.0, 320.0],
+            &mut open,
+            &mut detached,
+            |ui| {
+                let refresh = self.similarity_state.show_controls(ui, self.data_version);
+                if (refresh
+                    || !self.similarity_state.has_result()
+                    || self.similarity_state.needs_refresh(self.data_version))
+                    && let Some(df) = &self.dataframe
+                {
+                    match similarity::find_similar_columns(df, ROW_INDEX_COLUMN) {
+                        Ok(pairs) => self.similar_columns = pairs,
+                        Err(e) => self.notifications.error(format!("Analysis error: {}", e)),
+                    }
+                    self.similarity_state.mark_computed(self.data_version);
+                }
+
+                if self.similar_columns.is_empty() {
+                    ui.label("No duplicate columns found.");
+                    return;
+                }
+                ui.label("These columns repeat each other. Hide or drop the second of each pair.");
+                let mut hide = None;
+                let mut drop = None;
+                egui::Grid::new("similar_columns")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong("Column");
+                        ui.strong("Duplicate");
+                        ui.strong("Similarity");
+                        ui.end_row();
+                        for pair in &self.similar_columns {
+                            ui.label(&pair.left);
+                            ui.label(&pair.right);
+                            ui.label(pair.similarity.to_string());
+                            let present = self.column_names.contains(&pair.right);
+                            ui.add_enabled_ui(present, |ui| {
+                                let hidden = self.hidden_columns.contains(&pair.right);
+                                if ui.add_enabled(!hidden, egui::Button::new("Hide")).clicked() {
+                                    hide = Some(pair.right.clone());
+                                }
+                                if ui.button("Drop").clicked() {
+                                    drop = Some(pair.right.clone());
+                                }
+                            });
+                            ui.end_row();
+                        }
+                    });
+                if let Some(name) = hide {
+                    self.hidden_columns.insert(name);
+                }
+                if let Some(name) = drop {
+                    self.drop_column(&name);
+                }
+            },
+        );
+        self.similarity_dialog_open = open;
+        self.similarity_detached = detached;
+    }
+
+    /// Saves the working data's schema as JSON, or as SQL DDL in the chosen dialect.
+    pub(super) fn render_metadata_panel(&mut self, ctx: &Context) {
+        if self.metadata_open && self.file_metadata_for != self.files_to_load {
+            match metadata::read_metadata(&self.files_to_load) {
+                Ok(files) => {
+                    self.file_metadata = files;
+                    self.file_metadata_error = None;
+                }
+                Err(e) => {
+                    self.file_metadata.clear();
+                    self.file_metadata_error = Some(e.to_string());
+                }
+            }
+            self.file_metadata_for = self.files_to_load.clone();
+            self.row_group_selection.clear();
+            self.row_group_stats = None;
+        }
+        let mut open = self.metadata_open;
+        let mut detached = self.metadata_detached;
+        let mut load = None;
+        panels::show_panel(
+            ctx,
+            "Parquet metadata",
+            [640.0, 420.0],
+            &mut open,
+            &mut detached,
+            |ui| {
+                if let Some(error) = &self.file_metadata_error {
+                    ui.colored_label(Color32::RED, error);
+                    return;
+                }
+                for (path, file) in self.file_metadata_for.iter().zip(&self.file_metadata) {
+                    ui.label(format!(
+                        "{}: {} rows in {} row groups{}",
+                        file_label(path),
+                        file.num_rows,
+                        file.row_groups.len(),
+                        file.created_by
+                            .as_ref()
+                            .map(|by| format!(", written by {}", by))
+                            .unwrap_or_default()
+                    ));
+                }
+                let groups = metadata::row_groups(&self.file_metadata);
+                ui.horizontal(|ui| {
+                    let selected = self.row_group_selection.len();
+                    let rows: usize = self
+                        .row_group_selection
+                        .iter()
+                        .map(|&i| groups[i].rows)
+                        .sum();
+                    if ui
+                        .add_enabled(selected > 0, egui::Button::new("Load selected"))
+                        .on_hover_text("Read only the chosen row groups into the view")
+                        .clicked()
+                    {
+                        load = Some(RowLimit::RowGroups(
+                            self.row_group_selection
+                                .iter()
+                                .map(|&i| (groups[i].first_row, groups[i].rows))
+                                .collect(),
+                        ));
+                    }
+                    if ui.button("Load all").clicked() {
+                        load = Some(RowLimit::All);
+                    }
+                    if ui.button("Clear selection").clicked() {
+                        self.row_group_selection.clear();
+                    }
+                    ui.label(format!("{} selected, {} rows", selected, rows));
+                });
+                ui.separator();
+                ScrollArea::both()
+                    .id_salt("row_groups")
+                    .max_height(220.0)
+                    .auto_shrink([false, true])
+                    .show(ui, |ui| {
+                        egui::Grid::new("row_groups").striped(true).show(ui, |ui| {
+                            ui.label("");
+                            ui.strong("File");
+                            ui.strong("Row group");
+                            ui.strong("Rows");
+                            ui.strong("First row");
+                            ui.strong("Compressed");
+                            ui.strong("Uncompressed");
+                            ui.label("");
+                            ui.end_row();
+                            for (i, group) in groups.iter().enumerate() {
+                                let mut selected = self.row_group_selection.contains(&i);
+                                if ui.checkbox(&mut selected, "").changed() {
+                                    if selected {
+                                        self.row_group_selection.insert(i);
+                                    } else {
+                                        self.row_group_selection.remove(&i);
+                                    }
+                                }
+                                ui.label(
+                                    self.file_metadata_for[group.file]
+                                        .file_name()
+                                        .unwrap_or_default()
+                                        .to_string_lossy(),
+                                );
+                                ui.label(group.index.to_string());
+                                ui.label(group.rows.to_string());
+                                ui.label(group.first_row.to_string());
+                                ui.label(format::format_bytes(group.compressed_bytes as f64));
+                                ui.label(format::format_bytes(group.uncompressed_bytes as f64));
+                                let showing = self.row_group_stats == Some(i);
+                                if ui
+                                    .selectable_label(showing, "Statistics")
+                                    .on_hover_text("Min, max and null count per column chunk")
+                                    .clicked()
+                                {
+                                    self.row_group_stats = (!showing).then_some(i);
+                                }
+                                ui.end_row();
+                            }
+                        });
+                    });
+                let Some(group) = self.row_group_stats.and_then(|i| groups.get(i)) else {
+                    return;
+                };
+                ui.separator();
+                let stats =
+                    metadata::chunk_statistics(&self.file_metadata[group.file], group.index);
+                let stats = match stats {
+                    Ok(stats) => stats,
+                    Err(e) => {
+                        ui.colored_label(Color32::RED, e.to_string());
+                        return;
+                    }
+                };
+                let bounded = stats.iter().filter(|chunk| chunk.has_bounds()).count();
+                ui.label(format!(
+                    "Row group {} of {}: {} of {} columns have min/max statistics",
+                    group.index,
+                    self.file_metadata_for[group.file]
+                        .file_name()
+                        .unwrap_or_default()
+                        .to_string_lossy(),
+                    bounded,
+                    stats.len()
+                ));
+                ScrollArea::both()
+                    .id_salt("chunk_stats")
+                    .auto_shrink([false, false])
+                    .show(ui, |ui| {
+                        egui::Grid::new("chunk_stats").striped(true).show(ui, |ui| {
+                            ui.strong("Column");
+                            ui.strong("Min");
+                            ui.strong("Max");
+                            ui.strong("Nulls");
+                            ui.end_row();
+                            let missing = |ui: &mut Ui| {
+                                ui.colored_label(Color32::GRAY, "none");
+                            };
+                            for chunk in &stats {
+                                ui.label(&chunk.column);
+                                for bound in [&chunk.min, &chunk.max] {
+                                    match bound {
+                                        Some(value) => {
+                                            ui.add(Label::new(value).truncate());
+                                        }
+                                        None => missing(ui),
+                                    }
+                                }
+                                match chunk.null_count {
+                                    Some(nulls) => {
+                                        ui.label(nulls.to_string());
+                                    }
+                                    None => missing(ui),
+                                }
+                                ui.end_row();
+                            }
+                        });
+                    });
+            },
+        );
+        self.metadata_open = open;
+        self.metadata_detached = detached;
+        if let Some(limit) = load
+            && limit != self.row_limit
+        {
+            self.after_unsaved_edits(ctx, UnsavedAction::SetRowLimit(limit));
+        }
+    }
+
+    fn handle_export_schema(&mut self, sql: bool) {
+        let Some(df) = &self.original_dataframe else {
+            return;
+        };
+        let table = self
+            .files_to_load
+            .first()
+            .and_then(|path| path.file_stem())
+            .map_or("data".to_string(), |stem| {
+                stem.to_string_lossy().to_string()
+            });
+        let (extension, contents) = if sql {
+            let ddl = schema_export::to_sql(df, &table, self.schema_dialect, ROW_INDEX_COLUMN);
+            ("sql", ddl)
+        } else {
+            let json = schema_export::to_json(df, &self.dictionary, ROW_INDEX_COLUMN);
+            ("json", json)
+        };
+        let Some(path) = FileDialog::new()
+            .add_filter(format!("{} files", extension.to_uppercase()), &[extension])
+            .set_file_name(format!("{}.schema.{}", table, extension))
+            .save_file()
+        else {
+            return;
+        };
+        match std::fs::write(&path, contents) {
+            Ok(()) => self.run_hook(HookEvent::Export, &[path]),
+            Err(e) => self
+                .notifications
+                .error(format!("Schema export error: {}", e)),
+        }
+    }
+
+    pub(super) fn render_schema_dialog(&mut self, ctx: &Context) {
+        let mut open = self.schema_dialog_open;
+        let mut detached = self.schema_detached;
+        panels::show_panel(
+            ctx,
+            "Schema",
+            [600.0, 400.0],
+            &mut open,
+            &mut detached,
+            |ui| {
+                let Some(df) = &self.original_dataframe else {
+                    return;
+                };
+                let schema = df.schema().clone();
+                ui.horizontal(|ui| {
+                    if ui.button("Load dictionary…").clicked()
+                        && let Some(path) = FileDialog::new()
+                            .add_filter("Data dictionary", &["yaml", "yml", "json"])
+                            .pick_file()
+                    {
+                        self.load_dictionary(&path);
+                    }
+                    if ui.button("Save dictionary…").clicked() {
+                        let mut dialog = FileDialog::new()
+                            .add_filter("Data dictionary", &["yaml", "yml", "json"]);
+                        if let Some(path) = &self.dictionary_path {
+                            if let Some(dir) = path.parent() {
+                                dialog = dialog.set_directory(dir);
+                            }
+                            if let Some(name) = path.file_name() {
+                                dialog = dialog.set_file_name(name.to_string_lossy());
+                            }
+                        } else if let Some(file) = self.files_to_load.first() {
+                            let stem = file.file_stem().unwrap_or_default().to_string_lossy();
+                            dialog = dialog.set_file_name(format!("{}.dictionary.yaml", stem));
+                        }
+                        if let Some(path) = dialog.save_file() {
+                            match self.dictionary.save(&path) {
+                                Ok(()) => self.dictionary_path = Some(path),
+                                Err(e) => self
+                                    .notifications
+                                    .error(format!("Could not save data dictionary: {}", e)),
+                            }
+                        }
+                    }
+                    if let Some(path) = &self.dictionary_path {
+                        ui.weak(path.display().to_string());
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Export schema:");
+                    if ui.button("JSON…").clicked() {
+                        self.handle_export_schema(false);
+                    }
+                    if ui.button("SQL…").clicked() {
+                        self.handle_export_schema(true);
+                    }
+                    ComboBox::from_id_salt("schema_dialect")
+                        .selected_text(self.schema_dialect.to_string())
+                        .show_ui(ui, |ui| {
+                            for dialect in SqlDialect::ALL {
+                                ui.selectable_value(
+                                    &mut self.schema_dialect,
+                                    dialect,
+                                    dialect.to_string(),
+                                );
+                            }
+                        });
+                });
+                ui.separator();
+                ScrollArea::both()
+                    .auto_shrink([false, false])
+                    .show(ui, |ui| {
+                        egui::Grid::new("schema").striped(true).show(ui, |ui| {
+                            ui.strong("Column");
+                            ui.strong("Type");
+                            ui.strong("Description");
+                            ui.strong("Unit");
+                            ui.end_row();
+                            for (name, dtype) in schema.iter() {
+                                ui.label(name.as_str());
+                                ui.label(dtype.to_string());
+                                let doc =
+                                    self.dictionary.columns.entry(name.to_string()).or_default();
+                                ui.text_edit_singleline(&mut doc.description);
+                                ui.add(
+                                    egui::TextEdit::singleline(&mut doc.unit).desired_width(60.0),
+                                );
+                                ui.end_row();
+                            }
+                        });
+                    });
+            },
+        );
+        self.schema_dialog_open = open;
+        self.schema_detached = detached;
+    }
+}
@@ -0,0 +1,309 @@
+//! The Settings and Display dialogs, and saving the settings file.
+
+use super::*;
+
+impl Tablr {
+    pub(super) fn render_settings_dialog(&mut self, ctx: &Context) {
+        let mut open = self.settings_dialog_open;
+        let mut changed = false;
+        Window::new("Settings")
+            .auto_sized()
+            .collapsible(false)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                egui::Grid::new("settings").show(ui, |ui| {
+                    ui.label("Theme");
+                    ui.horizontal(|ui| {
+                        for theme in Theme::ALL {
+                            changed |= ui
+                                .selectable_value(
+                                    &mut self.settings.theme,
+                                    theme,
+                                    theme.to_string(),
+                                )
+                                .clicked();
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label("Font size");
+                    changed |= ui
+                        .add(egui::Slider::new(&mut self.settings.font_size, 10.0..=32.0))
+                        .changed();
+                    ui.end_row();
+
+                    ui.label("UI scale");
+                    ui.horizontal(|ui| {
+                        let response = ui.add(
+                            egui::Slider::new(&mut self.settings.ui_scale, 0.5..=3.0)
+                                .fixed_decimals(1)
+                                .suffix("×"),
+                        );
+                        if response.changed() {
+                            ctx.set_zoom_factor(self.settings.ui_scale);
+                            changed = true;
+                        }
+                        if ui.button("Reset").clicked() {
+                            self.settings.ui_scale = 1.0;
+                            ctx.set_zoom_factor(1.0);
+                            changed = true;
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label("Links");
+                    changed |= ui
+                        .checkbox(
+                            &mut self.settings.clickable_links,
+                            "Open URLs in the browser",
+                        )
+                        .changed();
+                    ui.end_row();
+
+                    ui.label("Default row height");
+                    if ui
+                        .add(
+                            egui::Slider::new(&mut self.settings.row_height, 14.0..=80.0)
+                                .suffix(" px"),
+                        )
+                        .changed()
+                    {
+                        self.row_height = self.settings.row_height;
+                        changed = true;
+                    }
+                    ui.end_row();
+
+                    let now = chrono::Utc::now().timestamp_micros();
+                    let samples = [
+                        AnyValue::Date((now / 86_400_000_000) as i32),
+                        AnyValue::Datetime(now, TimeUnit::Microseconds, None),
+                    ];
+                    for (label, sample) in [
+                        ("Date format", &samples[0]),
+                        ("Datetime format", &samples[1]),
+                    ] {
+                        ui.label(label);
+                        ui.horizontal(|ui| {
+                            let pattern = match sample {
+                                AnyValue::Date(_) => &mut self.settings.date_format,
+                                _ => &mut self.settings.datetime_format,
+                            };
+                            changed |= ui
+                                .add(
+                                    egui::TextEdit::singleline(pattern)
+                                        .hint_text("strftime pattern, empty for the default")
+                                        .desired_width(180.0),
+                                )
+                                .changed();
+                            // The sample picks which of the two patterns applies.
+                            let preview = if pattern.is_empty() {
+                                Some(sample.to_string())
+                            } else {
+                                format::format_temporal(sample, pattern, pattern)
+                            };
+                            match preview {
+                                Some(text) => ui.weak(text),
+                                None => {
+                                    ui.colored_label(ui.visuals().error_fg_color, "Invalid pattern")
+                                }
+                            };
+                        });
+                        ui.end_row();
+                    }
+
+                    ui.label("Default export format");
+                    ComboBox::from_id_salt("default_export_format")
+                        .selected_text(self.settings.export_format.to_string())
+                        .show_ui(ui, |ui| {
+                            for format in FileFormat::ALL {
+                                changed |= ui
+                                    .selectable_value(
+                                        &mut self.settings.export_format,
+                                        format,
+                                        format.to_string(),
+                                    )
+                                    .clicked();
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label("Keyboard shortcuts");
+                    egui::Grid::new("keybindings").show(ui, |ui| {
+                        for action in Action::ALL {
+                            ui.label(action.to_string());
+                            let binding = self.settings.keybindings.get_mut(action);
+                            changed |= ui
+                                .add(
+                                    egui::TextEdit::singleline(binding)
+                                        .hint_text("Unbound")
+                                        .desired_width(100.0),
+                                )
+                                .changed();
+                            if !binding.is_empty() && keymap::parse_shortcut(binding).is_none() {
+                                ui.colored_label(ui.visuals().error_fg_color, "Not a shortcut");
+                            }
+                            ui.end_row();
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label("Single instance");
+                    if ui
+                        .checkbox(
+                            &mut self.settings.single_instance,
+                            "Open files in the running window",
+                        )
+                        .changed()
+                    {
+                        if self.settings.single_instance {
+                            self.listen_for_instances(ctx);
+                        }
+                        changed = true;
+                    }
+                    ui.end_row();
+
+                    ui.label("Fiscal year starts in");
+                    let month = &mut self.settings.fiscal_year_start_month;
+                    ComboBox::from_id_salt("fiscal_year_start_month")
+                        .selected_text(MONTH_NAMES[(*month as usize).clamp(1, 12) - 1])
+                        .show_ui(ui, |ui| {
+                            for (i, name) in MONTH_NAMES.iter().enumerate() {
+                                changed |=
+                                    ui.selectable_value(month, i as u32 + 1, *name).clicked();
+                            }
+                        });
+                    ui.end_row();
+
+                    let hooks = &mut self.settings.hooks;
+                    for (label, command) in [
+                        ("After load", &mut hooks.after_load),
+                        ("After export", &mut hooks.after_export),
+                    ] {
+                        ui.label(label);
+                        changed |= ui
+                            .add(
+                                egui::TextEdit::singleline(command)
+                                    .hint_text("Command, gets file paths as arguments")
+                                    .desired_width(280.0),
+                            )
+                            .lost_focus();
+                        ui.end_row();
+                    }
+                });
+                ui.weak("Ctrl + and Ctrl − also zoom; Ctrl 0 resets.");
+            });
+        self.settings_dialog_open = open;
+
+        if changed {
+            ctx.set_theme(self.settings.theme.preference());
+            self.save_settings();
+        }
+    }
+
+    /// Reports a settings file that could not be loaded, and keeps it from being
+    /// overwritten with the defaults in use instead.
+    pub(super) fn settings_load_failed(&mut self, error: String) {
+        tracing::warn!("{}", error);
+        self.notifications
+            .error(format!("{}. Using the default settings.", error));
+        self.settings_file_invalid = true;
+    }
+
+    pub(super) fn save_settings(&mut self) {
+        // Safe mode starts from default settings; changes last until the window closes
+        // and leave the settings file alone.
+        if self.safe_mode {
+            return;
+        }
+        if self.settings_file_invalid {
+            self.notifications.warning(
+                "Settings are not saved while the settings file has errors; fix or delete it and restart.",
+            );
+            return;
+        }
+        if let Err(e) = self.settings.save() {
+            self.notifications
+                .error(format!("Could not save settings: {}", e));
+        }
+    }
+
+    pub(super) fn render_display_dialog(&mut self, ctx: &Context) {
+        let mut open = self.display_dialog_open;
+        Window::new("Display")
+            .auto_sized()
+            .collapsible(false)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                egui::Grid::new("display_settings").show(ui, |ui| {
+                    ui.label("Density");
+                    ui.horizontal(|ui| {
+                        for density in [Density::Compact, Density::Comfortable] {
+                            if ui
+                                .selectable_value(&mut self.density, density, density.to_string())
+                                .clicked()
+                            {
+                                self.row_height = density.row_height();
+                            }
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label("Row height");
+                    ui.add(egui::Slider::new(&mut self.row_height, 14.0..=80.0).suffix(" px"));
+                    ui.end_row();
+
+                    ui.label("Totals row");
+                    ComboBox::from_id_salt("footer_aggregation")
+                        .selected_text(
+                            self.footer_aggregation
+                                .map(|a| a.to_string())
+                                .unwrap_or_else(|| "Off".to_string()),
+                        )
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut self.footer_aggregation, None, "Off");
+                            for aggregation in Aggregation::ALL {
+                                ui.selectable_value(
+                                    &mut self.footer_aggregation,
+                                    Some(aggregation),
+                                    aggregation.to_string(),
+                                );
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label("Minimap");
+                    ui.checkbox(&mut self.show_minimap, "Show markers beside the scrollbar");
+                    ui.end_row();
+
+                    ui.label("Null bars");
+                    ui.checkbox(
+                        &mut self.show_null_bars,
+                        "Show the share of nulls under headers",
+                    );
+                    ui.end_row();
+
+                    ui.label("Row numbers");
+                    ui.vertical(|ui| {
+                        let mut show_row_index = !self.hidden_columns.contains(ROW_INDEX_COLUMN);
+                        if ui
+                            .checkbox(&mut show_row_index, "Row Index (position in file)")
+                            .changed()
+                        {
+                            if show_row_index {
+                                self.hidden_columns.remove(ROW_INDEX_COLUMN);
+                            } else {
+                                self.hidden_columns.insert(ROW_INDEX_COLUMN.to_string());
+                            }
+                        }
+                        ui.checkbox(
+                            &mut self.show_view_position,
+                            "# (position in filtered/sorted view)",
+                        );
+                    });
+                    ui.end_row();
+                });
+                ui.label("Right-click a column header to wrap or truncate its text.");
+            });
+        self.display_dialog_open = open;
+    }
+}
@@ -0,0 +1,1021 @@
+//! Dialogs that derive or reshape the data: group by and pivot with drill-down, column
+//! splits, find and replace, unpivot, joins, appends and computed columns.
+
+use super::*;
+
+impl Tablr {
+    fn run_group_by(&mut self) {
+        let Some(df) = &self.dataframe else {
+            return;
+        };
+        let started = Instant::now();
+        match group_by::group_by(df, &self.group_keys, &self.group_aggregations) {
+            Ok(result) => {
+                self.group_result_keys = self.group_keys.clone();
+                self.group_pane
+                    .set_title(format!("Grouped by {}", self.group_keys.join(", ")));
+                self.group_pane.set_data(result);
+                self.record_timing("Group by", started.elapsed());
+            }
+            Err(e) => self.notifications.error(format!("Group by error: {}", e)),
+        }
+    }
+
+    /// Narrows the table to the rows of the group selected in the group-by result.
+    fn drill_into_group(&mut self) {
+        let (Some(df), Some(values)) = (
+            &self.dataframe,
+            self.group_pane.selected_values(&self.group_result_keys),
+        ) else {
+            return;
+        };
+        let predicate = group_by::group_filter(&self.group_result_keys, &values);
+        match df.clone().lazy().filter(predicate).collect() {
+            Ok(rows) => {
+                let description = self
+                    .group_result_keys
+                    .iter()
+                    .zip(&values)
+                    .map(|(key, value)| format!("{} = {}", key, value.str_value()))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                self.narrow_view(rows, format!("Rows of group {}", description));
+            }
+            Err(e) => self.notifications.error(format!("Group by error: {}", e)),
+        }
+    }
+
+    fn run_pivot(&mut self) {
+        let (Some(df), Some(on), Some(values)) =
+            (&self.dataframe, &self.pivot_on, &self.pivot_values)
+        else {
+            return;
+        };
+        let started = Instant::now();
+        match pivot::pivot(df, &self.pivot_index, on, values, self.pivot_aggregation) {
+            Ok(result) => {
+                self.pivot_result_index = self.pivot_index.clone();
+                self.pivot_pane.set_title(format!(
+                    "{} of {} by {} and {}",
+                    self.pivot_aggregation,
+                    values,
+                    self.pivot_index.join(", "),
+                    on
+                ));
+                self.pivot_pane.set_data(result);
+                self.record_timing("Pivot", started.elapsed());
+            }
+            Err(e) => {
+                self.pivot_pane.clear();
+                self.notifications.error(format!("Pivot error: {}", e));
+            }
+        }
+        self.pivot_state.mark_computed(self.data_version);
+    }
+
+    /// Narrows the table to the rows behind the row selected in the pivot result.
+    fn drill_into_pivot_row(&mut self) {
+        let (Some(df), Some(values)) = (
+            &self.dataframe,
+            self.pivot_pane.selected_values(&self.pivot_result_index),
+        ) else {
+            return;
+        };
+        let predicate = group_by::group_filter(&self.pivot_result_index, &values);
+        match df.clone().lazy().filter(predicate).collect() {
+            Ok(rows) => {
+                let description = self
+                    .pivot_result_index
+                    .iter()
+                    .zip(&values)
+                    .map(|(key, value)| format!("{} = {}", key, value.str_value()))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                self.narrow_view(rows, format!("Rows of {}", description));
+            }
+            Err(e) => self.notifications.error(format!("Pivot error: {}", e)),
+        }
+    }
+
+    /// Shows a subset of the current view until the user goes back to all rows.
+    pub(super) fn narrow_view(&mut self, rows: DataFrame, description: String) {
+        let Some(df) = &self.dataframe else {
+            return;
+        };
+        let previous = self.drill_down.take().map_or(df.clone(), |(_, prev)| prev);
+        self.drill_down = Some((description, previous));
+        self.set_view(rows);
+        self.tab = Tab::Data;
+    }
+
+    pub(super) fn render_drill_down_bar(&mut self, ui: &mut Ui) {
+        let Some(description) = self.drill_down.as_ref().map(|(d, _)| d.clone()) else {
+            return;
+        };
+        ui.horizontal(|ui| {
+            ui.label(description);
+            if ui.button("Back to all rows").clicked()
+                && let Some((_, previous)) = self.drill_down.take()
+            {
+                self.set_view(previous);
+            }
+        });
+    }
+
+    pub(super) fn render_pivot_dialog(&mut self, ctx: &Context) {
+        let mut open = self.pivot_open;
+        let mut detached = self.pivot_detached;
+        let mut run = false;
+        let mut drill = false;
+        panels::show_panel(
+            ctx,
+            "Pivot",
+            [720.0, 520.0],
+            &mut open,
+            &mut detached,
+            |ui| {
+                let Some(df) = &self.dataframe else {
+                    return;
+                };
+                let columns: Vec<String> = self
+                    .column_names
+                    .iter()
+                    .filter(|name| *name != ROW_INDEX_COLUMN)
+                    .cloned()
+                    .collect();
+                ui.label("Rows");
+                ui.horizontal_wrapped(|ui| {
+                    for name in &columns {
+                        let mut selected = self.pivot_index.contains(name);
+                        if ui.checkbox(&mut selected, name).changed() {
+                            if selected {
+                                self.pivot_index.push(name.clone());
+                            } else {
+                                self.pivot_index.retain(|key| key != name);
+                            }
+                        }
+                    }
+                });
+                egui::Grid::new("pivot_options").show(ui, |ui| {
+                    ui.label("Columns from");
+                    ComboBox::from_id_salt("pivot_on")
+                        .selected_text(self.pivot_on.as_deref().unwrap_or("Select column"))
+                        .show_ui(ui, |ui| {
+                            for name in &columns {
+                                ui.selectable_value(&mut self.pivot_on, Some(name.clone()), name);
+                            }
+                        });
+                    ui.end_row();
+                    ui.label("Values");
+                    ui.horizontal(|ui| {
+                        ComboBox::from_id_salt("pivot_values")
+                            .selected_text(self.pivot_values.as_deref().unwrap_or("Select column"))
+                            .show_ui(ui, |ui| {
+                                for name in &columns {
+                                    ui.selectable_value(
+                                        &mut self.pivot_values,
+                                        Some(name.clone()),
+                                        name,
+                                    );
+                                }
+                            });
+                        let dtype = self
+                            .pivot_values
+                            .as_ref()
+                            .and_then(|name| df.column(name).ok())
+                            .map(|column| column.dtype().clone());
+                        ComboBox::from_id_salt("pivot_aggregation")
+                            .selected_text(self.pivot_aggregation.to_string())
+                            .show_ui(ui, |ui| {
+                                for option in Aggregation::ALL {
+                                    if dtype.as_ref().is_none_or(|d| option.applies_to(d)) {
+                                        ui.selectable_value(
+                                            &mut self.pivot_aggregation,
+                                            option,
+                                            option.to_string(),
+                                        );
+                                    }
+                                }
+                            });
+                    });
+                    ui.end_row();
+                });
+                let ready = !self.pivot_index.is_empty()
+                    && self.pivot_on.is_some()
+                    && self.pivot_values.is_some();
+                ui.horizontal(|ui| {
+                    ui.add_enabled_ui(ready, |ui| {
+                        run = ui.button("Pivot").clicked();
+                    });
+                });
+                if !self.pivot_pane.has_data() {
+                    return;
+                }
+                run |= self.pivot_state.show_controls(ui, self.data_version)
+                    || self.pivot_state.needs_refresh(self.data_version);
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.strong(self.pivot_pane.title());
+                    let selected = self
+                        .pivot_pane
+                        .selected_values(&self.pivot_result_index)
+                        .is_some();
+                    drill = ui
+                        .add_enabled(selected, egui::Button::new("Show rows of selected row"))
+                        .clicked();
+                });
+                ui.weak("Filter the result below, or click a header to sort by that column.");
+                self.pivot_pane.set_row_height(self.row_height);
+                self.pivot_pane.show(ui);
+            },
+        );
+        self.pivot_open = open;
+        self.pivot_detached = detached;
+        if run {
+            self.run_pivot();
+        }
+        if drill {
+            self.drill_into_pivot_row();
+        }
+    }
+
+    pub(super) fn render_group_dialog(&mut self, ctx: &Context) {
+        let mut open = self.group_dialog_open;
+        let mut detached = self.group_detached;
+        panels::show_panel(
+            ctx,
+            "Group by",
+            [720.0, 520.0],
+            &mut open,
+            &mut detached,
+            |ui| {
+                let Some(df) = self.dataframe.clone() else {
+                    return;
+                };
+                let columns: Vec<String> = self
+                    .column_names
+                    .iter()
+                    .filter(|name| *name != ROW_INDEX_COLUMN)
+                    .cloned()
+                    .collect();
+                ui.label("Group columns");
+                ui.horizontal_wrapped(|ui| {
+                    for name in &columns {
+                        let mut selected = self.group_keys.contains(name);
+                        if ui.checkbox(&mut selected, name).changed() {
+                            if selected {
+                                self.group_keys.push(name.clone());
+                            } else {
+                                self.group_keys.retain(|key| key != name);
+                            }
+                        }
+                    }
+                });
+
+                ui.label("Aggregations");
+                let mut remove = None;
+                for (i, (column, aggregation)) in self.group_aggregations.iter_mut().enumerate() {
+                    ui.horizontal(|ui| {
+                        ComboBox::from_id_salt(("group_agg_column", i))
+                            .selected_text(column.as_str())
+                            .show_ui(ui, |ui| {
+                                for name in &columns {
+                                    ui.selectable_value(column, name.clone(), name);
+                                }
+                            });
+                        let dtype = df.column(column).map(|c| c.dtype().clone());
+                        ComboBox::from_id_salt(("group_agg_kind", i))
+                            .selected_text(aggregation.to_string())
+                            .show_ui(ui, |ui| {
+                                for option in Aggregation::ALL {
+                                    if dtype.as_ref().is_ok_and(|d| option.applies_to(d)) {
+                                        ui.selectable_value(
+                                            aggregation,
+                                            option,
+                                            option.to_string(),
+                                        );
+                                    }
+                                }
+                            });
+                        if ui.small_button("✖").clicked() {
+                            remove = Some(i);
+                        }
+                    });
+                }
+                if let Some(i) = remove {
+                    self.group_aggregations.remove(i);
+                }
+                ui.horizontal(|ui| {
+                    if ui.button("+ Add aggregation").clicked()
+                        && let Some(first) = columns.first()
+                    {
+                        self.group_aggregations
+                            .push((first.clone(), Aggregation::Count));
+                    }
+                    ui.add_enabled_ui(!self.group_keys.is_empty(), |ui| {
+                        if ui.button("Group").clicked() {
+                            self.run_group_by();
+                        }
+                    });
+                });
+
+                if !self.group_pane.has_data() {
+                    return;
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.strong(self.group_pane.title());
+                    let selected = self
+                        .group_pane
+                        .selected_values(&self.group_result_keys)
+                        .is_some();
+                    if ui
+                        .add_enabled(selected, egui::Button::new("Show rows of selected group"))
+                        .clicked()
+                    {
+                        self.drill_into_group();
+                    }
+                });
+                self.group_pane.set_row_height(self.row_height);
+                self.group_pane.show(ui);
+            },
+        );
+        self.group_dialog_open = open;
+        self.group_detached = detached;
+    }
+
+    pub(super) fn drop_column(&mut self, col_name: &str) {
+        let Some(df) = &self.original_dataframe else {
+            return;
+        };
+        match df.drop(col_name) {
+            Ok(df) => {
+                self.computed_columns.retain(|(name, _)| name != col_name);
+                self.replace_data(df, format!("Drop column {}", col_name));
+            }
+            Err(e) => self.notifications.error(format!("Drop error: {}", e)),
+        }
+    }
+
+    fn split_selected_column(&mut self) {
+        let (Some(df), Some(col_name)) = (&self.original_dataframe, self.split_column.clone())
+        else {
+            return;
+        };
+        match split::split_column(
+            df,
+            &col_name,
+            &self.split_delimiter,
+            self.split_regex,
+            self.split_parts,
+        ) {
+            Ok(df) => {
+                self.replace_data(
+                    df,
+                    format!(
+                        "Split {} on {:?} into {} columns",
+                        col_name, self.split_delimiter, self.split_parts
+                    ),
+                );
+                // Place the parts next to their source rather than at the end.
+                let parts = split::part_names(&col_name, self.split_parts);
+                self.column_order.retain(|name| !parts.contains(name));
+                if let Some(source) = self.column_order.iter().position(|n| *n == col_name) {
+                    for (offset, part) in parts.into_iter().enumerate() {
+                        self.column_order.insert(source + 1 + offset, part);
+                    }
+                }
+                self.split_column_open = false;
+            }
+            Err(e) => self.notifications.error(format!("Split error: {}", e)),
+        }
+    }
+
+    pub(super) fn render_split_dialog(&mut self, ctx: &Context) {
+        let mut open = self.split_column_open;
+        Window::new("Split column")
+            .auto_sized()
+            .collapsible(false)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                let string_columns: Vec<String> = self
+                    .original_dataframe
+                    .as_ref()
+                    .map(|df| {
+                        df.get_columns()
+                            .iter()
+                            .filter(|column| column.dtype() == &DataType::String)
+                            .map(|column| column.name().to_string())
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                egui::Grid::new("split_options").show(ui, |ui| {
+                    ui.label("Column:");
+                    ComboBox::from_id_salt("split_column")
+                        .selected_text(self.split_column.as_deref().unwrap_or("Select column"))
+                        .show_ui(ui, |ui| {
+                            for name in string_columns {
+                                let label = name.clone();
+                                ui.selectable_value(&mut self.split_column, Some(name), label);
+                            }
+                        });
+                    ui.end_row();
+                    ui.label("Delimiter:");
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.split_delimiter)
+                                .desired_width(80.0),
+                        );
+                        ui.checkbox(&mut self.split_regex, "Regex");
+                    });
+                    ui.end_row();
+                    ui.label("Columns:");
+                    ui.add(egui::DragValue::new(&mut self.split_parts).range(2..=50));
+                    ui.end_row();
+                });
+                if let Some(col_name) = &self.split_column {
+                    ui.weak(format!(
+                        "Creates {}; the last keeps the rest of the value.",
+                        split::part_names(col_name, self.split_parts).join(", ")
+                    ));
+                }
+                if ui
+                    .add_enabled(self.split_column.is_some(), egui::Button::new("Split"))
+                    .clicked()
+                {
+                    self.split_selected_column();
+                }
+            });
+        self.split_column_open = self.split_column_open && open;
+    }
+
+    pub(super) fn handle_append_files(&mut self) {
+        let Some(paths) = FileDialog::new()
+            .add_filter("Parquet files", &["parquet"])
+            .pick_files()
+        else {
+            return;
+        };
+        let current_source = self.dataset_title();
+        for path in paths {
+            let Some(current) = &self.original_dataframe else {
+                return;
+            };
+            let name = file_label(&path);
+            let result = loader::read_parquet(std::slice::from_ref(&path)).and_then(|other| {
+                append::append(current, &current_source, &other, &name, ROW_INDEX_COLUMN)
+            });
+            match result {
+                Ok(df) => self.replace_data(df, format!("Append {}", name)),
+                Err(e) => {
+                    self.notifications.error(format!("Append error: {}", e));
+                    return;
+                }
+            }
+        }
+    }
+
+    fn preview_replace(&mut self) {
+        let (Some(df), Some(col_name)) = (&self.original_dataframe, &self.replace_column) else {
+            return;
+        };
+        let result =
+            replace::Replace::new(&self.replace_find, &self.replace_with, self.replace_regex)
+                .and_then(|replace| replace.preview(df, col_name, ROW_INDEX_COLUMN));
+        match result {
+            Ok(preview) => self.replace_preview = Some(preview),
+            Err(e) => self.notifications.error(format!("Replace error: {}", e)),
+        }
+    }
+
+    fn apply_replace(&mut self) {
+        let (Some(df), Some(col_name)) = (&self.original_dataframe, self.replace_column.clone())
+        else {
+            return;
+        };
+        let result =
+            replace::Replace::new(&self.replace_find, &self.replace_with, self.replace_regex)
+                .and_then(|replace| replace.apply_to(df, &col_name));
+        match result {
+            Ok(df) => {
+                let step = format!(
+                    "Replace {:?} with {:?} in {}",
+                    self.replace_find, self.replace_with, col_name
+                );
+                self.replace_preview = None;
+                self.replace_data(df, step);
+            }
+            Err(e) => self.notifications.error(format!("Replace error: {}", e)),
+        }
+    }
+
+    pub(super) fn render_replace_dialog(&mut self, ctx: &Context) {
+        let mut open = self.replace_open;
+        Window::new("Find and replace")
+            .auto_sized()
+            .collapsible(false)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                let string_columns: Vec<String> = self
+                    .original_dataframe
+                    .as_ref()
+                    .map(|df| {
+                        df.get_columns()
+                            .iter()
+                            .filter(|column| column.dtype() == &DataType::String)
+                            .map(|column| column.name().to_string())
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let mut changed = false;
+                egui::Grid::new("replace_options").show(ui, |ui| {
+                    ui.label("Column:");
+                    ComboBox::from_id_salt("replace_column")
+                        .selected_text(self.replace_column.as_deref().unwrap_or("Select column"))
+                        .show_ui(ui, |ui| {
+                            for name in string_columns {
+                                let label = name.clone();
+                                changed |= ui
+                                    .selectable_value(&mut self.replace_column, Some(name), label)
+                                    .changed();
+                            }
+                        });
+                    ui.end_row();
+                    ui.label("Find:");
+                    changed |= ui.text_edit_singleline(&mut self.replace_find).changed();
+                    ui.end_row();
+                    ui.label("Replace with:");
+                    changed |= ui.text_edit_singleline(&mut self.replace_with).changed();
+                    ui.end_row();
+                    ui.label("");
+                    changed |= ui
+                        .checkbox(&mut self.replace_regex, "Regex")
+                        .on_hover_text("Insert captured groups with ${1} or ${name}")
+                        .changed();
+                    ui.end_row();
+                });
+                if changed {
+                    self.replace_preview = None;
+                }
+                let ready = self.replace_column.is_some() && !self.replace_find.is_empty();
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(ready, egui::Button::new("Preview"))
+                        .clicked()
+                    {
+                        self.preview_replace();
+                    }
+                    let previewed = self
+                        .replace_preview
+                        .as_ref()
+                        .is_some_and(|preview| preview.affected > 0);
+                    if ui
+                        .add_enabled(previewed, egui::Button::new("Apply"))
+                        .clicked()
+                    {
+                        self.apply_replace();
+                    }
+                });
+                let Some(preview) = &self.replace_preview else {
+                    return;
+                };
+                if preview.affected == 0 {
+                    ui.label("No values match.");
+                    return;
+                }
+                ui.label(format!("{} values will change", preview.affected));
+                ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    egui::Grid::new("replace_preview")
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.strong("Row");
+                            ui.strong("Before");
+                            ui.strong("After");
+                            ui.end_row();
+                            for (row, before, after) in &preview.samples {
+                                ui.label(row);
+                                ui.label(before);
+                                ui.label(after);
+                                ui.end_row();
+                            }
+                        });
+                    if preview.affected > preview.samples.len() {
+                        ui.weak(format!(
+                            "… and {} more",
+                            preview.affected - preview.samples.len()
+                        ));
+                    }
+                });
+            });
+        self.replace_open = open;
+    }
+
+    fn run_unpivot(&mut self) {
+        let Some(df) = &self.original_dataframe else {
+            return;
+        };
+        match unpivot::unpivot(
+            df,
+            &self.unpivot_index,
+            &self.unpivot_values,
+            &self.unpivot_variable_name,
+            &self.unpivot_value_name,
+            ROW_INDEX_COLUMN,
+        ) {
+            Ok(df) => {
+                let step = format!(
+                    "Unpivot {} by {}",
+                    self.unpivot_values.join(", "),
+                    self.unpivot_index.join(", ")
+                );
+                self.unpivot_index.clear();
+                self.unpivot_values.clear();
+                self.unpivot_open = false;
+                self.replace_data(df, step);
+            }
+            Err(e) => self.notifications.error(format!("Unpivot error: {}", e)),
+        }
+    }
+
+    pub(super) fn render_unpivot_dialog(&mut self, ctx: &Context) {
+        let mut open = self.unpivot_open;
+        Window::new("Unpivot")
+            .auto_sized()
+            .collapsible(false)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.label("Id columns stay as they are; value columns become rows.");
+                ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+                    egui::Grid::new("unpivot_columns")
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.strong("Column");
+                            ui.strong("Id");
+                            ui.strong("Value");
+                            ui.end_row();
+                            for name in &self.column_names {
+                                if name == ROW_INDEX_COLUMN {
+                                    continue;
+                                }
+                                ui.label(name);
+                                let mut is_index = self.unpivot_index.contains(name);
+                                if ui.checkbox(&mut is_index, "").changed() {
+                                    self.unpivot_values.retain(|n| n != name);
+                                    if is_index {
+                                        self.unpivot_index.push(name.clone());
+                                    } else {
+                                        self.unpivot_index.retain(|n| n != name);
+                                    }
+                                }
+                                let mut is_value = self.unpivot_values.contains(name);
+                                if ui.checkbox(&mut is_value, "").changed() {
+                                    self.unpivot_index.retain(|n| n != name);
+                                    if is_value {
+                                        self.unpivot_values.push(name.clone());
+                                    } else {
+                                        self.unpivot_values.retain(|n| n != name);
+                                    }
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+                if ui.button("All other columns are values").clicked() {
+                    self.unpivot_values = self
+                        .column_names
+                        .iter()
+                        .filter(|name| {
+                            *name != ROW_INDEX_COLUMN && !self.unpivot_index.contains(name)
+                        })
+                        .cloned()
+                        .collect();
+                }
+                egui::Grid::new("unpivot_names").show(ui, |ui| {
+                    ui.label("Name column:");
+                    ui.text_edit_singleline(&mut self.unpivot_variable_name);
+                    ui.end_row();
+                    ui.label("Value column:");
+                    ui.text_edit_singleline(&mut self.unpivot_value_name);
+                    ui.end_row();
+                });
+                if ui
+                    .add_enabled(
+                        !self.unpivot_values.is_empty(),
+                        egui::Button::new("Unpivot"),
+                    )
+                    .clicked()
+                {
+                    self.run_unpivot();
+                }
+            });
+        self.unpivot_open = self.unpivot_open && open;
+    }
+
+    fn handle_join_pick_file(&mut self) {
+        let Some(path) = FileDialog::new()
+            .add_filter("Parquet files", &["parquet"])
+            .pick_file()
+        else {
+            return;
+        };
+        match loader::read_parquet(std::slice::from_ref(&path)) {
+            Ok(df) => {
+                let name = file_label(&path);
+                // Default to a key column of the same name, if there is one.
+                self.join_right_key = self
+                    .join_left_key
+                    .clone()
+                    .filter(|key| df.column(key).is_ok());
+                self.join_other = Some((name, df));
+            }
+            Err(e) => {
+                self.notifications
+                    .error(format!("Error processing Parquet files: {}", e));
+            }
+        }
+    }
+
+    fn run_join(&mut self) {
+        let (Some(left), Some((name, right)), Some(left_key), Some(right_key)) = (
+            &self.original_dataframe,
+            &self.join_other,
+            &self.join_left_key,
+            &self.join_right_key,
+        ) else {
+            return;
+        };
+        match join::join(left, right, left_key, right_key, self.join_kind) {
+            Ok(df) => {
+                let step = format!(
+                    "{} join {} on {} = {}",
+                    self.join_kind, name, left_key, right_key
+                );
+                self.replace_data(df, step);
+                self.join_open = false;
+                self.join_other = None;
+            }
+            Err(e) => self.notifications.error(format!("Join error: {}", e)),
+        }
+    }
+
+    pub(super) fn render_join_dialog(&mut self, ctx: &Context) {
+        let mut open = self.join_open;
+        Window::new("Join file")
+            .auto_sized()
+            .collapsible(false)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.button("Choose file…").clicked() {
+                        self.handle_join_pick_file();
+                    }
+                    match &self.join_other {
+                        Some((name, df)) => {
+                            ui.label(format!("{} ({} rows)", name, df.height()));
+                        }
+                        None => {
+                            ui.weak("No file chosen");
+                        }
+                    }
+                });
+                let right_columns: Vec<String> = self
+                    .join_other
+                    .as_ref()
+                    .map(|(_, df)| {
+                        df.get_column_names()
+                            .iter()
+                            .map(|name| name.to_string())
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                egui::Grid::new("join_options").show(ui, |ui| {
+                    ui.label("Current key:");
+                    ComboBox::from_id_salt("join_left_key")
+                        .selected_text(self.join_left_key.as_deref().unwrap_or("Select column"))
+                        .show_ui(ui, |ui| {
+                            for name in &self.column_names {
+                                if name != ROW_INDEX_COLUMN {
+                                    ui.selectable_value(
+                                        &mut self.join_left_key,
+                                        Some(name.clone()),
+                                        name,
+                                    );
+                                }
+                            }
+                        });
+                    ui.end_row();
+                    ui.label("Other key:");
+                    ComboBox::from_id_salt("join_right_key")
+                        .selected_text(self.join_right_key.as_deref().unwrap_or("Select column"))
+                        .show_ui(ui, |ui| {
+                            for name in &right_columns {
+                                ui.selectable_value(
+                                    &mut self.join_right_key,
+                                    Some(name.clone()),
+                                    name,
+                                );
+                            }
+                        });
+                    ui.end_row();
+                    ui.label("Join type:");
+                    ComboBox::from_id_salt("join_kind")
+                        .selected_text(self.join_kind.to_string())
+                        .show_ui(ui, |ui| {
+                            for kind in JoinKind::ALL {
+                                ui.selectable_value(&mut self.join_kind, kind, kind.to_string())
+                                    .on_hover_text(kind.description());
+                            }
+                        });
+                    ui.end_row();
+                });
+                ui.weak(self.join_kind.description());
+                let ready = self.join_other.is_some()
+                    && self.join_left_key.is_some()
+                    && self.join_right_key.is_some();
+                if ui.add_enabled(ready, egui::Button::new("Join")).clicked() {
+                    self.run_join();
+                }
+            });
+        self.join_open = self.join_open && open;
+    }
+
+    pub(super) fn explode_column(&mut self, col_name: &str) {
+        let Some(df) = &self.original_dataframe else {
+            return;
+        };
+        match nested::explode(df, col_name) {
+            Ok(df) => self.replace_data(df, format!("Explode {}", col_name)),
+            Err(e) => self.notifications.error(format!("Explode error: {}", e)),
+        }
+    }
+
+    pub(super) fn unnest_column(&mut self, col_name: &str) {
+        let Some(df) = &self.original_dataframe else {
+            return;
+        };
+        match nested::unnest(df, col_name) {
+            Ok(df) => {
+                let position = self.column_order.iter().position(|n| n == col_name);
+                self.replace_data(df, format!("Unnest {}", col_name));
+                // Put the fields where the struct column was rather than at the end.
+                if let Some(position) = position {
+                    let prefix = format!("{}.", col_name);
+                    let (fields, rest): (Vec<String>, Vec<String>) = self
+                        .column_order
+                        .drain(..)
+                        .partition(|name| name.starts_with(&prefix));
+                    self.column_order = rest;
+                    let position = position.min(self.column_order.len());
+                    self.column_order.splice(position..position, fields);
+                }
+            }
+            Err(e) => self.notifications.error(format!("Unnest error: {}", e)),
+        }
+    }
+
+    pub(super) fn drop_hidden_columns(&mut self) {
+        let Some(df) = &self.original_dataframe else {
+            return;
+        };
+        let hidden: Vec<String> = self
+            .column_order
+            .iter()
+            .filter(|name| *name != ROW_INDEX_COLUMN && self.hidden_columns.contains(*name))
+            .cloned()
+            .collect();
+        if hidden.is_empty() {
+            return;
+        }
+        let df = df.drop_many(&hidden);
+        self.computed_columns
+            .retain(|(name, _)| !hidden.contains(name));
+        self.replace_data(df, format!("Drop columns {}", hidden.join(", ")));
+    }
+
+    /// Renames a column of the loaded data, carrying its display settings over.
+    pub(super) fn rename_column(&mut self, old: &str, new: &str) {
+        let Some(df) = &self.original_dataframe else {
+            return;
+        };
+        let new = new.trim();
+        if new.is_empty() || new == old {
+            return;
+        }
+        if self.column_names.iter().any(|name| name == new) {
+            self.notifications
+                .error(format!("Rename error: column {} already exists", new));
+            return;
+        }
+        let mut df = df.clone();
+        if let Err(e) = df.rename(old, new.into()) {
+            self.notifications.error(format!("Rename error: {}", e));
+            return;
+        }
+        fn rename_key<V>(map: &mut HashMap<String, V>, old: &str, new: &str) {
+            if let Some(value) = map.remove(old) {
+                map.insert(new.to_string(), value);
+            }
+        }
+        fn rename_member(set: &mut HashSet<String>, old: &str, new: &str) {
+            if set.remove(old) {
+                set.insert(new.to_string());
+            }
+        }
+        rename_key(&mut self.column_widths, old, new);
+        rename_key(&mut self.column_wrap, old, new);
+        rename_key(&mut self.column_align, old, new);
+        rename_key(&mut self.column_display, old, new);
+        rename_key(&mut self.epoch_columns, old, new);
+        rename_member(&mut self.hidden_columns, old, new);
+        rename_member(&mut self.monospace_columns, old, new);
+        rename_member(&mut self.masked_columns, old, new);
+        rename_member(&mut self.json_columns, old, new);
+        rename_member(&mut self.image_columns, old, new);
+        for name in self
+            .column_order
+            .iter_mut()
+            .chain(self.highlight_column.iter_mut())
+            .chain(self.computed_columns.iter_mut().map(|(name, _)| name))
+        {
+            if name == old {
+                *name = new.to_string();
+            }
+        }
+        self.replace_data(df, format!("Rename column {} to {}", old, new));
+    }
+
+    /// Appends a column computed from `computed_expression` to the loaded data.
+    fn add_computed_column(&mut self) {
+        let Some(df) = &self.original_dataframe else {
+            return;
+        };
+        let name = self.computed_name.trim().to_string();
+        let text = self.computed_expression.trim().to_string();
+        if name.is_empty() {
+            self.computed_error = Some("Enter a column name".to_string());
+            return;
+        }
+        if self.column_names.contains(&name) {
+            self.computed_error = Some(format!("Column {} already exists", name));
+            return;
+        }
+        let result = expression::parse(&text)
+            .and_then(|expr| df.clone().lazy().with_column(expr.alias(&name)).collect());
+        match result {
+            Ok(df) => {
+                self.computed_error = None;
+                self.computed_name.clear();
+                self.computed_expression.clear();
+                self.computed_columns.push((name.clone(), text.clone()));
+                self.replace_data(df, format!("Add column {} = {}", name, text));
+            }
+            Err(e) => self.computed_error = Some(e.to_string()),
+        }
+    }
+
+    pub(super) fn render_computed_dialog(&mut self, ctx: &Context) {
+        let mut open = self.computed_open;
+        let mut remove = None;
+        Window::new("Computed columns")
+            .auto_sized()
+            .collapsible(false)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                egui::Grid::new("computed_grid")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label("Name:");
+                        ui.text_edit_singleline(&mut self.computed_name);
+                        ui.end_row();
+                        ui.label("Expression:");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.computed_expression)
+                                .hint_text("col(\"bytes\") / 1048576")
+                                .desired_width(320.0),
+                        );
+                        ui.end_row();
+                    });
+                if ui.button("Add").clicked() {
+                    self.add_computed_column();
+                }
+                if let Some(error) = &self.computed_error {
+                    ui.colored_label(Color32::RED, error);
+                }
+                if !self.computed_columns.is_empty() {
+                    ui.separator();
+                    for (name, text) in &self.computed_columns {
+                        ui.horizontal(|ui| {
+                            if ui.small_button("✖").on_hover_text("Remove").clicked() {
+                                remove = Some(name.clone());
+                            }
+                            ui.label(format!("{} = {}", name, text));
+                        });
+                    }
+                }
+            });
+        self.computed_open = open;
+        if let Some(name) = remove {
+            self.drop_column(&name);
+        }
+    }
+}
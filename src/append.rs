@@ -0,0 +1,47 @@
+use polars::prelude::*;
+
+/// Column naming the file each row came from once files have been appended.
+pub const SOURCE_COLUMN: &str = "source_file";
+
+/// Appends the rows of `other` to `current`, filling `SOURCE_COLUMN` with `other_source`
+/// for the new rows and `current_source` for existing rows that have none yet.
+///
+/// Columns are matched by name: missing ones are filled with nulls and differing types
+/// are widened to a common type. New rows are numbered in `row_index` after the
+/// existing ones.
+pub fn append(
+    current: &DataFrame,
+    current_source: &str,
+    other: &DataFrame,
+    other_source: &str,
+    row_index: &str,
+) -> PolarsResult<DataFrame> {
+    let mut current = current.clone().lazy();
+    if current.collect_schema()?.get(SOURCE_COLUMN).is_none() {
+        current = current.with_column(lit(current_source).alias(SOURCE_COLUMN));
+    }
+    let next_index = current_max_index(&current, row_index)?;
+    let other = other
+        .clone()
+        .lazy()
+        .with_row_index(row_index, Some(next_index))
+        .with_column(lit(other_source).alias(SOURCE_COLUMN));
+    concat(
+        [current, other],
+        UnionArgs {
+            diagonal: true,
+            to_supertypes: true,
+            ..Default::default()
+        },
+    )?
+    .collect()
+}
+
+fn current_max_index(current: &LazyFrame, row_index: &str) -> PolarsResult<IdxSize> {
+    let max = current.clone().select([col(row_index).max()]).collect()?;
+    Ok(max
+        .column(row_index)?
+        .idx()?
+        .get(0)
+        .map_or(0, |max| max + 1))
+}
@@ -0,0 +1,126 @@
+//! The most frequent values of a column as a bar chart.
+
+use crate::minichart;
+use crate::plot;
+use eframe::egui::Ui;
+use egui_plot::{Bar, BarChart};
+use polars::prelude::*;
+
+pub const DEFAULT_TOP: usize = 20;
+pub const MAX_TOP: usize = 200;
+/// Bar thickness, where bars are one apart.
+const BAR_WIDTH: f64 = 0.8;
+/// Longer values are cut short in the axis labels; the tooltip shows them in full.
+const LABEL_CHARS: usize = 24;
+const LABEL_WIDTH: f32 = 120.0;
+
+pub struct ValueCounts {
+    /// Most frequent first.
+    pub top: Vec<(String, usize)>,
+    /// Rows with any other value.
+    pub other: usize,
+    pub nulls: usize,
+}
+
+pub fn compute(column: &Column, limit: usize) -> ValueCounts {
+    let top = minichart::top_values(column, limit);
+    let nulls = column.null_count();
+    let shown: usize = top.iter().map(|(_, count)| count).sum();
+    ValueCounts {
+        other: column.len() - nulls - shown,
+        top,
+        nulls,
+    }
+}
+
+/// The bars in order from the top: the values, then the other values and the nulls when
+/// there are any. Only values have `Some` and can be clicked.
+fn rows(counts: &ValueCounts) -> Vec<(Option<&String>, &str, usize)> {
+    counts
+        .top
+        .iter()
+        .map(|(value, count)| (Some(value), value.as_str(), *count))
+        .chain([
+            (None, "(other values)", counts.other),
+            (None, "(null)", counts.nulls),
+        ])
+        .filter(|(value, _, count)| value.is_some() || *count > 0)
+        .collect()
+}
+
+/// Shows one horizontal bar per value, most frequent on top, returning the value whose
+/// bar was clicked.
+pub fn show(ui: &mut Ui, counts: &ValueCounts) -> Option<String> {
+    let rows = rows(counts);
+    let color = ui.visuals().selection.bg_fill;
+    let weak = ui.visuals().weak_text_color().gamma_multiply(0.5);
+    let total = rows.iter().map(|(_, _, count)| count).sum::<usize>().max(1) as f64;
+    let labels: Vec<String> = rows
+        .iter()
+        .map(|(_, label, _)| plot::truncate_label(label, LABEL_CHARS))
+        .collect();
+    // Row i is drawn at y = -i, so that the first row is on top.
+    let row_at = |y: f64| {
+        let row = (-y).round();
+        (row >= 0.0 && (row as usize) < rows.len() && (row + y).abs() <= BAR_WIDTH / 2.0)
+            .then_some(row as usize)
+    };
+    let plot = egui_plot::Plot::new("value_counts")
+        .x_axis_label("Rows")
+        .y_axis_formatter(|mark, _| {
+            let row = -mark.value;
+            if row.fract() == 0.0 && row >= 0.0 {
+                labels.get(row as usize).cloned().unwrap_or_default()
+            } else {
+                String::new()
+            }
+        })
+        .y_grid_spacer(egui_plot::uniform_grid_spacer(|_| [1.0, 5.0, 10.0]))
+        .y_axis_min_width(LABEL_WIDTH)
+        .include_x(0.0)
+        .show_x(false)
+        .show_y(false)
+        .allow_zoom([false, true])
+        .allow_drag([false, true])
+        .allow_scroll(false)
+        .allow_boxed_zoom(false)
+        .show(ui, |plot_ui| {
+            let hovered = plot_ui
+                .pointer_coordinate()
+                .and_then(|pointer| row_at(pointer.y));
+            let bars = rows
+                .iter()
+                .enumerate()
+                .map(|(i, (value, label, count))| {
+                    let fill = match value {
+                        Some(_) if hovered == Some(i) => color,
+                        Some(_) => color.gamma_multiply(0.7),
+                        None => weak,
+                    };
+                    Bar::new(-(i as f64), *count as f64)
+                        .name(label)
+                        .width(BAR_WIDTH)
+                        .fill(fill)
+                })
+                .collect();
+            plot_ui.bar_chart(BarChart::new(bars).horizontal().allow_hover(false));
+            hovered
+        });
+    let row = plot.inner.filter(|_| plot.response.hovered())?;
+    let (value, label, count) = rows[row];
+    let response = plot.response.on_hover_ui_at_pointer(|ui| {
+        ui.label(label);
+        ui.label(format!("{} ({:.1}%)", count, 100.0 * count as f64 / total));
+        if value.is_some() {
+            ui.weak("Click to see these rows");
+        }
+    });
+    value.filter(|_| response.clicked()).cloned()
+}
+
+/// Keeps the rows whose value, as text, is `value`.
+pub fn value_filter(column: &str, value: &str) -> Expr {
+    col(column)
+        .cast(DataType::String)
+        .eq(lit(value.to_string()))
+}
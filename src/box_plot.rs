@@ -0,0 +1,139 @@
+//! Distributions of a numeric column per group, drawn as box plots.
+
+use crate::plot::{self, palette};
+use crate::scatter;
+use eframe::egui::Stroke;
+use egui_plot::{BoxElem, BoxPlot, BoxSpread, PlotPoints, PlotUi, Points};
+use polars::prelude::*;
+use std::collections::HashMap;
+
+pub const DEFAULT_GROUPS: usize = 12;
+pub const MAX_GROUPS: usize = 50;
+/// Outliers drawn per group; the rest are counted but not painted.
+const MAX_OUTLIERS: usize = 500;
+const BOX_WIDTH: f64 = 0.6;
+/// Longer group names are cut short under their box; the tooltip shows them in full.
+const LABEL_CHARS: usize = 14;
+
+pub struct GroupBox {
+    /// `None` for rows with a null group.
+    pub group: Option<String>,
+    pub count: usize,
+    pub q1: f64,
+    pub median: f64,
+    pub q3: f64,
+    /// The furthest values within 1.5 times the interquartile range of the box.
+    pub low_whisker: f64,
+    pub high_whisker: f64,
+    pub outliers: Vec<f64>,
+    pub outlier_count: usize,
+}
+
+/// Box plots of `value` for the `limit` largest groups of `group`, largest first.
+/// Rows with a null group form a group of their own.
+pub fn compute(
+    df: &DataFrame,
+    value: &str,
+    group: &str,
+    limit: usize,
+) -> PolarsResult<Vec<GroupBox>> {
+    let values = scatter::to_f64(df.column(value)?)?;
+    let groups = df.column(group)?.cast(&DataType::String)?;
+    let groups = groups.str()?;
+    let mut by_group: HashMap<Option<&str>, Vec<f64>> = HashMap::new();
+    for (group, value) in groups.iter().zip(values.iter()) {
+        if let Some(value) = value.filter(|value| value.is_finite()) {
+            by_group.entry(group).or_default().push(value);
+        }
+    }
+    let mut by_group: Vec<(Option<&str>, Vec<f64>)> = by_group.into_iter().collect();
+    by_group.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then_with(|| a.0.cmp(&b.0)));
+    by_group.truncate(limit);
+    Ok(by_group
+        .into_iter()
+        .map(|(group, mut values)| {
+            values.sort_by(f64::total_cmp);
+            let q1 = quantile(&values, 0.25);
+            let q3 = quantile(&values, 0.75);
+            let reach = 1.5 * (q3 - q1);
+            let inside = |value: &&f64| (q1 - reach..=q3 + reach).contains(*value);
+            let low_whisker = values.iter().find(inside).copied().unwrap_or(q1);
+            let high_whisker = values.iter().rev().find(inside).copied().unwrap_or(q3);
+            let outliers: Vec<f64> = values.iter().filter(|v| !inside(v)).copied().collect();
+            let step = outliers.len().div_ceil(MAX_OUTLIERS).max(1);
+            GroupBox {
+                group: group.map(str::to_string),
+                count: values.len(),
+                q1,
+                median: quantile(&values, 0.5),
+                q3,
+                low_whisker,
+                high_whisker,
+                outlier_count: outliers.len(),
+                outliers: outliers.into_iter().step_by(step).collect(),
+            }
+        })
+        .collect())
+}
+
+/// Linearly interpolated quantile of sorted, non-empty `values`.
+fn quantile(values: &[f64], q: f64) -> f64 {
+    let position = q * (values.len() - 1) as f64;
+    let (below, above) = (position.floor() as usize, position.ceil() as usize);
+    values[below] + (values[above] - values[below]) * (position - below as f64)
+}
+
+/// Adds one box per group at x = 0, 1, 2, …, with its outliers as circles.
+pub fn show(boxes: &[GroupBox], plot_ui: &mut PlotUi, hovered: Option<usize>) {
+    for (i, b) in boxes.iter().enumerate() {
+        let color = palette(i);
+        let x = i as f64;
+        let stroke = Stroke::new(if hovered == Some(i) { 2.0 } else { 1.2 }, color);
+        let spread = BoxSpread::new(b.low_whisker, b.q1, b.median, b.q3, b.high_whisker);
+        let element = BoxElem::new(x, spread)
+            .name(b.label())
+            .box_width(BOX_WIDTH)
+            .whisker_width(BOX_WIDTH / 2.0)
+            .fill(color.gamma_multiply(0.3))
+            .stroke(stroke);
+        plot_ui.box_plot(BoxPlot::new(vec![element]).allow_hover(false));
+        if !b.outliers.is_empty() {
+            let outliers = b.outliers.iter().map(|&outlier| [x, outlier]).collect();
+            plot_ui.points(
+                Points::new(PlotPoints::new(outliers))
+                    .color(color)
+                    .radius(2.5)
+                    .filled(false)
+                    .allow_hover(false),
+            );
+        }
+    }
+}
+
+/// The group whose box is at data position `x`.
+pub fn group_at(boxes: &[GroupBox], x: f64) -> Option<usize> {
+    let i = x.round();
+    (i >= 0.0 && (i as usize) < boxes.len() && (x - i).abs() <= BOX_WIDTH / 2.0)
+        .then_some(i as usize)
+}
+
+impl GroupBox {
+    pub fn label(&self) -> &str {
+        self.group.as_deref().unwrap_or("(null)")
+    }
+
+    /// [`GroupBox::label`] short enough for the x axis.
+    pub fn axis_label(&self) -> String {
+        plot::truncate_label(self.label(), LABEL_CHARS)
+    }
+}
+
+/// Keeps the rows of `group`, compared as text like [`compute`] groups them.
+pub fn group_filter(column: &str, group: Option<&str>) -> Expr {
+    match group {
+        Some(group) => col(column)
+            .cast(DataType::String)
+            .eq(lit(group.to_string())),
+        None => col(column).is_null(),
+    }
+}
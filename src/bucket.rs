@@ -0,0 +1,106 @@
+//! Time bucketing: derives a label column such as `2024-W05` or `FY2025-Q1` from a
+//! date or datetime column, for grouping and resampling by calendar period.
+
+use polars::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BucketUnit {
+    Day,
+    IsoWeek,
+    Month,
+    Quarter,
+    Year,
+}
+
+impl BucketUnit {
+    pub const ALL: [BucketUnit; 5] = [
+        BucketUnit::Day,
+        BucketUnit::IsoWeek,
+        BucketUnit::Month,
+        BucketUnit::Quarter,
+        BucketUnit::Year,
+    ];
+}
+
+impl std::fmt::Display for BucketUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BucketUnit::Day => write!(f, "day"),
+            BucketUnit::IsoWeek => write!(f, "ISO week"),
+            BucketUnit::Month => write!(f, "month"),
+            BucketUnit::Quarter => write!(f, "quarter"),
+            BucketUnit::Year => write!(f, "year"),
+        }
+    }
+}
+
+/// Fiscal year and quarter for a calendar month, with the fiscal year named after the
+/// calendar year it ends in (a year starting in October 2024 is FY2025).
+fn fiscal_period(year: i32, month: i8, start_month: u32) -> (i32, u32) {
+    let month = month as u32;
+    let shifted = (month + 12 - start_month) % 12;
+    let fiscal_year = if start_month > 1 && month >= start_month {
+        year + 1
+    } else {
+        year
+    };
+    (fiscal_year, shifted / 3 + 1)
+}
+
+/// Returns the name of the bucket column derived from `column`.
+pub fn bucket_column_name(column: &str, unit: BucketUnit) -> String {
+    format!("{} ({})", column, unit)
+}
+
+/// Returns a copy of `df` with a string column labelling each row's `unit` period.
+/// Quarters and years follow a fiscal calendar when `fiscal_start_month` is not January.
+pub fn add_bucket_column(
+    df: &DataFrame,
+    column: &str,
+    unit: BucketUnit,
+    fiscal_start_month: u32,
+) -> PolarsResult<DataFrame> {
+    let parts = df
+        .clone()
+        .lazy()
+        .select([
+            col(column).dt().year().alias("year"),
+            col(column).dt().month().alias("month"),
+            col(column).dt().day().alias("day"),
+            col(column).dt().iso_year().alias("iso_year"),
+            col(column).dt().week().alias("week"),
+        ])
+        .collect()?;
+    let years = parts.column("year")?.i32()?;
+    let months = parts.column("month")?.i8()?;
+    let days = parts.column("day")?.i8()?;
+    let iso_years = parts.column("iso_year")?.i32()?;
+    let weeks = parts.column("week")?.i8()?;
+    let fiscal = fiscal_start_month != 1;
+
+    let labels: StringChunked = (0..df.height())
+        .map(|i| {
+            let year = years.get(i)?;
+            let month = months.get(i)?;
+            let label = match unit {
+                BucketUnit::Day => format!("{}-{:02}-{:02}", year, month, days.get(i)?),
+                BucketUnit::IsoWeek => format!("{}-W{:02}", iso_years.get(i)?, weeks.get(i)?),
+                BucketUnit::Month => format!("{}-{:02}", year, month),
+                BucketUnit::Quarter if fiscal => {
+                    let (fiscal_year, quarter) = fiscal_period(year, month, fiscal_start_month);
+                    format!("FY{}-Q{}", fiscal_year, quarter)
+                }
+                BucketUnit::Quarter => format!("{}-Q{}", year, (month - 1) / 3 + 1),
+                BucketUnit::Year if fiscal => {
+                    format!("FY{}", fiscal_period(year, month, fiscal_start_month).0)
+                }
+                BucketUnit::Year => year.to_string(),
+            };
+            Some(label)
+        })
+        .collect();
+    let name = bucket_column_name(column, unit);
+    let mut df = df.clone();
+    df.with_column(labels.with_name(name.into()).into_series())?;
+    Ok(df)
+}
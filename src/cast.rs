@@ -0,0 +1,151 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use polars::prelude::*;
+
+/// Text layouts accepted when casting strings to dates and datetimes. Polars' own cast
+/// only understands a single layout per column.
+const DATE_FORMATS: [&str; 3] = ["%Y-%m-%d", "%Y/%m/%d", "%Y%m%d"];
+const DATETIME_FORMATS: [&str; 4] = [
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M",
+    "%Y-%m-%dT%H:%M",
+];
+
+/// Types a column can be cast to from the header menu.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CastTarget {
+    Int64,
+    Float64,
+    Utf8,
+    Date,
+    Datetime,
+    Boolean,
+    Categorical,
+}
+
+impl CastTarget {
+    pub const ALL: [CastTarget; 7] = [
+        CastTarget::Int64,
+        CastTarget::Float64,
+        CastTarget::Utf8,
+        CastTarget::Date,
+        CastTarget::Datetime,
+        CastTarget::Boolean,
+        CastTarget::Categorical,
+    ];
+
+    fn dtype(self) -> DataType {
+        match self {
+            CastTarget::Int64 => DataType::Int64,
+            CastTarget::Float64 => DataType::Float64,
+            CastTarget::Utf8 => DataType::String,
+            CastTarget::Date => DataType::Date,
+            CastTarget::Datetime => DataType::Datetime(TimeUnit::Microseconds, None),
+            CastTarget::Boolean => DataType::Boolean,
+            CastTarget::Categorical => DataType::Categorical(None, CategoricalOrdering::Physical),
+        }
+    }
+}
+
+impl std::fmt::Display for CastTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CastTarget::Int64 => write!(f, "Int64"),
+            CastTarget::Float64 => write!(f, "Float64"),
+            CastTarget::Utf8 => write!(f, "Utf8"),
+            CastTarget::Date => write!(f, "Date"),
+            CastTarget::Datetime => write!(f, "Datetime"),
+            CastTarget::Boolean => write!(f, "Boolean"),
+            CastTarget::Categorical => write!(f, "Categorical"),
+        }
+    }
+}
+
+/// Parses the usual spellings of booleans; anything else becomes null.
+fn parse_bool(text: &str) -> Option<bool> {
+    match text.trim().to_ascii_lowercase().as_str() {
+        "true" | "t" | "yes" | "y" | "1" => Some(true),
+        "false" | "f" | "no" | "n" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+fn parse_date(text: &str) -> Option<NaiveDate> {
+    let text = text.trim();
+    DATE_FORMATS
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(text, format).ok())
+}
+
+/// Parses a timestamp, treating a bare date as midnight.
+fn parse_datetime(text: &str) -> Option<NaiveDateTime> {
+    let text = text.trim();
+    DATETIME_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+        .or_else(|| parse_date(text)?.and_hms_opt(0, 0, 0))
+}
+
+fn cast_values(column: &Column, target: CastTarget) -> PolarsResult<Column> {
+    if column.dtype() != &DataType::String {
+        return column.cast(&target.dtype());
+    }
+    let values = column.str()?;
+    let name = column.name().clone();
+    let parsed = match target {
+        CastTarget::Boolean => values
+            .into_iter()
+            .map(|value| value.and_then(parse_bool))
+            .collect::<BooleanChunked>()
+            .with_name(name)
+            .into_column(),
+        CastTarget::Date => {
+            let epoch = NaiveDate::default();
+            values
+                .into_iter()
+                .map(|value| {
+                    let date = parse_date(value?)?;
+                    Some(date.signed_duration_since(epoch).num_days() as i32)
+                })
+                .collect::<Int32Chunked>()
+                .with_name(name)
+                .into_column()
+                .cast(&DataType::Date)?
+        }
+        CastTarget::Datetime => values
+            .into_iter()
+            .map(|value| Some(parse_datetime(value?)?.and_utc().timestamp_micros()))
+            .collect::<Int64Chunked>()
+            .with_name(name)
+            .into_column()
+            .cast(&target.dtype())?,
+        _ => column.cast(&target.dtype())?,
+    };
+    Ok(parsed)
+}
+
+/// Returns a copy of `df` with column `name` cast to `target`.
+///
+/// Fails without changing anything when a non-null value cannot be converted, naming
+/// how many values failed and the first of them, rather than silently nulling them.
+pub fn cast_column(df: &DataFrame, name: &str, target: CastTarget) -> PolarsResult<DataFrame> {
+    let column = df.column(name)?;
+    let cast = cast_values(column, target)?;
+    let failed = column.is_not_null() & cast.is_null();
+    let failures = failed.sum().unwrap_or(0) as usize;
+    if failures > 0 {
+        let example = failed
+            .into_iter()
+            .position(|failed| failed == Some(true))
+            .map(|row| column.get(row).map(|value| value.to_string()))
+            .transpose()?
+            .unwrap_or_default();
+        polars_bail!(
+            ComputeError: "{} values in {} cannot be read as {}, e.g. {}",
+            failures, name, target, example
+        );
+    }
+    let mut df = df.clone();
+    df.replace(name, cast.take_materialized_series())?;
+    Ok(df)
+}
@@ -0,0 +1,458 @@
+//! Command-line parsing, and the commands that run without opening a window for use
+//! in scripts.
+
+use crate::export::{self, Compression, CsvOptions, FileFormat};
+use crate::query::QueryEngines;
+use crate::{loader, metadata, xlsx};
+use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum};
+use polars::prelude::*;
+use std::error::Error;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Longest file metadata value printed by `--schema`; embedded schemas can be huge.
+const MAX_METADATA_CHARS: usize = 100;
+
+/// Name under which the loaded files can be queried.
+pub const QUERY_TABLE: &str = "tbl";
+
+/// How the table looks when the window opens, from `--columns`, `--filter` and `--sort`.
+#[derive(Default)]
+pub struct StartupView {
+    /// Columns to show, in this order; empty shows all.
+    pub columns: Vec<String>,
+    /// An expression such as `col("x") > 5` that rows must satisfy.
+    pub filter: Option<String>,
+    /// Column to sort by and whether the order is descending.
+    pub sort: Option<(String, bool)>,
+}
+
+/// Tablr, a Parquet viewer.
+///
+/// Without a command, the files open in the viewer. `--schema`, `--query` and `--tui`
+/// select their commands too, so `tablr file.parquet --query …` works as well.
+#[derive(Parser)]
+#[command(name = "tablr", version, args_conflicts_with_subcommands = true)]
+pub struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[command(flatten)]
+    view: ViewArgs,
+    /// Print the files' schema instead of opening them, like `tablr schema`
+    #[arg(long, requires = "paths", conflicts_with_all = ["query", "tui"])]
+    schema: bool,
+    /// Run SQL against the files instead of opening them, like `tablr query`
+    #[arg(long, value_name = "SQL", requires = "paths", conflicts_with = "tui")]
+    query: Option<String>,
+    #[command(flatten)]
+    query_options: QueryOptions,
+    /// Browse the files in the terminal, like `tablr tui`
+    #[arg(long, requires = "paths")]
+    tui: bool,
+    /// Log more to stderr; repeat (-vv, -vvv) for debug and trace output. RUST_LOG, when
+    /// set, takes precedence
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    pub verbose: u8,
+}
+
+impl Cli {
+    /// The command to run, with the top-level flags standing in for their subcommands.
+    pub fn into_command(self) -> Command {
+        let paths = self.view.paths.clone();
+        match self.command {
+            Some(command) => command,
+            None if self.schema => Command::Schema(SchemaArgs { paths }),
+            None => match self.query {
+                Some(query) => Command::Query(QueryArgs {
+                    paths,
+                    query,
+                    options: self.query_options,
+                }),
+                None if self.tui => Command::Tui(TuiArgs { paths }),
+                None => Command::View(self.view),
+            },
+        }
+    }
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Open the files in the viewer (the default)
+    View(ViewArgs),
+    /// Write a Parquet, CSV or NDJSON file as csv, ndjson, parquet or xlsx
+    Convert(ConvertArgs),
+    /// Print columns, types, row count and file metadata
+    Schema(SchemaArgs),
+    /// Run SQL against the files as table tbl and print the result
+    Query(QueryArgs),
+    /// Browse the files in the terminal, for machines without a display
+    Tui(TuiArgs),
+}
+
+/// Checked while parsing so that a typo fails in the terminal, not in an empty window.
+fn existing_file(arg: &str) -> Result<PathBuf, String> {
+    let path = PathBuf::from(arg);
+    if path.is_file() {
+        Ok(path)
+    } else {
+        Err(format!("{}: no such file", path.display()))
+    }
+}
+
+fn file_format(name: &str) -> Result<FileFormat, String> {
+    FileFormat::from_name(name).ok_or_else(|| "expected csv, ndjson, parquet or xlsx".to_string())
+}
+
+/// The arguments of the windowed viewer.
+#[derive(Args, Default)]
+pub struct ViewArgs {
+    /// Parquet files to open
+    #[arg(value_name = "FILE", value_parser = existing_file)]
+    pub paths: Vec<PathBuf>,
+    /// Show only these columns, in this order
+    #[arg(long, value_name = "a,b,c", value_delimiter = ',')]
+    pub columns: Vec<String>,
+    /// Keep rows matching an expression, e.g. 'col("x") > 5'
+    #[arg(long, value_name = "EXPRESSION")]
+    pub filter: Option<String>,
+    /// Sort by a column, descending with a leading '-'
+    #[arg(long, value_name = "[-]COLUMN", allow_hyphen_values = true)]
+    pub sort: Option<String>,
+    /// Start with default settings, without session, layouts or hooks
+    #[arg(long)]
+    pub safe_mode: bool,
+    /// Open a new window even when single-instance mode is on
+    #[arg(long)]
+    pub new_window: bool,
+}
+
+impl ViewArgs {
+    pub fn startup_view(&self) -> StartupView {
+        StartupView {
+            columns: self
+                .columns
+                .iter()
+                .map(|name| name.trim().to_string())
+                .filter(|name| !name.is_empty())
+                .collect(),
+            filter: self.filter.clone(),
+            sort: self
+                .sort
+                .as_deref()
+                .map(|column| match column.strip_prefix('-') {
+                    Some(column) => (column.to_string(), true),
+                    None => (column.trim_start_matches('+').to_string(), false),
+                }),
+        }
+    }
+}
+
+#[derive(Args)]
+pub struct ConvertArgs {
+    /// Parquet, CSV or NDJSON file to read
+    #[arg(value_parser = existing_file)]
+    pub input: PathBuf,
+    /// Output format; defaults to the output's extension
+    #[arg(long, value_name = "csv|ndjson|parquet|xlsx", value_parser = file_format)]
+    pub to: Option<FileFormat>,
+    /// File to write; defaults to the input with the new extension
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+pub struct SchemaArgs {
+    /// Parquet, CSV or NDJSON files to read
+    #[arg(value_name = "FILE", required = true, value_parser = existing_file)]
+    pub paths: Vec<PathBuf>,
+}
+
+#[derive(Clone, Copy, Default, ValueEnum)]
+pub enum OutputFormat {
+    /// An aligned plain-text table
+    #[default]
+    Table,
+    /// Comma-separated values with a header row
+    Csv,
+}
+
+#[derive(Args, Default)]
+pub struct QueryOptions {
+    /// How to print the result
+    #[arg(long, value_enum, default_value_t, requires = "query")]
+    pub format: OutputFormat,
+    /// Run the query on this engine: polars, the default, or duckdb when built with the
+    /// duckdb feature
+    #[arg(long, value_name = "NAME", requires = "query")]
+    pub engine: Option<String>,
+}
+
+#[derive(Args)]
+pub struct QueryArgs {
+    /// Parquet, CSV or NDJSON files to read
+    #[arg(value_name = "FILE", required = true, value_parser = existing_file)]
+    pub paths: Vec<PathBuf>,
+    /// SQL to run, with the files as table tbl
+    #[arg(short, long, value_name = "SQL")]
+    pub query: String,
+    #[command(flatten)]
+    pub options: QueryOptions,
+}
+
+#[derive(Args)]
+pub struct TuiArgs {
+    /// Parquet files to browse
+    #[arg(value_name = "FILE", required = true, value_parser = existing_file)]
+    pub paths: Vec<PathBuf>,
+}
+
+/// The format of a file, judged by its extension.
+fn format_of(path: &Path) -> Option<FileFormat> {
+    path.extension()
+        .and_then(|extension| FileFormat::from_name(&extension.to_string_lossy()))
+}
+
+/// Reads a Parquet, CSV or NDJSON file.
+pub fn read_file(path: &Path) -> Result<DataFrame, Box<dyn Error>> {
+    let df = match format_of(path) {
+        Some(FileFormat::Parquet) => loader::read_parquet(&[path.to_path_buf()])?,
+        Some(FileFormat::Csv) => CsvReadOptions::default()
+            .with_has_header(true)
+            .try_into_reader_with_file_path(Some(path.to_path_buf()))?
+            .finish()?,
+        Some(FileFormat::NdJson) => JsonLineReader::from_path(path)?.finish()?,
+        _ => return Err(format!("{}: unsupported input format", path.display()).into()),
+    };
+    Ok(df)
+}
+
+fn write_file(df: &DataFrame, path: &Path, format: FileFormat) -> PolarsResult<()> {
+    match format {
+        FileFormat::Csv => export::write_csv(df, path, &CsvOptions::default()),
+        FileFormat::NdJson => export::write_ndjson(df, path),
+        FileFormat::Parquet => export::write_parquet(df, path, Compression::Snappy),
+        FileFormat::Excel => xlsx::write_xlsx(df, path, true),
+    }
+}
+
+/// `tablr convert`: reads one file and writes it in another format. The target format
+/// comes from `--to` or else from the output's extension; without `-o`, the output is
+/// written next to the input.
+pub fn convert(args: &ConvertArgs) -> Result<(), Box<dyn Error>> {
+    let input = &args.input;
+    let format = args
+        .to
+        .or_else(|| args.output.as_deref().and_then(format_of))
+        .ok_or("no output format given; pass --to or an output with a known extension")?;
+    let output = args
+        .output
+        .clone()
+        .unwrap_or_else(|| input.with_extension(format.extension()));
+    if &output == input {
+        return Err(format!("{} would overwrite the input", output.display()).into());
+    }
+    let df = read_file(input)?;
+    write_file(&df, &output, format)?;
+    Ok(())
+}
+
+/// The given files as one lazy frame, one after the other. Parquet files are scanned so
+/// that a query only reads what it needs; other formats are read in full. Columns are
+/// matched by position, and widened where the files' types differ.
+fn scan_files(paths: &[PathBuf]) -> Result<LazyFrame, Box<dyn Error>> {
+    if paths.is_empty() {
+        return Err("no input files".into());
+    }
+    if paths
+        .iter()
+        .all(|path| format_of(path) == Some(FileFormat::Parquet))
+    {
+        return Ok(loader::scan(paths)?);
+    }
+    let frames = paths
+        .iter()
+        .map(|path| match format_of(path) {
+            Some(FileFormat::Parquet) => Ok(loader::scan(std::slice::from_ref(path))?),
+            _ => Ok(read_file(path)?.lazy()),
+        })
+        .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
+    let args = UnionArgs {
+        to_supertypes: true,
+        ..Default::default()
+    };
+    Ok(concat(frames, args)?)
+}
+
+/// Lays out `df` as a plain-text table with every row, right-aligning numbers.
+fn text_table(df: &DataFrame) -> PolarsResult<String> {
+    let columns = df.get_columns();
+    let mut cells: Vec<Vec<String>> = vec![
+        columns
+            .iter()
+            .map(|column| column.name().to_string())
+            .collect(),
+    ];
+    for row in 0..df.height() {
+        cells.push(
+            columns
+                .iter()
+                .map(|column| {
+                    let value = column.get(row)?;
+                    Ok(value
+                        .get_str()
+                        .map_or_else(|| value.to_string(), str::to_string))
+                })
+                .collect::<PolarsResult<_>>()?,
+        );
+    }
+    let widths: Vec<usize> = (0..columns.len())
+        .map(|col| {
+            cells
+                .iter()
+                .map(|row| row[col].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let mut out = String::new();
+    for (i, row) in cells.iter().enumerate() {
+        let line: Vec<String> = row
+            .iter()
+            .zip(columns)
+            .zip(&widths)
+            .map(|((text, column), &width)| {
+                if i > 0 && column.dtype().is_primitive_numeric() {
+                    format!("{:>width$}", text)
+                } else {
+                    format!("{:<width$}", text)
+                }
+            })
+            .collect();
+        out.push_str(line.join("  ").trim_end());
+        out.push('\n');
+        if i == 0 {
+            let rule: Vec<String> = widths.iter().map(|&width| "-".repeat(width)).collect();
+            out.push_str(&rule.join("  "));
+            out.push('\n');
+        }
+    }
+    Ok(out)
+}
+
+/// `tablr query <file>... --query <sql>`: runs a SQL query against the files, available as
+/// [`QUERY_TABLE`], and prints the result as a table or as CSV.
+pub fn query(args: &QueryArgs) -> Result<(), Box<dyn Error>> {
+    query_with(args, &QueryEngines::default())
+}
+
+/// Like [`query`], with `--engine` choosing among `engines`, for binaries that add their
+/// own query engines.
+pub fn query_with(args: &QueryArgs, engines: &QueryEngines) -> Result<(), Box<dyn Error>> {
+    write_query(args, engines, &mut io::stdout().lock())
+}
+
+fn write_query(
+    args: &QueryArgs,
+    engines: &QueryEngines,
+    out: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    let engine = engines.get(args.options.engine.as_deref())?;
+    let tables = vec![(QUERY_TABLE.to_string(), scan_files(&args.paths)?)];
+    let mut df = engine.execute(tables, &args.query)?;
+    if let OutputFormat::Csv = args.options.format {
+        CsvWriter::new(out).finish(&mut df)?;
+    } else {
+        out.write_all(text_table(&df)?.as_bytes())?;
+    }
+    Ok(())
+}
+
+fn print_schema(path: &Path, out: &mut impl Write) -> Result<(), Box<dyn Error>> {
+    writeln!(out, "{}", path.display())?;
+    let schema = if format_of(path) == Some(FileFormat::Parquet) {
+        let file = &metadata::read_metadata(&[path.to_path_buf()])?[0];
+        writeln!(out, "  rows: {}", file.num_rows)?;
+        writeln!(out, "  row groups: {}", file.row_groups.len())?;
+        writeln!(out, "  format version: {}", file.version)?;
+        if let Some(created_by) = &file.created_by {
+            writeln!(out, "  created by: {}", created_by)?;
+        }
+        let pairs = file.key_value_metadata.as_deref().unwrap_or_default();
+        if !pairs.is_empty() {
+            writeln!(out, "  metadata:")?;
+            for pair in pairs {
+                let value = pair.value.as_deref().unwrap_or_default();
+                let shown: String = value.chars().take(MAX_METADATA_CHARS).collect();
+                let ellipsis = if shown.len() < value.len() { "…" } else { "" };
+                writeln!(out, "    {}: {}{}", pair.key, shown, ellipsis)?;
+            }
+        }
+        loader::scan(&[path.to_path_buf()])?.collect_schema()?
+    } else {
+        let df = read_file(path)?;
+        writeln!(out, "  rows: {}", df.height())?;
+        df.schema().clone()
+    };
+    writeln!(out, "  columns:")?;
+    let width = schema.iter_names().map(|name| name.chars().count()).max();
+    for (name, dtype) in schema.iter() {
+        writeln!(
+            out,
+            "    {:<width$}  {}",
+            name.as_str(),
+            dtype,
+            width = width.unwrap_or(0)
+        )?;
+    }
+    Ok(())
+}
+
+/// `tablr schema <file>...`: prints each file's columns, types, row count and, for
+/// Parquet, the file-level metadata.
+pub fn schema(args: &SchemaArgs) -> Result<(), Box<dyn Error>> {
+    let mut stdout = io::stdout().lock();
+    for (i, path) in args.paths.iter().enumerate() {
+        if i > 0 {
+            writeln!(stdout)?;
+        }
+        print_schema(path, &mut stdout).map_err(|e| format!("{}: {}", path.display(), e))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn queries_several_csv_files_as_one_table() {
+        let dir = std::env::temp_dir().join(format!("tablr-cli-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let (a, b) = (dir.join("a.csv"), dir.join("b.csv"));
+        fs::write(&a, "name,amount\nx,1\ny,2\n").unwrap();
+        fs::write(&b, "name,amount\nz,3.5\n").unwrap();
+        let cli = Cli::try_parse_from([
+            "tablr".as_ref(),
+            "query".as_ref(),
+            a.as_os_str(),
+            b.as_os_str(),
+            "--query".as_ref(),
+            "SELECT name, amount FROM tbl ORDER BY amount DESC".as_ref(),
+            "--format".as_ref(),
+            "csv".as_ref(),
+        ])
+        .unwrap();
+        let Command::Query(args) = cli.into_command() else {
+            panic!("expected the query command");
+        };
+        let mut out = Vec::new();
+        let result = write_query(&args, &QueryEngines::default(), &mut out);
+        fs::remove_dir_all(&dir).unwrap();
+        result.unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "name,amount\nz,3.5\ny,2.0\nx,1.0\n"
+        );
+    }
+}
@@ -0,0 +1,36 @@
+use polars::prelude::*;
+
+/// Returns the columns whose non-null values are all the same, with that value.
+/// All-null columns are included with the value `null`; `skip` is ignored.
+pub fn find_constant_columns(df: &DataFrame, skip: &str) -> PolarsResult<Vec<(String, String)>> {
+    let mut constant = Vec::new();
+    if df.height() < 2 {
+        return Ok(constant);
+    }
+    for column in df.get_columns() {
+        if column.name().as_str() == skip {
+            continue;
+        }
+        let values = column.drop_nulls();
+        let value = match values.len() {
+            0 => "null".to_string(),
+            _ if values.n_unique()? == 1 => values.get(0)?.str_value().to_string(),
+            _ => continue,
+        };
+        constant.push((column.name().to_string(), value));
+    }
+    Ok(constant)
+}
+
+/// One-line summary such as `region=EU, version=3, …`, listing at most `limit` columns.
+pub fn summary(columns: &[(String, String)], limit: usize) -> String {
+    let mut parts: Vec<String> = columns
+        .iter()
+        .take(limit)
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect();
+    if columns.len() > limit {
+        parts.push("…".to_string());
+    }
+    parts.join(", ")
+}
@@ -0,0 +1,92 @@
+use crate::similarity::pearson;
+use eframe::egui::{self, Align2, Color32, Sense, TextStyle, Ui, Vec2};
+use polars::prelude::*;
+
+const CELL_SIZE: Vec2 = Vec2::new(56.0, 24.0);
+
+pub struct CorrelationMatrix {
+    pub columns: Vec<String>,
+    /// Pearson correlation for every pair of `columns`, `None` where it is undefined.
+    pub values: Vec<Vec<Option<f64>>>,
+}
+
+/// Computes pairwise Pearson correlations between numeric `columns`, ignoring rows
+/// where either value is null.
+pub fn correlation_matrix(df: &DataFrame, columns: &[String]) -> PolarsResult<CorrelationMatrix> {
+    let data = columns
+        .iter()
+        .map(|name| {
+            Ok(df
+                .column(name)?
+                .cast(&DataType::Float64)?
+                .f64()?
+                .into_iter()
+                .collect::<Vec<_>>())
+        })
+        .collect::<PolarsResult<Vec<_>>>()?;
+    let mut values = vec![vec![None; columns.len()]; columns.len()];
+    for i in 0..columns.len() {
+        for j in i..columns.len() {
+            let r = if i == j {
+                Some(1.0)
+            } else {
+                pearson(&data[i], &data[j])
+            };
+            values[i][j] = r;
+            values[j][i] = r;
+        }
+    }
+    Ok(CorrelationMatrix {
+        columns: columns.to_vec(),
+        values,
+    })
+}
+
+/// Red for positive, blue for negative correlations, stronger the closer to ±1.
+fn cell_color(r: f64) -> Color32 {
+    let alpha = (r.abs() * 200.0) as u8;
+    if r >= 0.0 {
+        Color32::from_rgba_unmultiplied(220, 60, 60, alpha)
+    } else {
+        Color32::from_rgba_unmultiplied(60, 110, 220, alpha)
+    }
+}
+
+pub fn show(ui: &mut Ui, matrix: &CorrelationMatrix) {
+    egui::Grid::new("correlation_matrix")
+        .spacing([2.0, 2.0])
+        .show(ui, |ui| {
+            ui.label("");
+            for name in &matrix.columns {
+                ui.add_sized(
+                    [CELL_SIZE.x, CELL_SIZE.y],
+                    egui::Label::new(name.as_str()).truncate(),
+                )
+                .on_hover_text(name);
+            }
+            ui.end_row();
+            for (i, row) in matrix.values.iter().enumerate() {
+                ui.label(&matrix.columns[i]);
+                for (j, r) in row.iter().enumerate() {
+                    let (rect, response) = ui.allocate_exact_size(CELL_SIZE, Sense::hover());
+                    let text = match r {
+                        Some(r) => {
+                            ui.painter().rect_filled(rect, 2.0, cell_color(*r));
+                            format!("{:.2}", r)
+                        }
+                        None => "–".to_string(),
+                    };
+                    ui.painter().text(
+                        rect.center(),
+                        Align2::CENTER_CENTER,
+                        text,
+                        TextStyle::Small.resolve(ui.style()),
+                        ui.visuals().text_color(),
+                    );
+                    response
+                        .on_hover_text(format!("{} × {}", matrix.columns[i], matrix.columns[j]));
+                }
+                ui.end_row();
+            }
+        });
+}
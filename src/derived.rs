@@ -0,0 +1,56 @@
+use eframe::egui::{Color32, Ui};
+
+/// Tracks whether a view derived from the table (scan results, aggregates, charts)
+/// is up to date with the data it was computed from.
+///
+/// The app bumps a data version whenever the visible data changes; derived views
+/// compare it with the version they were computed for.
+#[derive(Debug, Clone)]
+pub struct DerivedState {
+    pub auto_refresh: bool,
+    computed_for: Option<u64>,
+}
+
+impl DerivedState {
+    pub fn new(auto_refresh: bool) -> Self {
+        Self {
+            auto_refresh,
+            computed_for: None,
+        }
+    }
+
+    pub fn is_stale(&self, data_version: u64) -> bool {
+        self.computed_for != Some(data_version)
+    }
+
+    pub fn has_result(&self) -> bool {
+        self.computed_for.is_some()
+    }
+
+    /// Returns `true` when the view should be recomputed without user interaction.
+    pub fn needs_refresh(&self, data_version: u64) -> bool {
+        self.auto_refresh && self.is_stale(data_version)
+    }
+
+    pub fn mark_computed(&mut self, data_version: u64) {
+        self.computed_for = Some(data_version);
+    }
+
+    pub fn reset(&mut self) {
+        self.computed_for = None;
+    }
+
+    /// Shows the auto-refresh toggle and a manual refresh button, returning `true`
+    /// when the user asked for a refresh.
+    pub fn show_controls(&mut self, ui: &mut Ui, data_version: u64) -> bool {
+        let mut refresh = false;
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.auto_refresh, "Auto refresh");
+            refresh = ui.button("⟳ Refresh").clicked();
+            if self.has_result() && self.is_stale(data_version) {
+                ui.colored_label(Color32::YELLOW, "Out of date");
+            }
+        });
+        refresh
+    }
+}
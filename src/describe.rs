@@ -0,0 +1,88 @@
+use polars::prelude::*;
+
+const QUANTILES: [(&str, f64); 3] = [("25%", 0.25), ("50%", 0.5), ("75%", 0.75)];
+
+/// The smallest or largest value of a column as text, or `None` if it has none.
+pub fn min_max_text(column: &Column, max: bool) -> Option<String> {
+    let series = column.as_materialized_series();
+    let scalar = if max {
+        series.max_reduce()
+    } else {
+        series.min_reduce()
+    };
+    let scalar = scalar.ok()?;
+    let value = scalar.as_any_value();
+    (!value.is_null()).then(|| value.str_value().to_string())
+}
+
+/// Summarises every column of `df` except `skip`, one row per column: count, null count,
+/// mean, standard deviation, min, quartiles and max.
+///
+/// This is Polars' `describe` turned on its side, so wide tables stay readable. Mean,
+/// standard deviation and quartiles are only computed for numeric columns.
+pub fn describe(df: &DataFrame, skip: &str) -> PolarsResult<DataFrame> {
+    let columns: Vec<&Column> = df
+        .get_columns()
+        .iter()
+        .filter(|column| column.name().as_str() != skip)
+        .collect();
+    let numeric: Vec<&str> = columns
+        .iter()
+        .filter(|column| column.dtype().is_primitive_numeric())
+        .map(|column| column.name().as_str())
+        .collect();
+
+    let mut exprs = Vec::new();
+    for name in &numeric {
+        let value = col(*name).cast(DataType::Float64);
+        exprs.push(value.clone().mean().alias(format!("{}:mean", name)));
+        exprs.push(value.clone().std(1).alias(format!("{}:std", name)));
+        for (label, q) in QUANTILES {
+            exprs.push(
+                value
+                    .clone()
+                    .quantile(lit(q), QuantileMethod::Linear)
+                    .alias(format!("{}:{}", name, label)),
+            );
+        }
+    }
+    let stats = if exprs.is_empty() {
+        DataFrame::empty()
+    } else {
+        df.clone().lazy().select(exprs).collect()?
+    };
+    let stat = |name: &str, label: &str| -> Option<f64> {
+        stats
+            .column(&format!("{}:{}", name, label))
+            .ok()
+            .and_then(|column| column.get(0).ok())
+            .and_then(|value| value.extract::<f64>())
+    };
+
+    let names: Vec<&str> = columns.iter().map(|c| c.name().as_str()).collect();
+    let dtypes: Vec<String> = columns.iter().map(|c| c.dtype().to_string()).collect();
+    let counts: Vec<u64> = columns
+        .iter()
+        .map(|c| (c.len() - c.null_count()) as u64)
+        .collect();
+    let null_counts: Vec<u64> = columns.iter().map(|c| c.null_count() as u64).collect();
+    let float_stat =
+        |label: &str| -> Vec<Option<f64>> { names.iter().map(|name| stat(name, label)).collect() };
+    let mins: Vec<Option<String>> = columns.iter().map(|c| min_max_text(c, false)).collect();
+    let maxs: Vec<Option<String>> = columns.iter().map(|c| min_max_text(c, true)).collect();
+
+    let mut result = vec![
+        Column::new("column".into(), &names),
+        Column::new("type".into(), dtypes),
+        Column::new("count".into(), counts),
+        Column::new("null_count".into(), null_counts),
+        Column::new("mean".into(), float_stat("mean")),
+        Column::new("std".into(), float_stat("std")),
+        Column::new("min".into(), mins),
+    ];
+    for (label, _) in QUANTILES {
+        result.push(Column::new(label.into(), float_stat(label)));
+    }
+    result.push(Column::new("max".into(), maxs));
+    DataFrame::new(result)
+}
@@ -0,0 +1,105 @@
+//! Logging through `tracing`, printed to stderr at the level chosen with `--verbose` and
+//! kept in memory for the Diagnostics window.
+
+use std::collections::VecDeque;
+use std::io::{self, IsTerminal};
+use std::sync::Mutex;
+use tracing::Level;
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::filter::{EnvFilter, Targets};
+use tracing_subscriber::fmt::{self, MakeWriter, time::ChronoLocal};
+use tracing_subscriber::prelude::*;
+
+/// Log lines kept for the Diagnostics window.
+const MAX_LOG_LINES: usize = 500;
+
+const TIME_FORMAT: &str = "%H:%M:%S%.3f";
+
+static LOG: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// Collects one formatted event and adds it to [`LOG`] when dropped.
+#[derive(Default)]
+struct LogLine(Vec<u8>);
+
+impl io::Write for LogLine {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for LogLine {
+    fn drop(&mut self) {
+        let line = String::from_utf8_lossy(&self.0).trim_end().to_string();
+        if !line.is_empty()
+            && let Ok(mut log) = LOG.lock()
+        {
+            if log.len() == MAX_LOG_LINES {
+                log.pop_front();
+            }
+            log.push_back(line);
+        }
+    }
+}
+
+/// Writes events into the Diagnostics window's log.
+struct RecentLog;
+
+impl<'a> MakeWriter<'a> for RecentLog {
+    type Writer = LogLine;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        LogLine::default()
+    }
+}
+
+/// Installs the logger. Stderr gets warnings and errors by default, then info, debug and
+/// trace for one, two or three `--verbose` flags; `RUST_LOG` overrides that with its own
+/// directives. Tablr's own debug events are kept for the Diagnostics window whatever the
+/// level.
+pub fn init_logging(verbosity: u8) {
+    let level = match verbosity {
+        0 => Level::WARN,
+        1 => Level::INFO,
+        2 => Level::DEBUG,
+        _ => Level::TRACE,
+    };
+    let stderr = fmt::layer()
+        .with_writer(io::stderr)
+        .with_ansi(io::stderr().is_terminal())
+        .with_timer(ChronoLocal::new(TIME_FORMAT.to_string()))
+        .with_filter(
+            EnvFilter::builder()
+                .with_default_directive(LevelFilter::from_level(level).into())
+                .from_env_lossy(),
+        );
+    let recent = fmt::layer()
+        .with_writer(RecentLog)
+        .with_ansi(false)
+        .with_timer(ChronoLocal::new(TIME_FORMAT.to_string()))
+        .with_filter(Targets::new().with_target("tablr", Level::DEBUG));
+    // Fails only when a logger is already installed, which is then kept.
+    let _ = tracing_subscriber::registry()
+        .with(stderr)
+        .with(recent)
+        .try_init();
+}
+
+/// Tablr's recent log lines, oldest first.
+pub fn recent_log() -> Vec<String> {
+    LOG.lock()
+        .map(|log| log.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Memory held by this process, where the platform reports it (Linux only for now).
+pub fn resident_memory() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+    let kib: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kib * 1024)
+}
@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Documentation for a single column.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ColumnDoc {
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub unit: String,
+}
+
+/// A data dictionary sidecar mapping column names to descriptions and units.
+///
+/// Stored as YAML or JSON, e.g.
+///
+/// ```yaml
+/// columns:
+///   fct_amt_adj_2:
+///     description: Order amount after refunds
+///     unit: USD
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DataDictionary {
+    pub columns: BTreeMap<String, ColumnDoc>,
+}
+
+fn is_yaml(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("yaml") || ext.eq_ignore_ascii_case("yml"))
+}
+
+impl DataDictionary {
+    pub fn get(&self, column: &str) -> Option<&ColumnDoc> {
+        self.columns.get(column)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let contents = fs::read_to_string(path)?;
+        if is_yaml(path) {
+            Ok(serde_yaml::from_str(&contents)?)
+        } else {
+            Ok(serde_json::from_str(&contents)?)
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut dictionary = self.clone();
+        dictionary
+            .columns
+            .retain(|_, doc| *doc != ColumnDoc::default());
+        let contents = if is_yaml(path) {
+            serde_yaml::to_string(&dictionary)?
+        } else {
+            serde_json::to_string_pretty(&dictionary)?
+        };
+        fs::write(path, contents)?;
+        Ok(())
+    }
+
+    /// Looks for `<name>.dictionary.{yaml,yml,json}` next to a data file.
+    pub fn find_sidecar(data_file: &Path) -> Option<PathBuf> {
+        ["yaml", "yml", "json"]
+            .iter()
+            .map(|ext| data_file.with_extension(format!("dictionary.{}", ext)))
+            .find(|path| path.is_file())
+    }
+}
@@ -0,0 +1,203 @@
+use polars::prelude::*;
+
+/// Name of the column holding each row's [`DiffStatus`].
+pub const STATUS_COLUMN: &str = "Diff";
+
+const RIGHT_SUFFIX: &str = "__right";
+const CHANGED_SUFFIX: &str = "__changed";
+const IN_LEFT: &str = "__in_left";
+const IN_RIGHT: &str = "__in_right";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DiffStatus {
+    Added,
+    Removed,
+    Changed,
+    Unchanged,
+}
+
+impl DiffStatus {
+    pub const ALL: [DiffStatus; 4] = [
+        DiffStatus::Added,
+        DiffStatus::Removed,
+        DiffStatus::Changed,
+        DiffStatus::Unchanged,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            DiffStatus::Added => "Added",
+            DiffStatus::Removed => "Removed",
+            DiffStatus::Changed => "Changed",
+            DiffStatus::Unchanged => "Unchanged",
+        }
+    }
+
+    pub fn from_label(label: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|status| status.label() == label)
+    }
+}
+
+impl std::fmt::Display for DiffStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+/// Result of comparing two frames row by row on a set of key columns.
+pub struct FileDiff {
+    /// Status, key and compared columns, plus a hidden changed flag per compared column.
+    pub frame: DataFrame,
+    pub key_columns: Vec<String>,
+    pub compared_columns: Vec<String>,
+    pub only_left: Vec<String>,
+    pub only_right: Vec<String>,
+    pub counts: [usize; 4],
+}
+
+impl FileDiff {
+    /// Columns to show, in order: status, keys, then compared columns.
+    pub fn display_columns(&self) -> Vec<String> {
+        std::iter::once(STATUS_COLUMN.to_string())
+            .chain(self.key_columns.iter().cloned())
+            .chain(self.compared_columns.iter().cloned())
+            .collect()
+    }
+
+    pub fn count(&self, status: DiffStatus) -> usize {
+        let idx = DiffStatus::ALL.iter().position(|s| *s == status).unwrap();
+        self.counts[idx]
+    }
+
+    /// Rows of [`FileDiff::frame`] whose status is one of `statuses`.
+    pub fn rows_with_status(&self, statuses: &[DiffStatus]) -> PolarsResult<DataFrame> {
+        let predicate = statuses
+            .iter()
+            .map(|status| col(STATUS_COLUMN).eq(lit(status.label())))
+            .reduce(|a, b| a.or(b))
+            .unwrap_or(lit(false));
+        self.frame.clone().lazy().filter(predicate).collect()
+    }
+}
+
+/// Whether `column` differs between the two files in `row` of a frame taken from a [`FileDiff`].
+pub fn is_changed(df: &DataFrame, row: usize, column: &str) -> bool {
+    df.column(&format!("{}{}", column, CHANGED_SUFFIX))
+        .ok()
+        .and_then(|flags| flags.get(row).ok())
+        .is_some_and(|value| value == AnyValue::Boolean(true))
+}
+
+/// Full-joins `left` and `right` on `keys` and classifies every row as added (only in
+/// `right`), removed (only in `left`), changed or unchanged. Changed cells read `old → new`.
+pub fn diff(left: &DataFrame, right: &DataFrame, keys: &[String]) -> PolarsResult<FileDiff> {
+    if keys.is_empty() {
+        polars_bail!(InvalidOperation: "select at least one key column");
+    }
+    let left_names: Vec<String> = left
+        .get_column_names()
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let right_names: Vec<String> = right
+        .get_column_names()
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    for key in keys {
+        if !right_names.contains(key) {
+            polars_bail!(ColumnNotFound: "key column \"{}\" is missing from the second file", key);
+        }
+    }
+    let compared_columns: Vec<String> = left_names
+        .iter()
+        .filter(|name| !keys.contains(name) && right_names.contains(name))
+        .cloned()
+        .collect();
+    let only_left = left_names
+        .iter()
+        .filter(|name| !right_names.contains(name))
+        .cloned()
+        .collect();
+    let only_right = right_names
+        .iter()
+        .filter(|name| !left_names.contains(name))
+        .cloned()
+        .collect();
+
+    let key_exprs: Vec<Expr> = keys.iter().map(|k| col(k.as_str())).collect();
+    let mut left_select = key_exprs.clone();
+    let mut right_select = key_exprs.clone();
+    for name in &compared_columns {
+        left_select.push(col(name.as_str()).cast(DataType::String));
+        right_select.push(col(name.as_str()).cast(DataType::String));
+    }
+    left_select.push(lit(true).alias(IN_LEFT));
+    right_select.push(lit(true).alias(IN_RIGHT));
+
+    let joined = left.clone().lazy().select(left_select).join(
+        right.clone().lazy().select(right_select),
+        key_exprs.clone(),
+        key_exprs,
+        JoinArgs::new(JoinType::Full)
+            .with_coalesce(JoinCoalesce::CoalesceColumns)
+            .with_suffix(Some(RIGHT_SUFFIX.into())),
+    );
+
+    let mut changed_flags = Vec::new();
+    let mut values = Vec::new();
+    for name in &compared_columns {
+        let old = col(name.as_str());
+        let new = col(format!("{}{}", name, RIGHT_SUFFIX));
+        let changed = col(IN_LEFT)
+            .is_not_null()
+            .and(col(IN_RIGHT).is_not_null())
+            .and(old.clone().neq_missing(new.clone()));
+        values.push(
+            when(changed.clone())
+                .then(
+                    old.clone().fill_null(lit("null"))
+                        + lit(" → ")
+                        + new.clone().fill_null(lit("null")),
+                )
+                .otherwise(when(col(IN_LEFT).is_null()).then(new).otherwise(old))
+                .alias(name.as_str()),
+        );
+        changed_flags.push(changed.alias(format!("{}{}", name, CHANGED_SUFFIX)));
+    }
+    let any_changed = changed_flags
+        .iter()
+        .cloned()
+        .reduce(|a, b| a.or(b))
+        .unwrap_or(lit(false));
+    let status = when(col(IN_LEFT).is_null())
+        .then(lit(DiffStatus::Added.label()))
+        .when(col(IN_RIGHT).is_null())
+        .then(lit(DiffStatus::Removed.label()))
+        .when(any_changed)
+        .then(lit(DiffStatus::Changed.label()))
+        .otherwise(lit(DiffStatus::Unchanged.label()))
+        .alias(STATUS_COLUMN);
+
+    let mut select = vec![status];
+    select.extend(keys.iter().map(|k| col(k.as_str())));
+    select.extend(values);
+    select.extend(changed_flags);
+    let frame = joined.select(select).collect()?;
+
+    let mut counts = [0usize; 4];
+    for label in frame.column(STATUS_COLUMN)?.str()?.into_iter().flatten() {
+        if let Some(status) = DiffStatus::from_label(label) {
+            counts[DiffStatus::ALL.iter().position(|s| *s == status).unwrap()] += 1;
+        }
+    }
+
+    Ok(FileDiff {
+        frame,
+        key_columns: keys.to_vec(),
+        compared_columns,
+        only_left,
+        only_right,
+        counts,
+    })
+}
@@ -0,0 +1,71 @@
+use polars::prelude::*;
+use std::collections::BTreeSet;
+
+pub struct Duplicates {
+    /// Positions in the frame of rows whose keys occur more than once.
+    pub rows: BTreeSet<usize>,
+    /// Number of distinct key combinations that occur more than once.
+    pub groups: usize,
+}
+
+fn is_duplicate(keys: &[String]) -> Expr {
+    let keys: Vec<Expr> = keys.iter().map(|name| col(name.as_str())).collect();
+    len().over(keys).gt(lit(1))
+}
+
+/// Finds rows that share their `keys` values with another row. Nulls compare equal.
+pub fn find_duplicates(df: &DataFrame, keys: &[String]) -> PolarsResult<Duplicates> {
+    let mask = df
+        .clone()
+        .lazy()
+        .select([is_duplicate(keys).alias("duplicate")])
+        .collect()?;
+    let rows = mask
+        .column("duplicate")?
+        .bool()?
+        .into_iter()
+        .enumerate()
+        .filter(|(_, duplicate)| *duplicate == Some(true))
+        .map(|(row, _)| row)
+        .collect();
+    let groups = df
+        .clone()
+        .lazy()
+        .group_by(
+            keys.iter()
+                .map(|name| col(name.as_str()))
+                .collect::<Vec<_>>(),
+        )
+        .agg([len().alias("count")])
+        .filter(col("count").gt(lit(1)))
+        .collect()?
+        .height();
+    Ok(Duplicates { rows, groups })
+}
+
+/// Keeps only duplicated rows, sorted by `keys` so that each group's rows are adjacent.
+pub fn filter_duplicates(df: &DataFrame, keys: &[String]) -> PolarsResult<DataFrame> {
+    df.clone()
+        .lazy()
+        .filter(is_duplicate(keys))
+        .sort(
+            keys,
+            SortMultipleOptions::default().with_maintain_order(true),
+        )
+        .collect()
+}
+
+/// Drops rows whose `keys` repeat an earlier row (or a later one, with `keep_last`),
+/// leaving the remaining rows in their original order.
+pub fn remove_duplicates(
+    df: &DataFrame,
+    keys: &[String],
+    keep_last: bool,
+) -> PolarsResult<DataFrame> {
+    let keep = if keep_last {
+        UniqueKeepStrategy::Last
+    } else {
+        UniqueKeepStrategy::First
+    };
+    df.unique_stable(Some(keys), keep, None)
+}
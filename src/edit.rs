@@ -0,0 +1,118 @@
+//! Changing cells and adding or removing rows of the loaded data, for fixing a handful
+//! of bad records.
+
+use polars::prelude::*;
+use std::collections::HashSet;
+
+/// Parses `text` as a one-value series of type `dtype`, failing if it doesn't fit. Empty
+/// text is null, except in string columns.
+pub fn parse_value(text: &str, dtype: &DataType) -> PolarsResult<Series> {
+    if text.is_empty() && dtype != &DataType::String {
+        return Ok(Series::full_null("".into(), 1, dtype));
+    }
+    let invalid = || polars_err!(InvalidOperation: "\"{}\" is not a valid {}", text, dtype);
+    let value = Series::new("".into(), [text]);
+    match dtype {
+        DataType::Boolean => {
+            let flag = match text.trim().to_lowercase().as_str() {
+                "true" | "1" | "yes" | "✓" => true,
+                "false" | "0" | "no" | "✗" => false,
+                _ => return Err(invalid()),
+            };
+            Ok(Series::new("".into(), [flag]))
+        }
+        // Strings are cast to dates but not to datetimes, which need parsing.
+        DataType::Datetime(unit, time_zone) => DataFrame::new(vec![value.into_column()])?
+            .lazy()
+            .select([col("").str().to_datetime(
+                Some(*unit),
+                time_zone.clone(),
+                StrptimeOptions::default(),
+                lit("raise"),
+            )])
+            .collect()
+            .ok()
+            .and_then(|df| df.get_columns().first().cloned())
+            .filter(|column| column.null_count() == 0)
+            .map(|column| column.as_materialized_series().clone())
+            .ok_or_else(invalid),
+        _ => value.strict_cast(dtype).map_err(|_| invalid()),
+    }
+}
+
+/// Sets `column` to `text` in the row whose `row_index` value is `row_id`.
+pub fn set_cell(
+    df: &DataFrame,
+    row_index: &str,
+    row_id: IdxSize,
+    column: &str,
+    text: &str,
+) -> PolarsResult<DataFrame> {
+    let dtype = df.column(column)?.dtype().clone();
+    let value = parse_value(text, &dtype)?;
+    df.clone()
+        .lazy()
+        .with_column(
+            when(col(row_index).eq(lit(row_id)))
+                .then(lit(value).first())
+                .otherwise(col(column))
+                .cast(dtype)
+                .alias(column),
+        )
+        .collect()
+}
+
+/// The cell's value as text in the editor; null is empty.
+pub fn edit_text(value: &AnyValue) -> String {
+    match value {
+        AnyValue::Null => String::new(),
+        value => value.str_value().to_string(),
+    }
+}
+
+/// Inserts a row of nulls after the row whose `row_index` value is `after`, or at the
+/// end. The new row's index is one past the highest, so it never collides with a
+/// loaded row. Returns the data and the new row's index.
+pub fn insert_blank_row(
+    df: &DataFrame,
+    row_index: &str,
+    after: Option<IdxSize>,
+) -> PolarsResult<(DataFrame, IdxSize)> {
+    let ids = df.column(row_index)?.idx()?;
+    let id = ids.max().map_or(0, |max| max + 1);
+    let columns: Vec<Column> = df
+        .get_columns()
+        .iter()
+        .map(|column| {
+            if column.name() == row_index {
+                Column::new(row_index.into(), [id])
+            } else {
+                Column::full_null(column.name().clone(), 1, column.dtype())
+            }
+        })
+        .collect();
+    let blank = DataFrame::new(columns)?;
+    let position = after
+        .and_then(|after| ids.iter().position(|id| id == Some(after)))
+        .map_or(df.height(), |position| position + 1);
+    let mut result = df.slice(0, position);
+    result.vstack_mut(&blank)?;
+    result.vstack_mut(&df.slice(position as i64, df.height() - position))?;
+    result.align_chunks();
+    Ok((result, id))
+}
+
+/// Removes the rows whose `row_index` values are in `ids`, keeping the order of the rest.
+pub fn delete_rows(
+    df: &DataFrame,
+    row_index: &str,
+    ids: &HashSet<IdxSize>,
+) -> PolarsResult<DataFrame> {
+    let keep: BooleanChunked = df
+        .column(row_index)?
+        .idx()?
+        .iter()
+        .map(|id| id.is_none_or(|id| !ids.contains(&id)))
+        .collect();
+    df.filter(&keep)
+}
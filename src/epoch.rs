@@ -0,0 +1,125 @@
+use polars::prelude::*;
+
+/// Number of non-null values inspected per column when detecting epochs.
+const SAMPLE_SIZE: usize = 1000;
+
+/// Plausible range for epoch timestamps in seconds: 1990-01-01 to 2100-01-01.
+const MIN_SECONDS: i64 = 631_152_000;
+const MAX_SECONDS: i64 = 4_102_444_800;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EpochUnit {
+    Seconds,
+    Milliseconds,
+    Microseconds,
+    Nanoseconds,
+}
+
+impl EpochUnit {
+    pub const ALL: [EpochUnit; 4] = [
+        EpochUnit::Seconds,
+        EpochUnit::Milliseconds,
+        EpochUnit::Microseconds,
+        EpochUnit::Nanoseconds,
+    ];
+
+    fn per_second(self) -> i64 {
+        match self {
+            EpochUnit::Seconds => 1,
+            EpochUnit::Milliseconds => 1_000,
+            EpochUnit::Microseconds => 1_000_000,
+            EpochUnit::Nanoseconds => 1_000_000_000,
+        }
+    }
+
+    fn contains(self, value: i64) -> bool {
+        let per_second = self.per_second();
+        (MIN_SECONDS.saturating_mul(per_second)..MAX_SECONDS.saturating_mul(per_second))
+            .contains(&value)
+    }
+}
+
+impl std::fmt::Display for EpochUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EpochUnit::Seconds => write!(f, "seconds"),
+            EpochUnit::Milliseconds => write!(f, "milliseconds"),
+            EpochUnit::Microseconds => write!(f, "microseconds"),
+            EpochUnit::Nanoseconds => write!(f, "nanoseconds"),
+        }
+    }
+}
+
+/// Guesses the epoch unit of an integer column from the magnitude of its values.
+/// Returns `None` unless every sampled value falls in the same plausible date range.
+pub fn detect_unit(column: &Column) -> Option<EpochUnit> {
+    if !column.dtype().is_integer() {
+        return None;
+    }
+    let values = column.cast(&DataType::Int64).ok()?;
+    let mut sampled = values.i64().ok()?.into_iter().flatten().take(SAMPLE_SIZE);
+    let first = sampled.next()?;
+    let unit = EpochUnit::ALL
+        .into_iter()
+        .find(|unit| unit.contains(first))?;
+    sampled.all(|value| unit.contains(value)).then_some(unit)
+}
+
+/// Returns the integer columns of `df` that look like epoch timestamps, with their likely unit.
+pub fn detect_epoch_columns(df: &DataFrame, skip: &str) -> Vec<(String, EpochUnit)> {
+    df.get_columns()
+        .iter()
+        .filter(|column| column.name().as_str() != skip)
+        .filter_map(|column| Some((column.name().to_string(), detect_unit(column)?)))
+        .collect()
+}
+
+/// Returns a copy of `df` with the integer column `name` converted to a UTC `Datetime`,
+/// and how many values fell outside the plausible range for `unit` and became null.
+pub fn to_datetime(
+    df: &DataFrame,
+    name: &str,
+    unit: EpochUnit,
+) -> PolarsResult<(DataFrame, usize)> {
+    let values = df.column(name)?.cast(&DataType::Int64)?;
+    let mut out_of_range = 0;
+    let values: Int64Chunked = values
+        .i64()?
+        .into_iter()
+        .map(|value| {
+            let value = value?;
+            if unit.contains(value) {
+                Some(value)
+            } else {
+                out_of_range += 1;
+                None
+            }
+        })
+        .collect();
+    let values = values.with_name(name.into()).into_series();
+    let (values, time_unit) = match unit {
+        EpochUnit::Seconds => (values * 1000i64, TimeUnit::Milliseconds),
+        EpochUnit::Milliseconds => (values, TimeUnit::Milliseconds),
+        EpochUnit::Microseconds => (values, TimeUnit::Microseconds),
+        EpochUnit::Nanoseconds => (values, TimeUnit::Nanoseconds),
+    };
+    let converted = values.cast(&DataType::Datetime(time_unit, Some(TimeZone::UTC)))?;
+    let mut df = df.clone();
+    df.replace(name, converted)?;
+    Ok((df, out_of_range))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn out_of_range_values_become_null() {
+        let df = df!("ts" => [Some(1_700_000_000i64), Some(42), None, Some(i64::MAX)]).unwrap();
+        let (converted, out_of_range) = to_datetime(&df, "ts", EpochUnit::Seconds).unwrap();
+        assert_eq!(out_of_range, 2);
+        let ts = converted.column("ts").unwrap();
+        assert_eq!(ts.null_count(), 3);
+        assert_eq!(ts.get(0).unwrap().to_string(), "2023-11-14 22:13:20 UTC");
+    }
+}
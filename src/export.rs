@@ -0,0 +1,529 @@
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileFormat {
+    Csv,
+    NdJson,
+    Parquet,
+    Excel,
+}
+
+impl FileFormat {
+    pub const ALL: [FileFormat; 4] = [
+        FileFormat::Csv,
+        FileFormat::NdJson,
+        FileFormat::Parquet,
+        FileFormat::Excel,
+    ];
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            FileFormat::Csv => "csv",
+            FileFormat::NdJson => "ndjson",
+            FileFormat::Parquet => "parquet",
+            FileFormat::Excel => "xlsx",
+        }
+    }
+
+    /// The format named by a file extension or format name, e.g. on the command line.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "csv" => Some(FileFormat::Csv),
+            "ndjson" | "jsonl" | "json" => Some(FileFormat::NdJson),
+            "parquet" | "pq" => Some(FileFormat::Parquet),
+            "xlsx" | "excel" => Some(FileFormat::Excel),
+            _ => None,
+        }
+    }
+
+    /// Parquet and Excel files are archives indexed at the end, so rows cannot be added.
+    pub fn can_append(self) -> bool {
+        matches!(self, FileFormat::Csv | FileFormat::NdJson)
+    }
+}
+
+impl std::fmt::Display for FileFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileFormat::Csv => write!(f, "CSV"),
+            FileFormat::NdJson => write!(f, "NDJSON"),
+            FileFormat::Parquet => write!(f, "Parquet"),
+            FileFormat::Excel => write!(f, "Excel"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Compression {
+    Snappy,
+    Zstd,
+    Uncompressed,
+}
+
+impl Compression {
+    pub const ALL: [Compression; 3] = [
+        Compression::Snappy,
+        Compression::Zstd,
+        Compression::Uncompressed,
+    ];
+
+    fn parquet(self) -> ParquetCompression {
+        match self {
+            Compression::Snappy => ParquetCompression::Snappy,
+            Compression::Zstd => ParquetCompression::Zstd(None),
+            Compression::Uncompressed => ParquetCompression::Uncompressed,
+        }
+    }
+}
+
+impl std::fmt::Display for Compression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Compression::Snappy => write!(f, "Snappy"),
+            Compression::Zstd => write!(f, "Zstandard"),
+            Compression::Uncompressed => write!(f, "Uncompressed"),
+        }
+    }
+}
+
+/// Field separators offered for CSV export, with their labels.
+pub const SEPARATORS: [(u8, &str); 4] = [
+    (b',', "Comma"),
+    (b';', "Semicolon"),
+    (b'\t', "Tab"),
+    (b'|', "Pipe"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CsvEncoding {
+    Utf8,
+    Utf16Le,
+    Windows1252,
+}
+
+impl CsvEncoding {
+    pub const ALL: [CsvEncoding; 3] = [
+        CsvEncoding::Utf8,
+        CsvEncoding::Utf16Le,
+        CsvEncoding::Windows1252,
+    ];
+}
+
+impl std::fmt::Display for CsvEncoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CsvEncoding::Utf8 => write!(f, "UTF-8"),
+            CsvEncoding::Utf16Le => write!(f, "UTF-16 LE"),
+            CsvEncoding::Windows1252 => write!(f, "Windows-1252"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvOptions {
+    pub separator: u8,
+    pub decimal_comma: bool,
+    /// Writes a byte order mark; has no effect for single-byte encodings.
+    pub bom: bool,
+    pub encoding: CsvEncoding,
+    /// Starts the file with a row of column names.
+    pub header: bool,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            separator: b',',
+            decimal_comma: false,
+            bom: false,
+            encoding: CsvEncoding::Utf8,
+            header: true,
+        }
+    }
+}
+
+impl CsvOptions {
+    /// What Excel expects in locales that use a decimal comma, such as German or French.
+    pub fn excel_europe() -> Self {
+        Self {
+            separator: b';',
+            decimal_comma: true,
+            bom: true,
+            encoding: CsvEncoding::Utf8,
+            header: true,
+        }
+    }
+}
+
+/// Windows-1252 assigns printable characters to 0x80..=0x9F, where Latin-1 has controls.
+const WINDOWS_1252_HIGH: [(char, u8); 27] = [
+    ('€', 0x80),
+    ('‚', 0x82),
+    ('ƒ', 0x83),
+    ('„', 0x84),
+    ('…', 0x85),
+    ('†', 0x86),
+    ('‡', 0x87),
+    ('ˆ', 0x88),
+    ('‰', 0x89),
+    ('Š', 0x8A),
+    ('‹', 0x8B),
+    ('Œ', 0x8C),
+    ('Ž', 0x8E),
+    ('‘', 0x91),
+    ('’', 0x92),
+    ('“', 0x93),
+    ('”', 0x94),
+    ('•', 0x95),
+    ('–', 0x96),
+    ('—', 0x97),
+    ('˜', 0x98),
+    ('™', 0x99),
+    ('š', 0x9A),
+    ('›', 0x9B),
+    ('œ', 0x9C),
+    ('ž', 0x9E),
+    ('Ÿ', 0x9F),
+];
+
+/// Characters the code page cannot represent are replaced with `?`.
+fn encode_windows_1252(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| match c as u32 {
+            0..=0x7F | 0xA0..=0xFF => c as u8,
+            _ => WINDOWS_1252_HIGH
+                .iter()
+                .find(|(high, _)| *high == c)
+                .map_or(b'?', |(_, byte)| *byte),
+        })
+        .collect()
+}
+
+fn decode_windows_1252(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|&byte| match byte {
+            0x80..=0x9F => WINDOWS_1252_HIGH
+                .iter()
+                .find(|(_, high)| *high == byte)
+                .map_or('\u{fffd}', |(c, _)| *c),
+            _ => byte as char,
+        })
+        .collect()
+}
+
+/// Reads the first line of a file written in `encoding`, without its byte order mark.
+fn read_first_line(path: &Path, encoding: CsvEncoding) -> PolarsResult<String> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let line = match encoding {
+        CsvEncoding::Utf8 => {
+            let mut line = String::new();
+            reader.read_line(&mut line)?;
+            line
+        }
+        CsvEncoding::Windows1252 => {
+            let mut bytes = Vec::new();
+            reader.read_until(b'\n', &mut bytes)?;
+            decode_windows_1252(&bytes)
+        }
+        CsvEncoding::Utf16Le => {
+            let mut units = Vec::new();
+            let mut pair = [0; 2];
+            while reader.read_exact(&mut pair).is_ok() {
+                let unit = u16::from_le_bytes(pair);
+                units.push(unit);
+                if unit == u16::from(b'\n') {
+                    break;
+                }
+            }
+            String::from_utf16_lossy(&units)
+        }
+    };
+    Ok(line.trim_start_matches('\u{feff}').to_string())
+}
+
+/// Formats float columns as text with a comma before the decimals.
+fn with_decimal_comma(df: &DataFrame) -> PolarsResult<DataFrame> {
+    let floats: Vec<PlSmallStr> = df
+        .get_columns()
+        .iter()
+        .filter(|column| column.dtype().is_float())
+        .map(|column| column.name().clone())
+        .collect();
+    if floats.is_empty() {
+        return Ok(df.clone());
+    }
+    df.clone()
+        .lazy()
+        .with_columns(
+            floats
+                .iter()
+                .map(|name| {
+                    col(name.clone())
+                        .cast(DataType::String)
+                        .str()
+                        .replace(lit("."), lit(","), true)
+                })
+                .collect::<Vec<_>>(),
+        )
+        .collect()
+}
+
+/// Polars only writes UTF-8, so other encodings are transcoded from this text.
+fn csv_text(df: &mut DataFrame, separator: u8, include_header: bool) -> PolarsResult<String> {
+    let mut buffer = Vec::new();
+    CsvWriter::new(&mut buffer)
+        .include_header(include_header)
+        .with_separator(separator)
+        .finish(df)?;
+    String::from_utf8(buffer).map_err(|e| polars_err!(ComputeError: "{}", e))
+}
+
+fn write_csv_rows(
+    df: &DataFrame,
+    file: &mut File,
+    options: &CsvOptions,
+    include_header: bool,
+) -> PolarsResult<()> {
+    let mut df = if options.decimal_comma {
+        with_decimal_comma(df)?
+    } else {
+        df.clone()
+    };
+    match options.encoding {
+        CsvEncoding::Utf8 => CsvWriter::new(file)
+            .include_header(include_header)
+            .include_bom(options.bom && include_header)
+            .with_separator(options.separator)
+            .finish(&mut df),
+        CsvEncoding::Utf16Le => {
+            let text = csv_text(&mut df, options.separator, include_header)?;
+            if options.bom && include_header {
+                file.write_all(&[0xFF, 0xFE])?;
+            }
+            let bytes: Vec<u8> = text.encode_utf16().flat_map(u16::to_le_bytes).collect();
+            Ok(file.write_all(&bytes)?)
+        }
+        CsvEncoding::Windows1252 => {
+            let text = csv_text(&mut df, options.separator, include_header)?;
+            Ok(file.write_all(&encode_windows_1252(&text))?)
+        }
+    }
+}
+
+/// Writes the given frame to a CSV file, with a header row unless disabled in `options`.
+pub fn write_csv(df: &DataFrame, path: &Path, options: &CsvOptions) -> PolarsResult<()> {
+    write_csv_rows(df, &mut File::create(path)?, options, options.header)
+}
+
+/// Writes the given frame as newline-delimited JSON, one object per row.
+pub fn write_ndjson(df: &DataFrame, path: &Path) -> PolarsResult<()> {
+    JsonWriter::new(File::create(path)?)
+        .with_json_format(JsonFormat::JsonLines)
+        .finish(&mut df.clone())
+}
+
+pub fn write_parquet(df: &DataFrame, path: &Path, compression: Compression) -> PolarsResult<()> {
+    ParquetWriter::new(File::create(path)?)
+        .with_compression(compression.parquet())
+        .finish(&mut df.clone())?;
+    Ok(())
+}
+
+fn column_names(df: &DataFrame) -> Vec<String> {
+    df.get_column_names()
+        .iter()
+        .map(|name| name.to_string())
+        .collect()
+}
+
+fn check_columns(columns: &[String], existing: &[String], path: &Path) -> PolarsResult<()> {
+    if columns != existing {
+        polars_bail!(
+            SchemaMismatch: "{} has columns [{}] but the view has [{}]",
+            path.display(),
+            existing.join(", "),
+            columns.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Opens `path` for appending, starting a new line if the file does not end with one.
+fn open_for_append(path: &Path, newline: &[u8]) -> PolarsResult<File> {
+    let mut file = OpenOptions::new().read(true).append(true).open(path)?;
+    let length = file.metadata()?.len();
+    if length >= newline.len() as u64 {
+        let mut last = vec![0; newline.len()];
+        file.seek(SeekFrom::End(-(newline.len() as i64)))?;
+        file.read_exact(&mut last)?;
+        if last != newline {
+            file.write_all(newline)?;
+        }
+    }
+    Ok(file)
+}
+
+/// Appends the rows of `df` to an existing CSV file written with the same options.
+///
+/// The file's header must list the same columns in the same order; without headers,
+/// only the number of columns is compared.
+pub fn append_csv(df: &DataFrame, path: &Path, options: &CsvOptions) -> PolarsResult<()> {
+    // Polars reads only UTF-8, so the first line is decoded first and parsed on its own.
+    let first_line = read_first_line(path, options.encoding)?;
+    let header = CsvReadOptions::default()
+        .with_has_header(options.header)
+        .with_n_rows(Some(if options.header { 0 } else { 1 }))
+        .with_parse_options(CsvParseOptions::default().with_separator(options.separator))
+        .into_reader_with_file_handle(std::io::Cursor::new(first_line.into_bytes()))
+        .finish()?;
+    if options.header {
+        check_columns(&column_names(df), &column_names(&header), path)?;
+    } else if header.width() != df.width() {
+        polars_bail!(
+            SchemaMismatch: "{} has {} columns but the view has {}",
+            path.display(),
+            header.width(),
+            df.width()
+        );
+    }
+    let newline: &[u8] = match options.encoding {
+        CsvEncoding::Utf16Le => &[b'\n', 0],
+        _ => b"\n",
+    };
+    write_csv_rows(df, &mut open_for_append(path, newline)?, options, false)
+}
+
+/// Appends the rows of `df` to an existing NDJSON file whose first record has the same keys.
+pub fn append_ndjson(df: &DataFrame, path: &Path) -> PolarsResult<()> {
+    let mut first_line = String::new();
+    BufReader::new(File::open(path)?).read_line(&mut first_line)?;
+    if !first_line.trim().is_empty() {
+        let record: serde_json::Map<String, serde_json::Value> = serde_json::from_str(
+            first_line.trim(),
+        )
+        .map_err(|e| polars_err!(ComputeError: "{} is not NDJSON: {}", path.display(), e))?;
+        // Key order carries no meaning in JSON objects, so only the sets are compared.
+        let mut columns = column_names(df);
+        columns.sort();
+        let mut existing: Vec<String> = record.keys().cloned().collect();
+        existing.sort();
+        check_columns(&columns, &existing, path)?;
+    }
+    JsonWriter::new(open_for_append(path, b"\n")?)
+        .with_json_format(JsonFormat::JsonLines)
+        .finish(&mut df.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("tablr-export-{}-{}", std::process::id(), name))
+    }
+
+    fn written(df: &DataFrame, name: &str, options: &CsvOptions) -> Vec<u8> {
+        let path = temp_path(name);
+        write_csv(df, &path, options).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn windows_1252_maps_high_characters_and_replaces_the_rest() {
+        assert_eq!(
+            encode_windows_1252("a€é™—日"),
+            vec![b'a', 0x80, 0xE9, 0x99, 0x97, b'?']
+        );
+    }
+
+    #[test]
+    fn utf16_output_has_a_bom_and_little_endian_units() {
+        let df = df!("name" => ["é"]).unwrap();
+        let options = CsvOptions {
+            bom: true,
+            encoding: CsvEncoding::Utf16Le,
+            ..CsvOptions::default()
+        };
+        let bytes = written(&df, "utf16.csv", &options);
+        assert_eq!(&bytes[..2], &[0xFF, 0xFE]);
+        let units: Vec<u16> = bytes[2..]
+            .chunks(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .collect();
+        assert_eq!(String::from_utf16(&units).unwrap(), "name\né\n");
+    }
+
+    #[test]
+    fn windows_1252_output_has_no_bom() {
+        let df = df!("price" => ["5€"]).unwrap();
+        let options = CsvOptions {
+            bom: true,
+            encoding: CsvEncoding::Windows1252,
+            ..CsvOptions::default()
+        };
+        assert_eq!(written(&df, "cp1252.csv", &options), b"price\n5\x80\n");
+    }
+
+    #[test]
+    fn excel_europe_uses_semicolons_and_decimal_commas() {
+        let df = df!("a" => [1.5], "b" => ["x"]).unwrap();
+        let bytes = written(&df, "europe.csv", &CsvOptions::excel_europe());
+        assert_eq!(bytes, "\u{feff}a;b\n1,5;x\n".as_bytes());
+    }
+
+    fn appended(options: &CsvOptions, name: &str) -> Vec<u8> {
+        let path = temp_path(name);
+        let first = df!("prix €" => ["1"], "b" => ["x"]).unwrap();
+        let second = df!("prix €" => ["2"], "b" => ["y"]).unwrap();
+        write_csv(&first, &path, options).unwrap();
+        let result = append_csv(&second, &path, options);
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        result.unwrap();
+        bytes
+    }
+
+    #[test]
+    fn appends_to_a_utf16_file_with_a_bom() {
+        let options = CsvOptions {
+            bom: true,
+            encoding: CsvEncoding::Utf16Le,
+            ..CsvOptions::default()
+        };
+        let bytes = appended(&options, "append-utf16.csv");
+        let units: Vec<u16> = bytes[2..]
+            .chunks(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .collect();
+        assert_eq!(String::from_utf16(&units).unwrap(), "prix €,b\n1,x\n2,y\n");
+    }
+
+    #[test]
+    fn appends_to_a_windows_1252_file() {
+        let options = CsvOptions {
+            encoding: CsvEncoding::Windows1252,
+            ..CsvOptions::default()
+        };
+        assert_eq!(
+            appended(&options, "append-cp1252.csv"),
+            b"prix \x80,b\n1,x\n2,y\n"
+        );
+    }
+
+    #[test]
+    fn refuses_to_append_other_columns() {
+        let path = temp_path("append-mismatch.csv");
+        write_csv(&df!("a" => [1]).unwrap(), &path, &CsvOptions::default()).unwrap();
+        let result = append_csv(&df!("b" => [2]).unwrap(), &path, &CsvOptions::default());
+        std::fs::remove_file(&path).unwrap();
+        assert!(result.is_err());
+    }
+}
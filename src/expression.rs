@@ -0,0 +1,435 @@
+//! Parser for the Polars-style expressions used to define computed columns, such as
+//! `col("bytes") / 1048576` or `col("name").str().to_uppercase()`.
+
+use polars::prelude::*;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Int(i64),
+    Float(f64),
+    Str(String),
+    Op(&'static str),
+}
+
+const OPERATORS: [&str; 18] = [
+    "==", "!=", "<=", ">=", "&&", "||", "<", ">", "+", "-", "*", "/", "%", "!", "&", "|", "(", ")",
+];
+
+fn tokenize(text: &str) -> PolarsResult<Vec<Token>> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '_') {
+                i += 1;
+            }
+            let is_float = i + 1 < chars.len() && chars[i] == '.' && chars[i + 1].is_ascii_digit();
+            if is_float {
+                i += 1;
+                while i < chars.len() && chars[i].is_ascii_digit() {
+                    i += 1;
+                }
+            }
+            let number: String = chars[start..i].iter().filter(|c| **c != '_').collect();
+            tokens.push(if is_float {
+                Token::Float(number.parse().map_err(to_compute_err)?)
+            } else {
+                Token::Int(number.parse().map_err(to_compute_err)?)
+            });
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push(Token::Ident(chars[start..i].iter().collect()));
+        } else if c == '"' {
+            let mut value = String::new();
+            i += 1;
+            loop {
+                match chars.get(i) {
+                    None => polars_bail!(ComputeError: "unterminated string"),
+                    Some('"') => break,
+                    Some('\\') => {
+                        value.push(*chars.get(i + 1).unwrap_or(&'\\'));
+                        i += 2;
+                    }
+                    Some(c) => {
+                        value.push(*c);
+                        i += 1;
+                    }
+                }
+            }
+            i += 1;
+            tokens.push(Token::Str(value));
+        } else if c == '.' || c == ',' {
+            tokens.push(Token::Op(if c == '.' { "." } else { "," }));
+            i += 1;
+        } else {
+            let rest: String = chars[i..chars.len().min(i + 2)].iter().collect();
+            let Some(op) = OPERATORS.iter().find(|op| rest.starts_with(**op)) else {
+                polars_bail!(ComputeError: "unexpected character '{}'", c);
+            };
+            tokens.push(Token::Op(op));
+            i += op.len();
+        }
+    }
+    Ok(tokens)
+}
+
+fn to_compute_err(e: impl std::fmt::Display) -> PolarsError {
+    polars_err!(ComputeError: "{}", e)
+}
+
+fn parse_dtype(name: &str) -> PolarsResult<DataType> {
+    Ok(match name.to_lowercase().as_str() {
+        "bool" | "boolean" => DataType::Boolean,
+        "i8" | "int8" => DataType::Int8,
+        "i16" | "int16" => DataType::Int16,
+        "i32" | "int32" => DataType::Int32,
+        "i64" | "int64" => DataType::Int64,
+        "u8" | "uint8" => DataType::UInt8,
+        "u16" | "uint16" => DataType::UInt16,
+        "u32" | "uint32" => DataType::UInt32,
+        "u64" | "uint64" => DataType::UInt64,
+        "f32" | "float32" => DataType::Float32,
+        "f64" | "float64" => DataType::Float64,
+        "str" | "string" | "utf8" => DataType::String,
+        "date" => DataType::Date,
+        _ => polars_bail!(ComputeError: "unknown type \"{}\"", name),
+    })
+}
+
+/// Method namespace the next call is looked up in, after `.str()` or `.dt()`.
+#[derive(Clone, Copy, PartialEq)]
+enum Namespace {
+    Expr,
+    Str,
+    Dt,
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat(&mut self, op: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Op(next)) if *next == op) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, op: &str) -> PolarsResult<()> {
+        if !self.eat(op) {
+            polars_bail!(ComputeError: "expected '{}'", op);
+        }
+        Ok(())
+    }
+
+    fn or(&mut self) -> PolarsResult<Expr> {
+        let mut expr = self.and()?;
+        while self.eat("|") || self.eat("||") {
+            expr = expr.or(self.and()?);
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> PolarsResult<Expr> {
+        let mut expr = self.comparison()?;
+        while self.eat("&") || self.eat("&&") {
+            expr = expr.and(self.comparison()?);
+        }
+        Ok(expr)
+    }
+
+    fn comparison(&mut self) -> PolarsResult<Expr> {
+        let left = self.additive()?;
+        for op in ["==", "!=", "<=", ">=", "<", ">"] {
+            if self.eat(op) {
+                let right = self.additive()?;
+                return Ok(match op {
+                    "==" => left.eq(right),
+                    "!=" => left.neq(right),
+                    "<=" => left.lt_eq(right),
+                    ">=" => left.gt_eq(right),
+                    "<" => left.lt(right),
+                    _ => left.gt(right),
+                });
+            }
+        }
+        Ok(left)
+    }
+
+    fn additive(&mut self) -> PolarsResult<Expr> {
+        let mut expr = self.multiplicative()?;
+        loop {
+            if self.eat("+") {
+                expr = expr + self.multiplicative()?;
+            } else if self.eat("-") {
+                expr = expr - self.multiplicative()?;
+            } else {
+                return Ok(expr);
+            }
+        }
+    }
+
+    fn multiplicative(&mut self) -> PolarsResult<Expr> {
+        let mut expr = self.unary()?;
+        loop {
+            if self.eat("*") {
+                expr = expr * self.unary()?;
+            } else if self.eat("/") {
+                // True division, so integer columns divide into fractions like in Python.
+                expr = binary_expr(expr, Operator::TrueDivide, self.unary()?);
+            } else if self.eat("%") {
+                expr = expr % self.unary()?;
+            } else {
+                return Ok(expr);
+            }
+        }
+    }
+
+    fn unary(&mut self) -> PolarsResult<Expr> {
+        if self.eat("-") {
+            Ok(lit(0) - self.unary()?)
+        } else if self.eat("!") {
+            Ok(self.unary()?.not())
+        } else {
+            self.postfix()
+        }
+    }
+
+    fn arguments(&mut self) -> PolarsResult<Vec<Token>> {
+        self.expect("(")?;
+        let mut args = Vec::new();
+        while !self.eat(")") {
+            if !args.is_empty() {
+                self.expect(",")?;
+            }
+            let negative = self.eat("-");
+            match self.next() {
+                Some(Token::Int(value)) if negative => args.push(Token::Int(-value)),
+                Some(Token::Float(value)) if negative => args.push(Token::Float(-value)),
+                Some(token @ (Token::Int(_) | Token::Float(_) | Token::Str(_))) if !negative => {
+                    args.push(token)
+                }
+                _ => polars_bail!(ComputeError: "method arguments must be literals"),
+            }
+        }
+        Ok(args)
+    }
+
+    fn postfix(&mut self) -> PolarsResult<Expr> {
+        let mut expr = self.primary()?;
+        let mut namespace = Namespace::Expr;
+        while self.eat(".") {
+            let Some(Token::Ident(method)) = self.next() else {
+                polars_bail!(ComputeError: "expected a method name after '.'");
+            };
+            let args = self.arguments()?;
+            let int_arg = |i: usize| match args.get(i) {
+                Some(Token::Int(value)) => Ok(*value),
+                _ => polars_bail!(ComputeError: "{}() expects an integer argument", method),
+            };
+            let str_arg = |i: usize| match args.get(i) {
+                Some(Token::Str(value)) => Ok(value.clone()),
+                _ => polars_bail!(ComputeError: "{}() expects a string argument", method),
+            };
+            let literal_arg = |i: usize| match args.get(i) {
+                Some(Token::Int(value)) => Ok(lit(*value)),
+                Some(Token::Float(value)) => Ok(lit(*value)),
+                Some(Token::Str(value)) => Ok(lit(value.clone())),
+                _ => polars_bail!(ComputeError: "{}() expects an argument", method),
+            };
+            expr = match (namespace, method.as_str()) {
+                (Namespace::Expr, "str") => {
+                    namespace = Namespace::Str;
+                    continue;
+                }
+                (Namespace::Expr, "dt") => {
+                    namespace = Namespace::Dt;
+                    continue;
+                }
+                (Namespace::Expr, "abs") => expr.abs(),
+                (Namespace::Expr, "round") => {
+                    expr.round(int_arg(0).unwrap_or(0) as u32, RoundMode::HalfAwayFromZero)
+                }
+                (Namespace::Expr, "floor") => expr.floor(),
+                (Namespace::Expr, "ceil") => expr.ceil(),
+                (Namespace::Expr, "is_null") => expr.is_null(),
+                (Namespace::Expr, "is_not_null") => expr.is_not_null(),
+                (Namespace::Expr, "fill_null") => expr.fill_null(literal_arg(0)?),
+                (Namespace::Expr, "cast") => expr.cast(parse_dtype(&str_arg(0)?)?),
+                (Namespace::Str, "to_uppercase") => expr.str().to_uppercase(),
+                (Namespace::Str, "to_lowercase") => expr.str().to_lowercase(),
+                (Namespace::Str, "len_chars") => expr.str().len_chars(),
+                (Namespace::Str, "contains") => expr.str().contains_literal(lit(str_arg(0)?)),
+                (Namespace::Str, "starts_with") => expr.str().starts_with(lit(str_arg(0)?)),
+                (Namespace::Str, "ends_with") => expr.str().ends_with(lit(str_arg(0)?)),
+                (Namespace::Str, "slice") => expr.str().slice(
+                    lit(int_arg(0)?),
+                    lit(int_arg(1).map_or(u64::MAX, |n| n as u64)),
+                ),
+                (Namespace::Dt, "year") => expr.dt().year(),
+                (Namespace::Dt, "month") => expr.dt().month(),
+                (Namespace::Dt, "day") => expr.dt().day(),
+                (Namespace::Dt, "hour") => expr.dt().hour(),
+                (Namespace::Dt, "weekday") => expr.dt().weekday(),
+                _ => polars_bail!(ComputeError: "unknown method {}()", method),
+            };
+            namespace = Namespace::Expr;
+        }
+        if namespace != Namespace::Expr {
+            polars_bail!(ComputeError: "expected a method after .str() or .dt()");
+        }
+        Ok(expr)
+    }
+
+    fn primary(&mut self) -> PolarsResult<Expr> {
+        match self.next() {
+            Some(Token::Int(value)) => Ok(lit(value)),
+            Some(Token::Float(value)) => Ok(lit(value)),
+            Some(Token::Str(value)) => Ok(lit(value)),
+            Some(Token::Op("(")) => {
+                let expr = self.or()?;
+                self.expect(")")?;
+                Ok(expr)
+            }
+            Some(Token::Ident(name)) => match name.as_str() {
+                "true" => Ok(lit(true)),
+                "false" => Ok(lit(false)),
+                "null" => Ok(lit(Null {})),
+                "col" => match self.arguments()?.as_slice() {
+                    [Token::Str(column)] => Ok(col(column.as_str())),
+                    _ => polars_bail!(ComputeError: "col() expects a column name in quotes"),
+                },
+                "lit" => {
+                    self.expect("(")?;
+                    let expr = self.or()?;
+                    self.expect(")")?;
+                    Ok(expr)
+                }
+                _ => polars_bail!(ComputeError: "unknown name {}; use col(\"{}\")", name, name),
+            },
+            Some(token) => polars_bail!(ComputeError: "unexpected {:?}", token),
+            None => polars_bail!(ComputeError: "unexpected end of expression"),
+        }
+    }
+}
+
+/// Parses an expression like `col("price") * 1.2` into a Polars [`Expr`].
+pub fn parse(text: &str) -> PolarsResult<Expr> {
+    let mut parser = Parser {
+        tokens: tokenize(text)?,
+        pos: 0,
+    };
+    let expr = parser.or()?;
+    if let Some(token) = parser.peek() {
+        polars_bail!(ComputeError: "unexpected {:?} after the expression", token);
+    }
+    Ok(expr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluate(text: &str) -> Column {
+        let df = df!(
+            "a" => [1i64, 2, 3],
+            "b" => [4i64, 5, 6],
+            "name" => ["Ann", "bob", "Cy"],
+        )
+        .unwrap();
+        let result = df
+            .lazy()
+            .select([parse(text).unwrap().alias("result")])
+            .collect()
+            .unwrap();
+        result.column("result").unwrap().clone()
+    }
+
+    fn error(text: &str) -> String {
+        parse(text).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn tokenizes_numbers_strings_and_two_character_operators() {
+        assert_eq!(
+            tokenize(r#"1_000 >= 2.5 "a \"b\"""#).unwrap(),
+            vec![
+                Token::Int(1000),
+                Token::Op(">="),
+                Token::Float(2.5),
+                Token::Str("a \"b\"".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn multiplication_binds_tighter_than_addition() {
+        let result = evaluate(r#"col("a") + col("b") * 2"#);
+        assert_eq!(
+            result.i64().unwrap().to_vec(),
+            [Some(9), Some(12), Some(15)]
+        );
+    }
+
+    #[test]
+    fn division_is_true_division() {
+        let result = evaluate(r#"col("a") / 2"#);
+        assert_eq!(
+            result.f64().unwrap().to_vec(),
+            [Some(0.5), Some(1.0), Some(1.5)]
+        );
+    }
+
+    #[test]
+    fn comparisons_combine_with_and_or() {
+        let result = evaluate(r#"col("a") > 1 && col("b") != 6 || -col("a") == -3"#);
+        let values: Vec<Option<bool>> = result.bool().unwrap().into_iter().collect();
+        assert_eq!(values, [Some(false), Some(true), Some(true)]);
+    }
+
+    #[test]
+    fn string_methods_follow_the_str_namespace() {
+        let result = evaluate(r#"col("name").str().to_uppercase().str().slice(0, 2)"#);
+        let values: Vec<Option<&str>> = result.str().unwrap().into_iter().collect();
+        assert_eq!(values, [Some("AN"), Some("BO"), Some("CY")]);
+    }
+
+    #[test]
+    fn casts_to_named_types() {
+        let result = evaluate(r#"col("a").cast("f32")"#);
+        assert_eq!(result.dtype(), &DataType::Float32);
+    }
+
+    #[test]
+    fn reports_malformed_expressions() {
+        assert!(error(r#"col("a") col("b")"#).contains("after the expression"));
+        assert!(error("price * 2").contains(r#"use col("price")"#));
+        assert!(error(r#"col("a"#).contains("unterminated string"));
+        assert!(error(r#"col("name").str()"#).contains("expected a method"));
+        assert!(error(r#"col("a").round(col("b"))"#).contains("must be literals"));
+        assert!(error(r#"col("a").cast("decimal")"#).contains("unknown type"));
+    }
+}
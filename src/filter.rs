@@ -0,0 +1,65 @@
+use eframe::egui::{ComboBox, Ui};
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum FilterType {
+    #[default]
+    Equals,
+    Contains,
+}
+
+impl FilterType {
+    pub const ALL: [FilterType; 2] = [FilterType::Equals, FilterType::Contains];
+}
+
+impl std::fmt::Display for FilterType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FilterType::Equals => write!(f, "Equals"),
+            FilterType::Contains => write!(f, "Contains"),
+        }
+    }
+}
+
+/// Builds the predicate for filtering `col_name` by `text`, comparing values as strings.
+pub fn filter_expr(col_name: &str, filter_type: FilterType, text: &str) -> Expr {
+    match filter_type {
+        FilterType::Equals => col(col_name).cast(DataType::String).eq(lit(text)),
+        FilterType::Contains => col(col_name)
+            .cast(DataType::String)
+            .str()
+            .contains(lit(text), false),
+    }
+}
+
+/// Filters any frame (raw data or a derived result table); an empty `text` keeps every row.
+pub fn filter_frame(
+    df: &DataFrame,
+    col_name: &str,
+    filter_type: FilterType,
+    text: &str,
+) -> PolarsResult<DataFrame> {
+    if text.is_empty() {
+        return Ok(df.clone());
+    }
+    df.clone()
+        .lazy()
+        .filter(filter_expr(col_name, filter_type, text))
+        .collect()
+}
+
+/// Shows a filter type picker and returns `true` when the selection changed.
+pub fn filter_type_combo(ui: &mut Ui, id_salt: &str, filter_type: &mut FilterType) -> bool {
+    let mut changed = false;
+    ComboBox::from_id_salt(id_salt)
+        .selected_text(filter_type.to_string())
+        .show_ui(ui, |ui| {
+            for option in FilterType::ALL {
+                changed |= ui
+                    .selectable_value(filter_type, option, option.to_string())
+                    .clicked();
+            }
+        });
+    changed
+}
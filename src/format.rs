@@ -0,0 +1,217 @@
+//! Human-friendly rendering of cell values. Only the display changes; sorting,
+//! filtering and exports keep working on the raw values.
+
+use chrono::{DateTime, NaiveDate};
+use polars::prelude::*;
+use std::fmt::Write;
+use web_time::{SystemTime, UNIX_EPOCH};
+
+/// Inserts thousands separators into the integer part of a formatted number.
+fn group_thousands(number: &str) -> String {
+    let (sign, rest) = match number.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", number),
+    };
+    let (int_part, frac_part) = match rest.split_once('.') {
+        Some((int_part, frac_part)) => (int_part, Some(frac_part)),
+        None => (rest, None),
+    };
+    let mut grouped = String::new();
+    for (i, digit) in int_part.chars().enumerate() {
+        if i > 0 && (int_part.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    match frac_part {
+        Some(frac) => format!("{}{}.{}", sign, grouped, frac),
+        None => format!("{}{}", sign, grouped),
+    }
+}
+
+/// Formats a byte count with binary prefixes, e.g. `3.4 MiB`.
+pub fn format_bytes(bytes: f64) -> String {
+    const UNITS: [&str; 7] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+    let mut value = bytes;
+    let mut unit = 0;
+    while value.abs() >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", value)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+/// Formats a duration given in seconds using the largest fitting unit, e.g. `1.2 s`.
+fn format_seconds(seconds: f64) -> String {
+    let abs = seconds.abs();
+    if abs == 0.0 {
+        "0 s".to_string()
+    } else if abs < 1e-6 {
+        format!("{:.0} ns", seconds * 1e9)
+    } else if abs < 1e-3 {
+        format!("{:.1} µs", seconds * 1e6)
+    } else if abs < 1.0 {
+        format!("{:.1} ms", seconds * 1e3)
+    } else if abs < 60.0 {
+        format!("{:.1} s", seconds)
+    } else if abs < 3600.0 {
+        format!("{:.1} min", seconds / 60.0)
+    } else if abs < 86400.0 {
+        format!("{:.1} h", seconds / 3600.0)
+    } else {
+        format!("{:.1} d", seconds / 86400.0)
+    }
+}
+
+fn format_currency(amount: f64, symbol: &str, prefix: bool) -> String {
+    let number = group_thousands(&format!("{:.2}", amount.abs()));
+    let sign = if amount < 0.0 { "-" } else { "" };
+    if prefix {
+        format!("{}{}{}", sign, symbol, number)
+    } else {
+        format!("{}{} {}", sign, number, symbol)
+    }
+}
+
+/// Renders a numeric value according to a data dictionary unit such as `ms`, `bytes`
+/// or `USD`. Unknown units are appended to the value; non-numeric values are left alone.
+pub fn format_with_unit(value: &AnyValue, unit: &str) -> Option<String> {
+    if value.is_null() || !value.dtype().is_primitive_numeric() {
+        return None;
+    }
+    let number = value.extract::<f64>()?;
+    let formatted = match unit.trim().to_ascii_lowercase().as_str() {
+        "ns" | "nanoseconds" => format_seconds(number / 1e9),
+        "us" | "µs" | "microseconds" => format_seconds(number / 1e6),
+        "ms" | "milliseconds" => format_seconds(number / 1e3),
+        "s" | "sec" | "seconds" => format_seconds(number),
+        "min" | "minutes" => format_seconds(number * 60.0),
+        "h" | "hours" => format_seconds(number * 3600.0),
+        "b" | "byte" | "bytes" => format_bytes(number),
+        "kib" => format_bytes(number * 1024.0),
+        "mib" => format_bytes(number * 1024.0 * 1024.0),
+        "gib" => format_bytes(number * 1024.0 * 1024.0 * 1024.0),
+        "usd" | "$" => format_currency(number, "$", true),
+        "eur" | "€" => format_currency(number, "€", true),
+        "gbp" | "£" => format_currency(number, "£", true),
+        "%" | "percent" => format!("{}%", number),
+        _ => format!("{} {}", value, unit.trim()),
+    };
+    Some(formatted)
+}
+
+/// Seconds since the Unix epoch for date and datetime values.
+fn epoch_seconds(value: &AnyValue) -> Option<f64> {
+    let (v, unit) = match value {
+        AnyValue::Datetime(v, unit, _) | AnyValue::DatetimeOwned(v, unit, _) => (*v, *unit),
+        AnyValue::Date(days) => return Some(*days as f64 * 86400.0),
+        _ => return None,
+    };
+    let per_second = match unit {
+        TimeUnit::Milliseconds => 1e3,
+        TimeUnit::Microseconds => 1e6,
+        TimeUnit::Nanoseconds => 1e9,
+    };
+    Some(v as f64 / per_second)
+}
+
+/// Describes an offset from now in the largest whole unit, e.g. `3 hours ago` or `in 2 days`.
+fn format_relative(seconds_ago: f64) -> String {
+    const UNITS: [(&str, f64); 6] = [
+        ("year", 365.0 * 86400.0),
+        ("month", 30.0 * 86400.0),
+        ("day", 86400.0),
+        ("hour", 3600.0),
+        ("minute", 60.0),
+        ("second", 1.0),
+    ];
+    let abs = seconds_ago.abs();
+    if abs < 1.0 {
+        return "just now".to_string();
+    }
+    let (name, size) = UNITS.into_iter().find(|(_, size)| abs >= *size).unwrap();
+    let count = (abs / size).floor() as u64;
+    let plural = if count == 1 { "" } else { "s" };
+    if seconds_ago >= 0.0 {
+        format!("{} {}{} ago", count, name, plural)
+    } else {
+        format!("in {} {}{}", count, name, plural)
+    }
+}
+
+/// Renders dates and datetimes without a time zone with chrono `strftime` patterns, e.g.
+/// `%d.%m.%Y`. `None` for other values, empty patterns and patterns chrono rejects.
+pub fn format_temporal(
+    value: &AnyValue,
+    date_format: &str,
+    datetime_format: &str,
+) -> Option<String> {
+    let mut text = String::new();
+    match value {
+        AnyValue::Date(days) if !date_format.is_empty() => {
+            let date = NaiveDate::from_num_days_from_ce_opt(days.checked_add(719_163)?)?;
+            write!(text, "{}", date.format(date_format)).ok()?;
+        }
+        AnyValue::Datetime(v, unit, None) | AnyValue::DatetimeOwned(v, unit, None)
+            if !datetime_format.is_empty() =>
+        {
+            let datetime = match unit {
+                TimeUnit::Milliseconds => DateTime::from_timestamp_millis(*v)?,
+                TimeUnit::Microseconds => DateTime::from_timestamp_micros(*v)?,
+                TimeUnit::Nanoseconds => DateTime::from_timestamp_nanos(*v),
+            };
+            write!(text, "{}", datetime.naive_utc().format(datetime_format)).ok()?;
+        }
+        _ => return None,
+    }
+    Some(text)
+}
+
+/// Per-column rendering chosen from the header menu, independent of any data dictionary.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum DisplayMode {
+    #[default]
+    Default,
+    ByteSize,
+    RelativeTime,
+}
+
+impl DisplayMode {
+    pub const ALL: [DisplayMode; 3] = [
+        DisplayMode::Default,
+        DisplayMode::ByteSize,
+        DisplayMode::RelativeTime,
+    ];
+
+    /// Renders `value` in this mode, or `None` when the mode does not apply to it.
+    pub fn format(self, value: &AnyValue) -> Option<String> {
+        match self {
+            DisplayMode::Default => None,
+            DisplayMode::ByteSize if value.dtype().is_primitive_numeric() => {
+                value.extract::<f64>().map(format_bytes)
+            }
+            DisplayMode::ByteSize => None,
+            DisplayMode::RelativeTime => {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .ok()?
+                    .as_secs_f64();
+                epoch_seconds(value).map(|seconds| format_relative(now - seconds))
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for DisplayMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DisplayMode::Default => write!(f, "Default"),
+            DisplayMode::ByteSize => write!(f, "Byte sizes (KiB/MiB/GiB)"),
+            DisplayMode::RelativeTime => write!(f, "Relative time (3 hours ago)"),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use crate::aggregate::Aggregation;
+use polars::prelude::*;
+
+/// Name of the row-count column added to every group-by result.
+pub const ROWS_COLUMN: &str = "rows";
+
+/// Groups `df` by `keys` and applies each `(column, aggregation)` pair, naming results
+/// like `price_sum`. Groups are sorted by key.
+pub fn group_by(
+    df: &DataFrame,
+    keys: &[String],
+    aggregations: &[(String, Aggregation)],
+) -> PolarsResult<DataFrame> {
+    if keys.is_empty() {
+        polars_bail!(InvalidOperation: "select at least one group column");
+    }
+    let mut exprs = vec![len().alias(ROWS_COLUMN)];
+    for (column, aggregation) in aggregations {
+        exprs.push(
+            aggregation
+                .expr(column)
+                .alias(format!("{}_{}", column, aggregation.suffix())),
+        );
+    }
+    let key_exprs: Vec<Expr> = keys.iter().map(|k| col(k.as_str())).collect();
+    df.clone()
+        .lazy()
+        .group_by(key_exprs)
+        .agg(exprs)
+        .sort(keys.to_vec(), SortMultipleOptions::default())
+        .collect()
+}
+
+/// Predicate selecting the rows of one group, given its key values (nulls match nulls).
+pub fn group_filter(keys: &[String], values: &[AnyValue]) -> Expr {
+    keys.iter()
+        .zip(values)
+        .map(|(key, value)| {
+            if value.is_null() {
+                col(key.as_str()).is_null()
+            } else {
+                let scalar = Scalar::new(value.dtype(), value.clone().into_static());
+                col(key.as_str()).eq(lit(scalar))
+            }
+        })
+        .reduce(|a, b| a.and(b))
+        .unwrap_or(lit(true))
+}
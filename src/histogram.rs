@@ -0,0 +1,107 @@
+//! Value counts in equal-width bins, for the histogram chart.
+
+use crate::plot::Bounds;
+use crate::scatter;
+use eframe::egui::{Color32, Stroke};
+use egui_plot::{Bar, BarChart, PlotUi};
+use polars::prelude::*;
+
+pub const DEFAULT_BINS: usize = 30;
+pub const MAX_BINS: usize = 500;
+
+pub struct Histogram {
+    pub min: f64,
+    pub bin_width: f64,
+    pub counts: Vec<usize>,
+}
+
+pub fn compute(column: &Column, bins: usize) -> PolarsResult<Option<Histogram>> {
+    let values: Vec<f64> = scatter::to_f64(column)?
+        .into_iter()
+        .flatten()
+        .filter(|value| value.is_finite())
+        .collect();
+    let Some(bounds) = Bounds::from_points(values.iter().map(|&value| [value, 0.0])) else {
+        return Ok(None);
+    };
+    let bins = bins.clamp(1, MAX_BINS);
+    let bin_width = if bounds.width() > 0.0 {
+        bounds.width() / bins as f64
+    } else {
+        1.0
+    };
+    let mut counts = vec![0; bins];
+    for value in values {
+        let bin = ((value - bounds.min_x) / bin_width) as usize;
+        counts[bin.min(bins - 1)] += 1;
+    }
+    Ok(Some(Histogram {
+        min: bounds.min_x,
+        bin_width,
+        counts,
+    }))
+}
+
+impl Histogram {
+    /// The value range of bin `i`.
+    pub fn bin(&self, i: usize) -> (f64, f64) {
+        let start = self.min + i as f64 * self.bin_width;
+        (start, start + self.bin_width)
+    }
+
+    /// The height of a bar: the count, or its base-10 logarithm with `log`, where the
+    /// plot's floor is a count of one half so that single values still show.
+    pub fn height(count: usize, log: bool) -> f64 {
+        if log {
+            (count as f64).max(0.5).log10()
+        } else {
+            count as f64
+        }
+    }
+
+    pub fn floor(log: bool) -> f64 {
+        Self::height(0, log)
+    }
+
+    /// Adds one bar per non-empty bin, drawing `hovered` stronger than the rest.
+    pub fn show(&self, plot_ui: &mut PlotUi, log: bool, color: Color32, hovered: Option<usize>) {
+        let floor = Self::floor(log);
+        let bars = self
+            .counts
+            .iter()
+            .enumerate()
+            .filter(|(_, count)| **count > 0)
+            .map(|(i, &count)| {
+                let (start, end) = self.bin(i);
+                let fill = if hovered == Some(i) {
+                    color
+                } else {
+                    color.gamma_multiply(0.7)
+                };
+                Bar::new((start + end) / 2.0, Self::height(count, log) - floor)
+                    .base_offset(floor)
+                    .width(self.bin_width)
+                    .fill(fill)
+                    .stroke(Stroke::new(0.5, color))
+            })
+            .collect();
+        plot_ui.bar_chart(BarChart::new(bars).allow_hover(false));
+    }
+
+    /// The bin under data position `x`.
+    pub fn bin_at(&self, x: f64) -> Option<usize> {
+        let bin = ((x - self.min) / self.bin_width).floor();
+        (bin >= 0.0 && (bin as usize) < self.counts.len()).then_some(bin as usize)
+    }
+}
+
+/// Keeps the rows in bin `[start, end)`, or `[start, end]` for the last bin.
+pub fn bin_filter(column: &str, (start, end): (f64, f64), last: bool) -> Expr {
+    let value = col(column).to_physical().cast(DataType::Float64);
+    let below_end = if last {
+        value.clone().lt_eq(lit(end))
+    } else {
+        value.clone().lt(lit(end))
+    };
+    value.gt_eq(lit(start)).and(below_end)
+}
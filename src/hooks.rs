@@ -0,0 +1,47 @@
+use std::io;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::thread;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HookEvent {
+    Load,
+    Export,
+}
+
+impl std::fmt::Display for HookEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HookEvent::Load => write!(f, "load"),
+            HookEvent::Export => write!(f, "export"),
+        }
+    }
+}
+
+/// Runs a user-configured hook command through the shell without waiting for it.
+///
+/// The affected file paths are passed as arguments (`"$@"` in sh, `%*` in cmd) and the
+/// event name as `TABLR_EVENT`, so a hook can be a script path or a one-liner.
+pub fn run(command: &str, event: HookEvent, paths: &[PathBuf]) -> io::Result<()> {
+    if command.trim().is_empty() {
+        return Ok(());
+    }
+    let mut process = if cfg!(windows) {
+        let mut process = Command::new("cmd");
+        process.arg("/C").arg(command);
+        process
+    } else {
+        let mut process = Command::new("sh");
+        // `$0` names the script in error messages; the paths become `$1`, `$2`, ...
+        process.arg("-c").arg(command).arg("tablr-hook");
+        process
+    };
+    let mut child = process
+        .args(paths)
+        .env("TABLR_EVENT", event.to_string())
+        .stdin(Stdio::null())
+        .spawn()?;
+    // Reap the process so finished hooks do not linger as zombies.
+    thread::spawn(move || child.wait());
+    Ok(())
+}
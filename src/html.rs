@@ -0,0 +1,76 @@
+//! Renders the current view as a self-contained HTML page that opens in any browser.
+
+use eframe::egui::Color32;
+use polars::prelude::*;
+
+const STYLE: &str = "\
+body { font-family: system-ui, sans-serif; font-size: 14px; margin: 0; }
+h1 { font-size: 18px; margin: 12px 16px; }
+.table { overflow: auto; max-height: calc(100vh - 56px); }
+table { border-collapse: collapse; margin: 0 16px 16px; }
+th, td { padding: 4px 10px; border-bottom: 1px solid #dde1e6; white-space: nowrap; }
+thead th { position: sticky; top: 0; background: #e9edf2; text-align: left; }
+tbody tr:nth-child(even) { background: #f5f7f9; }
+";
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn css_color(color: Color32) -> String {
+    let [r, g, b, a] = color.to_srgba_unmultiplied();
+    format!("rgba({}, {}, {}, {:.2})", r, g, b, a as f32 / 255.0)
+}
+
+/// A page with the given columns of `df` as a table: striped rows, a header that stays
+/// in place while scrolling, and per-cell backgrounds. `format` renders each cell as
+/// shown in the table, `align` gives each column's CSS text alignment and `background`
+/// the highlight colour of a cell by row position and column name.
+pub fn page(
+    df: &DataFrame,
+    columns: &[String],
+    title: &str,
+    format: impl Fn(&str, &AnyValue) -> String,
+    align: impl Fn(&str) -> &'static str,
+    background: impl Fn(usize, &str) -> Option<Color32>,
+) -> PolarsResult<String> {
+    let columns: Vec<&Column> = columns
+        .iter()
+        .map(|name| df.column(name))
+        .collect::<PolarsResult<_>>()?;
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str(&format!("<title>{}</title>\n", escape(title)));
+    out.push_str(&format!("<style>\n{}</style>\n</head>\n<body>\n", STYLE));
+    out.push_str(&format!("<h1>{}</h1>\n", escape(title)));
+    out.push_str("<div class=\"table\">\n<table>\n<thead>\n<tr>");
+    for column in &columns {
+        out.push_str(&format!("<th>{}</th>", escape(column.name())));
+    }
+    out.push_str("</tr>\n</thead>\n<tbody>\n");
+    let alignments: Vec<&str> = columns.iter().map(|column| align(column.name())).collect();
+    for row in 0..df.height() {
+        out.push_str("<tr>");
+        for (column, alignment) in columns.iter().zip(&alignments) {
+            let mut style = String::new();
+            if *alignment != "left" {
+                style.push_str(&format!("text-align: {};", alignment));
+            }
+            if let Some(color) = background(row, column.name()) {
+                style.push_str(&format!("background: {};", css_color(color)));
+            }
+            let text = escape(&format(column.name(), &column.get(row)?));
+            if style.is_empty() {
+                out.push_str(&format!("<td>{}</td>", text));
+            } else {
+                out.push_str(&format!("<td style=\"{}\">{}</td>", style, text));
+            }
+        }
+        out.push_str("</tr>\n");
+    }
+    out.push_str("</tbody>\n</table>\n</div>\n</body>\n</html>\n");
+    Ok(out)
+}
@@ -0,0 +1,34 @@
+use polars::prelude::*;
+
+/// Number of non-null values inspected per column when detecting images.
+const SAMPLE_SIZE: usize = 20;
+
+/// Returns the image format name when `bytes` start with PNG, JPEG, GIF or WebP magic bytes.
+pub fn image_format(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("png")
+    } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("jpeg")
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        Some("gif")
+    } else if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        Some("webp")
+    } else {
+        None
+    }
+}
+
+/// Returns the names of binary columns whose sampled values are all recognizable images.
+pub fn detect_image_columns(df: &DataFrame) -> Vec<String> {
+    df.get_columns()
+        .iter()
+        .filter(|column| {
+            let Ok(values) = column.binary() else {
+                return false;
+            };
+            let mut sampled = values.into_iter().flatten().take(SAMPLE_SIZE).peekable();
+            sampled.peek().is_some() && sampled.all(|bytes| image_format(bytes).is_some())
+        })
+        .map(|column| column.name().to_string())
+        .collect()
+}
@@ -0,0 +1,230 @@
+//! Lets a second launch hand its files to the window that is already open, over a
+//! local socket. The port and a secret token are kept in a file only the user can read,
+//! and a client must present the token before any files are accepted.
+
+use crate::settings::config_dir;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::Duration;
+
+/// Sent by the running instance first, so a port reused by another program is not
+/// mistaken for it.
+const GREETING: &str = "tablr";
+
+/// How long a connected client may take to send its files.
+const READ_TIMEOUT: Duration = Duration::from_secs(2);
+
+fn port_file() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("instance-port"))
+}
+
+/// A fresh random token, as hex.
+fn new_token() -> io::Result<String> {
+    let mut bytes = [0u8; 32];
+    getrandom::fill(&mut bytes).map_err(io::Error::other)?;
+    Ok(bytes.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+/// Compares in constant time, so the token cannot be guessed byte by byte.
+fn same_token(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0, |diff, (x, y)| diff | (x ^ y))
+            == 0
+}
+
+/// Creates or truncates `path` so that only the current user can read it.
+fn create_private(path: &Path) -> io::Result<File> {
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(0o600);
+        let file = options.open(path)?;
+        // `mode` only applies to new files; an older file may have been readable.
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+        Ok(file)
+    }
+    // Elsewhere the config directory is private to the user already.
+    #[cfg(not(unix))]
+    options.open(path)
+}
+
+/// Passes `paths` to a running instance. Returns false when none is running, in which
+/// case this process should open its own window.
+// A page is never launched with files to hand over.
+#[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+pub fn forward(paths: &[PathBuf]) -> bool {
+    let Some((port, token)) = port_file()
+        .and_then(|file| fs::read_to_string(file).ok())
+        .and_then(|text| {
+            let (port, token) = text.trim().split_once(' ')?;
+            Some((port.parse::<u16>().ok()?, token.to_string()))
+        })
+    else {
+        return false;
+    };
+    let send = || -> io::Result<bool> {
+        let address = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+        let mut stream = TcpStream::connect_timeout(&address, Duration::from_millis(300))?;
+        stream.set_read_timeout(Some(Duration::from_millis(500)))?;
+        let mut greeting = String::new();
+        BufReader::new(&stream).read_line(&mut greeting)?;
+        if greeting.trim_end() != GREETING {
+            return Ok(false);
+        }
+        writeln!(stream, "{}", token)?;
+        for path in paths {
+            // The running instance has its own working directory.
+            let path = fs::canonicalize(path).unwrap_or_else(|_| path.clone());
+            writeln!(stream, "{}", path.display())?;
+        }
+        Ok(true)
+    };
+    send().unwrap_or(false)
+}
+
+/// Reads the token and then the paths sent by one client.
+fn receive(stream: TcpStream, token: &str) -> io::Result<Vec<PathBuf>> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    writeln!(&stream, "{}", GREETING)?;
+    let mut lines = BufReader::new(&stream).lines();
+    let sent = lines.next().transpose()?.unwrap_or_default();
+    if !same_token(&sent, token) {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "wrong instance token",
+        ));
+    }
+    lines.map(|line| line.map(PathBuf::from)).collect()
+}
+
+/// Accepts files from later launches on background threads. `notify` runs after each
+/// batch arrives, e.g. to wake up the UI.
+pub fn listen(notify: impl Fn() + Send + Sync + 'static) -> io::Result<Receiver<Vec<PathBuf>>> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+    let file = port_file()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no config directory"))?;
+    if let Some(dir) = file.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let token = new_token()?;
+    writeln!(
+        create_private(&file)?,
+        "{} {}",
+        listener.local_addr()?.port(),
+        token
+    )?;
+    Ok(accept(listener, token, notify))
+}
+
+fn accept(
+    listener: TcpListener,
+    token: String,
+    notify: impl Fn() + Send + Sync + 'static,
+) -> Receiver<Vec<PathBuf>> {
+    let (sender, receiver) = mpsc::channel();
+    let notify = Arc::new(notify);
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            // One thread per client, so a client that never sends anything only holds
+            // up itself.
+            let sender = sender.clone();
+            let notify = notify.clone();
+            let token = token.clone();
+            thread::spawn(move || {
+                if let Ok(paths) = receive(stream, &token)
+                    && !paths.is_empty()
+                    && sender.send(paths).is_ok()
+                {
+                    notify();
+                }
+            });
+        }
+    });
+    receiver
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_are_random_hex() {
+        let (a, b) = (new_token().unwrap(), new_token().unwrap());
+        assert_eq!(a.len(), 64);
+        assert!(a.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn compares_tokens_exactly() {
+        assert!(same_token("abc", "abc"));
+        assert!(!same_token("abc", "abd"));
+        assert!(!same_token("abc", "ab"));
+        assert!(!same_token("", "abc"));
+    }
+
+    /// Connects like [`forward`] does, reading the greeting before sending `lines`.
+    fn send(address: SocketAddr, lines: String) -> thread::JoinHandle<()> {
+        thread::spawn(move || {
+            let mut stream = TcpStream::connect(address).unwrap();
+            let mut greeting = String::new();
+            BufReader::new(&stream).read_line(&mut greeting).unwrap();
+            // The instance may hang up before reading everything.
+            let _ = stream.write_all(lines.as_bytes());
+        })
+    }
+
+    #[test]
+    fn rejects_clients_without_the_token() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let address = listener.local_addr().unwrap();
+        let client = send(address, "guess\n/etc/passwd\n".to_string());
+        let (stream, _) = listener.accept().unwrap();
+        let error = receive(stream, &new_token().unwrap()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::PermissionDenied);
+        client.join().unwrap();
+    }
+
+    #[test]
+    fn accepts_paths_after_the_token() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let address = listener.local_addr().unwrap();
+        let token = new_token().unwrap();
+        let client = send(
+            address,
+            format!("{}\n/data/a.parquet\n/data/b.parquet\n", token),
+        );
+        let (stream, _) = listener.accept().unwrap();
+        assert_eq!(
+            receive(stream, &token).unwrap(),
+            [
+                PathBuf::from("/data/a.parquet"),
+                PathBuf::from("/data/b.parquet")
+            ]
+        );
+        client.join().unwrap();
+    }
+
+    #[test]
+    fn an_idle_client_does_not_hold_up_others() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let address = listener.local_addr().unwrap();
+        let token = new_token().unwrap();
+        let _idle = TcpStream::connect(address).unwrap();
+        let receiver = accept(listener, token.clone(), || {});
+        send(address, format!("{}\n/data/a.parquet\n", token));
+        assert_eq!(
+            receiver.recv_timeout(READ_TIMEOUT / 2).unwrap(),
+            [PathBuf::from("/data/a.parquet")]
+        );
+    }
+}
@@ -0,0 +1,70 @@
+use polars::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JoinKind {
+    Inner,
+    Left,
+    Outer,
+    Anti,
+}
+
+impl JoinKind {
+    pub const ALL: [JoinKind; 4] = [
+        JoinKind::Inner,
+        JoinKind::Left,
+        JoinKind::Outer,
+        JoinKind::Anti,
+    ];
+
+    fn join_type(self) -> JoinType {
+        match self {
+            JoinKind::Inner => JoinType::Inner,
+            JoinKind::Left => JoinType::Left,
+            JoinKind::Outer => JoinType::Full,
+            JoinKind::Anti => JoinType::Anti,
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            JoinKind::Inner => "Rows with a match in both files",
+            JoinKind::Left => "All current rows, with matching columns where found",
+            JoinKind::Outer => "All rows from both files",
+            JoinKind::Anti => "Current rows without a match",
+        }
+    }
+}
+
+impl std::fmt::Display for JoinKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JoinKind::Inner => write!(f, "Inner"),
+            JoinKind::Left => write!(f, "Left"),
+            JoinKind::Outer => write!(f, "Outer"),
+            JoinKind::Anti => write!(f, "Anti"),
+        }
+    }
+}
+
+/// Joins `right` onto `left`, keeping the order of `left`'s rows. Columns of `right`
+/// whose names are taken get a `_right` suffix, and the right key is cast to the left
+/// key's type so that e.g. integer ids match ids stored as text.
+pub fn join(
+    left: &DataFrame,
+    right: &DataFrame,
+    left_key: &str,
+    right_key: &str,
+    kind: JoinKind,
+) -> PolarsResult<DataFrame> {
+    let key_type = left.column(left_key)?.dtype().clone();
+    let right = right
+        .clone()
+        .lazy()
+        .with_column(col(right_key).strict_cast(key_type));
+    let mut args = JoinArgs::new(kind.join_type()).with_coalesce(JoinCoalesce::CoalesceColumns);
+    args.maintain_order = MaintainOrderJoin::Left;
+    left.clone()
+        .lazy()
+        .join(right, [col(left_key)], [col(right_key)], args)
+        .collect()
+}
@@ -0,0 +1,67 @@
+use eframe::egui::{CollapsingHeader, RichText, Ui};
+use polars::prelude::*;
+use serde_json::Value;
+
+/// Number of non-null values inspected per column when detecting JSON.
+const SAMPLE_SIZE: usize = 20;
+
+/// Returns the names of string columns whose sampled values all parse as JSON objects or arrays.
+pub fn detect_json_columns(df: &DataFrame) -> Vec<String> {
+    df.get_columns()
+        .iter()
+        .filter(|column| {
+            let Ok(values) = column.str() else {
+                return false;
+            };
+            let mut sampled = values.into_iter().flatten().take(SAMPLE_SIZE).peekable();
+            sampled.peek().is_some()
+                && sampled.all(|value| {
+                    let value = value.trim_start();
+                    (value.starts_with('{') || value.starts_with('['))
+                        && serde_json::from_str::<Value>(value).is_ok()
+                })
+        })
+        .map(|column| column.name().to_string())
+        .collect()
+}
+
+/// Renders a JSON value as a collapsible tree.
+pub fn show_json_tree(ui: &mut Ui, value: &Value) {
+    show_node(ui, None, value, &mut Vec::new());
+}
+
+fn show_node(ui: &mut Ui, key: Option<&str>, value: &Value, path: &mut Vec<String>) {
+    let prefix = key.map(|k| format!("{}: ", k)).unwrap_or_default();
+    match value {
+        Value::Object(map) => {
+            CollapsingHeader::new(format!("{}{{{}}}", prefix, map.len()))
+                .id_salt(&path)
+                .default_open(path.len() < 2)
+                .show(ui, |ui| {
+                    for (k, v) in map {
+                        path.push(k.clone());
+                        show_node(ui, Some(k), v, path);
+                        path.pop();
+                    }
+                });
+        }
+        Value::Array(items) => {
+            CollapsingHeader::new(format!("{}[{}]", prefix, items.len()))
+                .id_salt(&path)
+                .default_open(path.len() < 2)
+                .show(ui, |ui| {
+                    for (i, v) in items.iter().enumerate() {
+                        path.push(i.to_string());
+                        show_node(ui, Some(&i.to_string()), v, path);
+                        path.pop();
+                    }
+                });
+        }
+        scalar => {
+            ui.horizontal(|ui| {
+                ui.label(prefix);
+                ui.label(RichText::new(scalar.to_string()).monospace());
+            });
+        }
+    }
+}
@@ -0,0 +1,122 @@
+//! Keyboard shortcuts for window-wide actions, configurable in `settings.toml` as e.g.
+//! `search = "Ctrl+F"`.
+
+use eframe::egui::{Key, KeyboardShortcut, Modifiers};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Action {
+    Open,
+    Search,
+    Export,
+    Filter,
+    GoToRow,
+    ToggleBookmark,
+    Settings,
+}
+
+impl Action {
+    pub const ALL: [Action; 7] = [
+        Action::Open,
+        Action::Search,
+        Action::Export,
+        Action::Filter,
+        Action::GoToRow,
+        Action::ToggleBookmark,
+        Action::Settings,
+    ];
+}
+
+impl std::fmt::Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Action::Open => write!(f, "Open files"),
+            Action::Search => write!(f, "Search"),
+            Action::Export => write!(f, "Export in the default format"),
+            Action::Filter => write!(f, "Filter"),
+            Action::GoToRow => write!(f, "Go to row"),
+            Action::ToggleBookmark => write!(f, "Toggle bookmark"),
+            Action::Settings => write!(f, "Settings"),
+        }
+    }
+}
+
+/// One shortcut per action, written like `Ctrl+Shift+E`. `Ctrl` means Cmd on macOS;
+/// an empty string leaves the action unbound.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Keybindings {
+    pub open: String,
+    pub search: String,
+    pub export: String,
+    pub filter: String,
+    pub go_to_row: String,
+    pub toggle_bookmark: String,
+    pub settings: String,
+}
+
+impl Default for Keybindings {
+    fn default() -> Self {
+        Self {
+            open: "Ctrl+O".to_string(),
+            search: "Ctrl+F".to_string(),
+            export: "Ctrl+S".to_string(),
+            filter: "Ctrl+L".to_string(),
+            go_to_row: "Ctrl+G".to_string(),
+            toggle_bookmark: "Ctrl+B".to_string(),
+            settings: "Ctrl+,".to_string(),
+        }
+    }
+}
+
+impl Keybindings {
+    pub fn get_mut(&mut self, action: Action) -> &mut String {
+        match action {
+            Action::Open => &mut self.open,
+            Action::Search => &mut self.search,
+            Action::Export => &mut self.export,
+            Action::Filter => &mut self.filter,
+            Action::GoToRow => &mut self.go_to_row,
+            Action::ToggleBookmark => &mut self.toggle_bookmark,
+            Action::Settings => &mut self.settings,
+        }
+    }
+
+    pub fn get(&self, action: Action) -> &str {
+        match action {
+            Action::Open => &self.open,
+            Action::Search => &self.search,
+            Action::Export => &self.export,
+            Action::Filter => &self.filter,
+            Action::GoToRow => &self.go_to_row,
+            Action::ToggleBookmark => &self.toggle_bookmark,
+            Action::Settings => &self.settings,
+        }
+    }
+
+    pub fn shortcut(&self, action: Action) -> Option<KeyboardShortcut> {
+        parse_shortcut(self.get(action))
+    }
+}
+
+/// Parses `Ctrl+Shift+E`-style text; modifier names are case-insensitive and key names
+/// are egui's, e.g. `F5`, `PageDown` or `,`.
+pub fn parse_shortcut(text: &str) -> Option<KeyboardShortcut> {
+    let mut parts: Vec<&str> = text.split('+').map(str::trim).collect();
+    // `Ctrl++` binds the plus key.
+    if text.trim_end().ends_with("++") {
+        parts.truncate(parts.len() - 2);
+        parts.push("+");
+    }
+    let (key, modifier_names) = parts.split_last()?;
+    let mut modifiers = Modifiers::NONE;
+    for name in modifier_names {
+        modifiers |= match name.to_ascii_lowercase().as_str() {
+            "ctrl" | "cmd" | "command" => Modifiers::COMMAND,
+            "shift" => Modifiers::SHIFT,
+            "alt" | "option" => Modifiers::ALT,
+            _ => return None,
+        };
+    }
+    Some(KeyboardShortcut::new(modifiers, Key::from_name(key)?))
+}
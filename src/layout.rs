@@ -0,0 +1,58 @@
+use crate::settings;
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// Column arrangement remembered for every schema the user has opened.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ColumnLayout {
+    pub order: Vec<String>,
+    pub hidden: Vec<String>,
+    pub widths: HashMap<String, f32>,
+}
+
+/// Hashes column names and dtypes, so files sharing a schema share a layout.
+///
+/// Uses FNV-1a rather than `DefaultHasher` because the result is written to disk and
+/// must stay stable across builds.
+pub fn schema_hash(schema: &Schema) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for (name, dtype) in schema.iter() {
+        for byte in format!("{}:{};", name, dtype).bytes() {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+    hash
+}
+
+fn layouts_path() -> Option<PathBuf> {
+    settings::config_dir().map(|dir| dir.join("layouts.json"))
+}
+
+fn read_all() -> HashMap<String, ColumnLayout> {
+    layouts_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Returns the stored layout for a schema, if one was saved before.
+pub fn load(schema_hash: u64) -> Option<ColumnLayout> {
+    read_all().remove(&format!("{:016x}", schema_hash))
+}
+
+/// Stores the layout for a schema, keeping layouts of other schemas intact.
+pub fn save(schema_hash: u64, layout: &ColumnLayout) -> io::Result<()> {
+    let path = layouts_path()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no config directory"))?;
+    let mut layouts = read_all();
+    layouts.insert(format!("{:016x}", schema_hash), layout.clone());
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(&layouts)?)
+}
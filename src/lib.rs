@@ -0,0 +1,82 @@
+//! Parquet viewing for egui. The `tablr` binary is a thin wrapper around `run_viewer`, or
+//! `start_web` in the browser; [`table::DataFrameTable`] is the grid on its own, for
+//! embedding in other egui apps.
+
+mod aggregate;
+mod annotations;
+mod app;
+mod append;
+mod bar_chart;
+mod box_plot;
+mod bucket;
+mod cast;
+pub mod cli;
+mod constant;
+mod correlation;
+mod derived;
+mod describe;
+mod diagnostics;
+mod dictionary;
+mod diff;
+mod duplicates;
+mod edit;
+mod epoch;
+pub mod export;
+mod expression;
+pub mod filter;
+pub mod format;
+mod group_by;
+mod histogram;
+mod hooks;
+mod html;
+mod images;
+mod instance;
+mod join;
+mod json_view;
+mod keymap;
+mod layout;
+mod line_chart;
+pub mod loader;
+mod manifest;
+mod markdown;
+mod metadata;
+mod minichart;
+mod nested;
+mod notify;
+mod overview;
+mod panels;
+#[cfg(not(target_arch = "wasm32"))]
+mod pdf;
+mod pii;
+mod pivot;
+mod plot;
+mod profile;
+pub mod query;
+pub mod render;
+mod replace;
+mod scatter;
+mod schema_compare;
+mod schema_export;
+mod script;
+mod search;
+mod settings;
+mod similarity;
+mod split;
+pub mod table;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod tui;
+mod unpivot;
+mod watch;
+#[cfg(target_arch = "wasm32")]
+mod web;
+mod workspace;
+mod xlsx;
+
+#[cfg(target_arch = "wasm32")]
+pub use app::start_web;
+#[cfg(not(target_arch = "wasm32"))]
+pub use app::{forward_to_instance, run_viewer, run_viewer_with_renderers};
+pub use diagnostics::init_logging;
+
+/// Column holding each row's position in the loaded files.
+const ROW_INDEX_COLUMN: &str = "Row Index";
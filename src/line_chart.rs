@@ -0,0 +1,113 @@
+//! Numeric columns over a date or datetime column, for the line chart.
+
+use crate::plot;
+use crate::scatter;
+use egui_plot::{PlotPoints, PlotUi};
+use polars::prelude::*;
+
+/// Long series are reduced to the lowest and highest value in each of this many
+/// buckets, which keeps spikes visible at any zoom that shows the whole series.
+const MAX_BUCKETS: usize = 2_000;
+
+pub struct Line {
+    pub name: String,
+    /// Sorted by x.
+    pub points: Vec<[f64; 2]>,
+}
+
+pub struct LineChart {
+    pub lines: Vec<Line>,
+}
+
+/// Whether a column can be the time axis.
+pub fn is_time(dtype: &DataType) -> bool {
+    matches!(dtype, DataType::Date | DataType::Datetime(..))
+}
+
+pub fn compute(df: &DataFrame, x: &str, ys: &[String]) -> PolarsResult<LineChart> {
+    let mut columns = vec![col(x)];
+    columns.extend(ys.iter().map(|y| col(y.as_str())));
+    let sorted = df
+        .clone()
+        .lazy()
+        .select(columns)
+        .filter(col(x).is_not_null())
+        .sort([x], SortMultipleOptions::default())
+        .collect()?;
+    let xs = scatter::to_f64(sorted.column(x)?)?;
+    let lines = ys
+        .iter()
+        .map(|y| {
+            let values = scatter::to_f64(sorted.column(y)?)?;
+            let points = xs
+                .iter()
+                .zip(values.iter())
+                .filter_map(|(x, y)| Some([x?, y?]))
+                .filter(|[_, y]| y.is_finite())
+                .collect();
+            Ok(Line {
+                name: y.clone(),
+                points: downsample(points),
+            })
+        })
+        .collect::<PolarsResult<Vec<_>>>()?;
+    Ok(LineChart { lines })
+}
+
+fn downsample(points: Vec<[f64; 2]>) -> Vec<[f64; 2]> {
+    if points.len() <= 2 * MAX_BUCKETS {
+        return points;
+    }
+    let mut reduced = Vec::with_capacity(2 * MAX_BUCKETS);
+    for bucket in points.chunks(points.len().div_ceil(MAX_BUCKETS)) {
+        let by_y = |a: &&[f64; 2], b: &&[f64; 2]| a[1].total_cmp(&b[1]);
+        let low = bucket.iter().min_by(by_y).copied();
+        let high = bucket.iter().max_by(by_y).copied();
+        if let (Some(low), Some(high)) = (low, high) {
+            if low[0] <= high[0] {
+                reduced.extend([low, high]);
+            } else {
+                reduced.extend([high, low]);
+            }
+        }
+    }
+    reduced
+}
+
+impl LineChart {
+    /// Whether no line has a point to draw.
+    pub fn is_empty(&self) -> bool {
+        self.lines.iter().all(|line| line.points.is_empty())
+    }
+
+    /// Adds one named line per column, in the colours of the column checkboxes.
+    pub fn show(&self, plot_ui: &mut PlotUi) {
+        for (i, line) in self.lines.iter().enumerate() {
+            plot_ui.line(
+                egui_plot::Line::new(PlotPoints::new(line.points.clone()))
+                    .name(&line.name)
+                    .color(plot::palette(i))
+                    .width(1.5)
+                    .allow_hover(false),
+            );
+        }
+    }
+
+    /// Each line's point nearest to `x`, with the line's position for its colour.
+    pub fn values_at(&self, x: f64) -> Vec<(usize, &Line, [f64; 2])> {
+        self.lines
+            .iter()
+            .enumerate()
+            .filter_map(|(i, line)| {
+                let after = line.points.partition_point(|point| point[0] < x);
+                let candidates = [after.checked_sub(1), Some(after)];
+                let nearest = candidates
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|i| line.points.get(i))
+                    .min_by(|a, b| (a[0] - x).abs().total_cmp(&(b[0] - x).abs()))?;
+                Some((i, line, *nearest))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,205 @@
+use crate::metadata;
+use polars::prelude::*;
+use std::collections::HashSet;
+use std::io::Cursor;
+use std::path::PathBuf;
+
+/// How many of the dataset's rows to load.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RowLimit {
+    All,
+    /// The first rows of the dataset.
+    Head(usize),
+    /// The last rows of the dataset.
+    Tail(usize),
+    /// A random sample that is the same for the same seed. Rows are drawn from randomly
+    /// chosen row groups, so only those groups are read.
+    Sample {
+        rows: usize,
+        seed: u64,
+    },
+    /// Chosen row groups, as the position of their first row in the dataset and their
+    /// row count.
+    RowGroups(Vec<(usize, usize)>),
+}
+
+impl std::fmt::Display for RowLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RowLimit::All => write!(f, "All rows"),
+            RowLimit::Head(rows) => write!(f, "First {} rows", rows),
+            RowLimit::Tail(rows) => write!(f, "Last {} rows", rows),
+            RowLimit::Sample { rows, seed } => {
+                write!(f, "Random sample of {} rows (seed {})", rows, seed)
+            }
+            RowLimit::RowGroups(groups) => {
+                let rows: usize = groups.iter().map(|(_, rows)| rows).sum();
+                write!(f, "{} row groups ({} rows)", groups.len(), rows)
+            }
+        }
+    }
+}
+
+/// SplitMix64, a small seedable generator; sampling only needs reproducibility.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next() % bound
+    }
+}
+
+/// Picks `count` distinct positions below `total` with Floyd's algorithm, in ascending order.
+fn sample_positions(total: usize, count: usize, seed: u64) -> Vec<IdxSize> {
+    let mut rng = SplitMix64(seed);
+    let mut chosen = HashSet::with_capacity(count);
+    for upper in (total - count)..total {
+        let candidate = rng.below(upper as u64 + 1) as usize;
+        if !chosen.insert(candidate) {
+            chosen.insert(upper);
+        }
+    }
+    let mut positions: Vec<IdxSize> = chosen.into_iter().map(|i| i as IdxSize).collect();
+    positions.sort_unstable();
+    positions
+}
+
+/// Chooses row groups in random order until they hold `rows` rows, then `rows` positions
+/// among them. Returns the chosen groups in dataset order, as the position of their first
+/// row and their row count, with the sampled dataset positions.
+fn sample_row_groups(
+    groups: &[(usize, usize)],
+    rows: usize,
+    seed: u64,
+) -> (Vec<(usize, usize)>, Vec<IdxSize>) {
+    let mut rng = SplitMix64(seed);
+    let mut order: Vec<usize> = (0..groups.len()).collect();
+    for i in (1..order.len()).rev() {
+        order.swap(i, rng.below(i as u64 + 1) as usize);
+    }
+    let mut chosen = Vec::new();
+    let mut available = 0;
+    for i in order {
+        if available >= rows {
+            break;
+        }
+        chosen.push(groups[i]);
+        available += groups[i].1;
+    }
+    chosen.sort_unstable();
+    // Positions are drawn among the chosen rows as if they were contiguous, then mapped
+    // back to the dataset.
+    let mut positions = sample_positions(available, rows.min(available), rng.next());
+    let mut offset = 0;
+    let mut group = chosen.iter();
+    let mut current = group.next().copied().unwrap_or_default();
+    for position in &mut positions {
+        while *position as usize >= offset + current.1 {
+            offset += current.1;
+            current = group.next().copied().unwrap_or_default();
+        }
+        *position = (current.0 + *position as usize - offset) as IdxSize;
+    }
+    (chosen, positions)
+}
+
+/// Scans one or more Parquet files as a single lazy frame without reading any data yet.
+pub fn scan(paths: &[PathBuf]) -> PolarsResult<LazyFrame> {
+    let scan_sources = ScanSources::Paths(paths.to_vec().into());
+    LazyFrame::scan_parquet_sources(scan_sources, ScanArgsParquet::default())
+}
+
+/// Reads one or more Parquet files (e.g. the partitions of a dataset) into a single frame.
+pub fn read_parquet(paths: &[PathBuf]) -> PolarsResult<DataFrame> {
+    scan(paths)?.collect()
+}
+
+/// Like [`read_parquet`], but numbers the rows in `row_index` by their position in the
+/// dataset before `limit` applies, so sampled rows keep their original numbers.
+pub fn read_parquet_indexed(
+    paths: &[PathBuf],
+    row_index: &str,
+    limit: &RowLimit,
+) -> PolarsResult<DataFrame> {
+    limit_rows(
+        scan(paths)?.with_row_index(row_index, None),
+        row_index,
+        limit,
+        || metadata::read_metadata(paths),
+    )
+}
+
+/// Like [`read_parquet_indexed`], for a file that is already in memory, such as one
+/// dropped onto the browser window.
+pub fn read_parquet_bytes_indexed(
+    bytes: &[u8],
+    row_index: &str,
+    limit: &RowLimit,
+) -> PolarsResult<DataFrame> {
+    let scan = ParquetReader::new(Cursor::new(bytes))
+        .finish()?
+        .lazy()
+        .with_row_index(row_index, None);
+    limit_rows(scan, row_index, limit, || {
+        Ok(vec![
+            ParquetReader::new(Cursor::new(bytes))
+                .get_metadata()?
+                .clone(),
+        ])
+    })
+}
+
+/// `footers` reads the Parquet metadata of the scanned files, for sampling.
+fn limit_rows(
+    scan: LazyFrame,
+    row_index: &str,
+    limit: &RowLimit,
+    footers: impl FnOnce() -> PolarsResult<Vec<FileMetadataRef>>,
+) -> PolarsResult<DataFrame> {
+    match limit {
+        RowLimit::All => scan.collect(),
+        // Slices are pushed down into the scan, so only the needed row groups are read.
+        RowLimit::Head(rows) => scan.slice(0, *rows as IdxSize).collect(),
+        RowLimit::Tail(rows) => scan.tail(*rows as IdxSize).collect(),
+        RowLimit::RowGroups(groups) => {
+            concat(slices(scan, groups), UnionArgs::default())?.collect()
+        }
+        RowLimit::Sample { rows, seed } => {
+            // Row group sizes come from the Parquet footers, without reading any data.
+            let groups: Vec<(usize, usize)> = metadata::row_groups(&footers()?)
+                .iter()
+                .map(|group| (group.first_row, group.rows))
+                .collect();
+            if *rows >= groups.iter().map(|(_, rows)| rows).sum() {
+                return scan.collect();
+            }
+            let (groups, positions) = sample_row_groups(&groups, *rows, *seed);
+            let positions = IdxCa::from_vec(row_index.into(), positions);
+            let scan = concat(slices(scan, &groups), UnionArgs::default())?;
+            scan.join(
+                DataFrame::new(vec![positions.into_column()])?.lazy(),
+                [col(row_index)],
+                [col(row_index)],
+                JoinArgs::new(JoinType::Inner),
+            )
+            .sort([row_index], SortMultipleOptions::default())
+            .collect()
+        }
+    }
+}
+
+/// Slices are pushed down into the scan, so only the row groups they cover are read.
+fn slices(scan: LazyFrame, groups: &[(usize, usize)]) -> Vec<LazyFrame> {
+    groups
+        .iter()
+        .map(|&(first_row, rows)| scan.clone().slice(first_row as i64, rows as IdxSize))
+        .collect()
+}
@@ -0,0 +1,77 @@
+use polars::prelude::*;
+use serde::Serialize;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// A file the exported data was read from.
+#[derive(Debug, Serialize)]
+pub struct SourceFile {
+    pub path: String,
+    pub size: u64,
+    pub blake3: String,
+}
+
+/// Provenance record written next to an exported file: where the data came from and
+/// what was done to it, so a shared file can be traced back and reproduced.
+#[derive(Debug, Serialize)]
+pub struct Manifest {
+    pub tablr_version: &'static str,
+    pub created_at: String,
+    pub output: String,
+    pub rows: usize,
+    pub columns: Vec<String>,
+    pub sources: Vec<SourceFile>,
+    /// Human-readable transformations, filters and sorting in the order they apply.
+    pub steps: Vec<String>,
+}
+
+fn source_file(path: &Path) -> io::Result<SourceFile> {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update_reader(File::open(path)?)?;
+    Ok(SourceFile {
+        path: path
+            .canonicalize()
+            .unwrap_or_else(|_| path.to_path_buf())
+            .display()
+            .to_string(),
+        size: fs::metadata(path)?.len(),
+        blake3: hasher.finalize().to_hex().to_string(),
+    })
+}
+
+impl Manifest {
+    /// Describes `exported`, written to `output` from `sources`. Hashes every source file.
+    pub fn new(
+        output: &Path,
+        exported: &DataFrame,
+        sources: &[PathBuf],
+        steps: Vec<String>,
+    ) -> io::Result<Self> {
+        Ok(Self {
+            tablr_version: env!("CARGO_PKG_VERSION"),
+            created_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            output: output.display().to_string(),
+            rows: exported.height(),
+            columns: exported
+                .get_column_names()
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+            sources: sources
+                .iter()
+                .map(|path| source_file(path))
+                .collect::<io::Result<_>>()?,
+            steps,
+        })
+    }
+
+    /// Writes the manifest as `<output>.manifest.json`, returning its path.
+    pub fn write(&self, output: &Path) -> io::Result<PathBuf> {
+        let mut name = output.as_os_str().to_owned();
+        name.push(".manifest.json");
+        let path = PathBuf::from(name);
+        fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(path)
+    }
+}
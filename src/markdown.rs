@@ -0,0 +1,44 @@
+use polars::prelude::*;
+
+/// Makes cell text safe inside a pipe table, which cannot contain pipes or line breaks.
+fn escape(text: &str) -> String {
+    text.replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace(['\n', '\r'], "<br>")
+}
+
+/// Formats the given rows of the given columns as a GitHub-flavoured Markdown table,
+/// with numeric columns right-aligned. `format` renders each cell as shown in the table.
+pub fn table(
+    df: &DataFrame,
+    columns: &[String],
+    rows: impl IntoIterator<Item = usize>,
+    format: impl Fn(&str, &AnyValue) -> String,
+) -> PolarsResult<String> {
+    let columns: Vec<&Column> = columns
+        .iter()
+        .map(|name| df.column(name))
+        .collect::<PolarsResult<_>>()?;
+    let mut out = String::new();
+    let header: Vec<String> = columns.iter().map(|column| escape(column.name())).collect();
+    out.push_str(&format!("| {} |\n", header.join(" | ")));
+    let alignment: Vec<&str> = columns
+        .iter()
+        .map(|column| {
+            if column.dtype().is_primitive_numeric() {
+                "---:"
+            } else {
+                "---"
+            }
+        })
+        .collect();
+    out.push_str(&format!("| {} |\n", alignment.join(" | ")));
+    for row in rows {
+        let cells = columns
+            .iter()
+            .map(|column| Ok(escape(&format(column.name(), &column.get(row)?))))
+            .collect::<PolarsResult<Vec<_>>>()?;
+        out.push_str(&format!("| {} |\n", cells.join(" | ")));
+    }
+    Ok(out)
+}
@@ -0,0 +1,136 @@
+//! Reads Parquet footers to describe how files are laid out, without reading any data.
+
+use crate::append::SOURCE_COLUMN;
+use polars::prelude::*;
+use std::fs::File;
+use std::path::PathBuf;
+
+/// One row group of a loaded file, located within the whole dataset.
+pub struct RowGroup {
+    /// Position of the file in the list of loaded files.
+    pub file: usize,
+    /// Position of the row group within its file.
+    pub index: usize,
+    pub rows: usize,
+    /// Position of the group's first row in the dataset, counting across files.
+    pub first_row: usize,
+    pub compressed_bytes: usize,
+    pub uncompressed_bytes: usize,
+}
+
+pub fn read_metadata(paths: &[PathBuf]) -> PolarsResult<Vec<FileMetadataRef>> {
+    paths
+        .iter()
+        .map(|path| {
+            ParquetReader::new(File::open(path)?)
+                .get_metadata()
+                .cloned()
+        })
+        .collect()
+}
+
+/// The row groups of all files in dataset order.
+pub fn row_groups(files: &[FileMetadataRef]) -> Vec<RowGroup> {
+    let mut first_row = 0;
+    let mut groups = Vec::new();
+    for (file, metadata) in files.iter().enumerate() {
+        for (index, group) in metadata.row_groups.iter().enumerate() {
+            groups.push(RowGroup {
+                file,
+                index,
+                rows: group.num_rows(),
+                first_row,
+                compressed_bytes: group.compressed_size(),
+                uncompressed_bytes: group.total_byte_size(),
+            });
+            first_row += group.num_rows();
+        }
+    }
+    groups
+}
+
+/// Column with the position of each row's row group within its file.
+pub const SOURCE_ROW_GROUP_COLUMN: &str = "source_row_group";
+
+/// Adds [`SOURCE_COLUMN`] and [`SOURCE_ROW_GROUP_COLUMN`], locating each row of `df` by its
+/// dataset position in `row_index`. `names` labels the files described by `files`.
+pub fn with_source_columns(
+    df: &DataFrame,
+    row_index: &str,
+    names: &[String],
+    files: &[FileMetadataRef],
+) -> PolarsResult<DataFrame> {
+    let groups = row_groups(files);
+    let (sources, row_groups): (Vec<Option<&str>>, Vec<Option<u32>>) = df
+        .column(row_index)?
+        .idx()?
+        .into_iter()
+        .map(|position| {
+            let position = position? as usize;
+            let group = groups
+                .partition_point(|group| group.first_row <= position)
+                .checked_sub(1)
+                .map(|i| &groups[i])
+                .filter(|group| position < group.first_row + group.rows)?;
+            Some((names[group.file].as_str(), group.index as u32))
+        })
+        .map(|found| found.unzip())
+        .unzip();
+    let mut df = df.clone();
+    df.with_column(Column::new(SOURCE_COLUMN.into(), sources))?;
+    df.with_column(Column::new(SOURCE_ROW_GROUP_COLUMN.into(), row_groups))?;
+    Ok(df)
+}
+
+/// Statistics stored for one column chunk, which readers use to skip row groups.
+pub struct ChunkStats {
+    pub column: String,
+    pub min: Option<String>,
+    pub max: Option<String>,
+    pub null_count: Option<u64>,
+}
+
+impl ChunkStats {
+    /// Min/max bounds are what make a chunk prunable by filters.
+    pub fn has_bounds(&self) -> bool {
+        self.min.is_some() && self.max.is_some()
+    }
+}
+
+fn bound(name: &str, value: Option<ArrayRef>) -> PolarsResult<Option<String>> {
+    let Some(array) = value else {
+        return Ok(None);
+    };
+    let series = Series::from_arrow(name.into(), array)?;
+    Ok(match series.get(0)? {
+        AnyValue::Null => None,
+        AnyValue::String(text) => Some(text.to_string()),
+        value => Some(value.to_string()),
+    })
+}
+
+/// The statistics of each top-level column in row group `index` of `file`. Nested
+/// columns have no statistics of their own and are listed without values.
+pub fn chunk_statistics(file: &FileMetadata, index: usize) -> PolarsResult<Vec<ChunkStats>> {
+    let schema = infer_schema(file)?;
+    let group = &file.row_groups[index];
+    let mut stats = Vec::new();
+    for field in schema.iter_values() {
+        let mut chunk = ChunkStats {
+            column: field.name.to_string(),
+            min: None,
+            max: None,
+            null_count: None,
+        };
+        if let Some(mut columns) = group.columns_under_root_iter(&field.name)
+            && let Some(ParquetStatistics::Column(column)) = deserialize(field, &mut columns)?
+        {
+            let column = column.into_arrow()?;
+            chunk.min = bound(&field.name, column.min_value)?;
+            chunk.max = bound(&field.name, column.max_value)?;
+            chunk.null_count = column.null_count;
+        }
+        stats.push(chunk);
+    }
+    Ok(stats)
+}
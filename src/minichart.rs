@@ -0,0 +1,163 @@
+//! Small column-shape charts shown in the header popover.
+
+use eframe::egui::{Align, Layout, Pos2, Rect, Sense, Ui, Vec2};
+use polars::prelude::*;
+use std::collections::HashMap;
+
+const BINS: usize = 24;
+const TOP_VALUES: usize = 5;
+const CHART_SIZE: Vec2 = Vec2::new(220.0, 60.0);
+
+pub enum MiniChart {
+    /// Value counts per equal-width bin between `min` and `max`.
+    Histogram {
+        counts: Vec<usize>,
+        min: String,
+        max: String,
+    },
+    /// Like a histogram, but over dates or datetimes.
+    Timeline {
+        counts: Vec<usize>,
+        start: String,
+        end: String,
+    },
+    /// Most frequent values with their counts.
+    TopValues(Vec<(String, usize)>),
+}
+
+fn bin_counts(values: &[f64], min: f64, max: f64) -> Vec<usize> {
+    let mut counts = vec![0; BINS];
+    let width = (max - min) / BINS as f64;
+    for &value in values {
+        let bin = if width > 0.0 {
+            (((value - min) / width) as usize).min(BINS - 1)
+        } else {
+            0
+        };
+        counts[bin] += 1;
+    }
+    counts
+}
+
+fn min_max(values: impl Iterator<Item = f64>) -> Option<(f64, f64)> {
+    values.fold(None, |acc, v| match acc {
+        None => Some((v, v)),
+        Some((lo, hi)) => Some((lo.min(v), hi.max(v))),
+    })
+}
+
+/// Picks a chart for the column's type: histogram for numbers, timeline for dates,
+/// and top values for everything else. Returns `None` for all-null columns.
+pub fn compute(column: &Column) -> Option<MiniChart> {
+    let dtype = column.dtype();
+    if dtype.is_primitive_numeric() || dtype.is_temporal() {
+        let physical = column.to_physical_repr().cast(&DataType::Float64).ok()?;
+        let floats = physical.f64().ok()?;
+        let values: Vec<f64> = floats
+            .into_iter()
+            .flatten()
+            .filter(|v| v.is_finite())
+            .collect();
+        let (min, max) = min_max(values.iter().copied())?;
+        let counts = bin_counts(&values, min, max);
+        let series = column.as_materialized_series();
+        let label = |scalar: PolarsResult<Scalar>| {
+            scalar
+                .map(|s| s.as_any_value().to_string())
+                .unwrap_or_default()
+        };
+        let (min_label, max_label) = (label(series.min_reduce()), label(series.max_reduce()));
+        return Some(if dtype.is_temporal() {
+            MiniChart::Timeline {
+                counts,
+                start: min_label,
+                end: max_label,
+            }
+        } else {
+            MiniChart::Histogram {
+                counts,
+                min: min_label,
+                max: max_label,
+            }
+        });
+    }
+
+    let top = top_values(column, TOP_VALUES);
+    if top.is_empty() {
+        return None;
+    }
+    Some(MiniChart::TopValues(top))
+}
+
+/// The `limit` most frequent non-null values of a column as text, most frequent first.
+pub fn top_values(column: &Column, limit: usize) -> Vec<(String, usize)> {
+    let Ok(strings) = column.cast(&DataType::String) else {
+        return Vec::new();
+    };
+    let Ok(values) = strings.str() else {
+        return Vec::new();
+    };
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for value in values.into_iter().flatten() {
+        *counts.entry(value).or_default() += 1;
+    }
+    let mut top: Vec<(String, usize)> = counts
+        .into_iter()
+        .map(|(value, count)| (value.to_string(), count))
+        .collect();
+    top.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    top.truncate(limit);
+    top
+}
+
+fn paint_bars(ui: &mut Ui, counts: &[usize]) {
+    let (rect, _) = ui.allocate_exact_size(CHART_SIZE, Sense::hover());
+    let max = counts.iter().copied().max().unwrap_or(0).max(1) as f32;
+    let bar_width = rect.width() / counts.len() as f32;
+    let color = ui.visuals().selection.bg_fill;
+    for (i, count) in counts.iter().enumerate() {
+        let height = rect.height() * *count as f32 / max;
+        let x = rect.left() + i as f32 * bar_width;
+        let bar = Rect::from_min_max(
+            Pos2::new(x + 0.5, rect.bottom() - height),
+            Pos2::new(x + bar_width - 0.5, rect.bottom()),
+        );
+        ui.painter().rect_filled(bar, 0.0, color);
+    }
+    ui.painter().hline(
+        rect.x_range(),
+        rect.bottom(),
+        ui.visuals().widgets.noninteractive.bg_stroke,
+    );
+}
+
+pub fn show(ui: &mut Ui, chart: &MiniChart) {
+    match chart {
+        MiniChart::Histogram { counts, min, max } => {
+            paint_bars(ui, counts);
+            ui.horizontal(|ui| {
+                ui.weak(min);
+                ui.with_layout(Layout::right_to_left(Align::Min), |ui| ui.weak(max));
+            });
+        }
+        MiniChart::Timeline { counts, start, end } => {
+            paint_bars(ui, counts);
+            ui.weak(format!("{} – {}", start, end));
+        }
+        MiniChart::TopValues(top) => {
+            let max = top.first().map(|(_, count)| *count).unwrap_or(1).max(1) as f32;
+            let color = ui.visuals().selection.bg_fill;
+            for (value, count) in top {
+                ui.horizontal(|ui| {
+                    let (rect, _) = ui.allocate_exact_size(Vec2::new(80.0, 12.0), Sense::hover());
+                    let bar = Rect::from_min_size(
+                        rect.min,
+                        Vec2::new(rect.width() * *count as f32 / max, rect.height()),
+                    );
+                    ui.painter().rect_filled(bar, 0.0, color);
+                    ui.label(format!("{} ({})", value, count));
+                });
+            }
+        }
+    }
+}
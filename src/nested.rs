@@ -0,0 +1,33 @@
+//! Flattening of nested Parquet columns: exploding lists into rows and unnesting
+//! structs into one column per field.
+
+use polars::prelude::*;
+
+pub fn is_list(dtype: &DataType) -> bool {
+    matches!(dtype, DataType::List(_) | DataType::Array(..))
+}
+
+pub fn is_struct(dtype: &DataType) -> bool {
+    matches!(dtype, DataType::Struct(_))
+}
+
+/// Returns a copy of `df` with one row per element of the list column `name`; the other
+/// columns repeat, and empty or null lists keep a single row with a null element.
+pub fn explode(df: &DataFrame, name: &str) -> PolarsResult<DataFrame> {
+    df.explode([name])
+}
+
+/// Returns a copy of `df` with the struct column `name` replaced by one column per field,
+/// named `name.field` so they cannot collide with existing columns.
+pub fn unnest(df: &DataFrame, name: &str) -> PolarsResult<DataFrame> {
+    let column = df.column(name)?;
+    let fields = column.struct_()?.fields_as_series();
+    let position = df.get_column_index(name).unwrap();
+    let mut df = df.drop(name)?;
+    for (offset, mut field) in fields.into_iter().enumerate() {
+        let field_name = format!("{}.{}", name, field.name());
+        field.rename(field_name.into());
+        df.insert_column(position + offset, field.into_column())?;
+    }
+    Ok(df)
+}
@@ -0,0 +1,214 @@
+//! Errors, warnings and confirmations shown as toasts that fade on their own, and kept in
+//! a log for the rest of the session.
+
+use crate::panels;
+use chrono::{DateTime, Local};
+use eframe::egui::{
+    self, Align2, CollapsingHeader, Color32, Context, Frame, Id, RichText, ScrollArea, Ui,
+};
+use std::time::Duration;
+use web_time::Instant;
+
+/// How long a toast stays up; errors stay twice as long.
+const TOAST_DURATION: Duration = Duration::from_secs(5);
+
+/// Toasts shown at once; older ones are only in the log.
+const MAX_TOASTS: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Level {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Level {
+    fn color(self, ui: &Ui) -> Color32 {
+        match self {
+            Level::Info => ui.visuals().text_color(),
+            Level::Warning => ui.visuals().warn_fg_color,
+            Level::Error => ui.visuals().error_fg_color,
+        }
+    }
+
+    fn icon(self) -> &'static str {
+        match self {
+            Level::Info => "ℹ",
+            Level::Warning => "⚠",
+            Level::Error => "⛔",
+        }
+    }
+}
+
+pub struct Notification {
+    pub level: Level,
+    pub message: String,
+    pub time: DateTime<Local>,
+    shown: Instant,
+    dismissed: bool,
+}
+
+impl Notification {
+    fn summary(&self) -> &str {
+        self.message.lines().next().unwrap_or_default()
+    }
+
+    fn timestamp(&self) -> String {
+        self.time.format("%H:%M:%S").to_string()
+    }
+
+    fn expired(&self) -> bool {
+        let duration = match self.level {
+            Level::Error => TOAST_DURATION * 2,
+            _ => TOAST_DURATION,
+        };
+        self.dismissed || self.shown.elapsed() > duration
+    }
+}
+
+#[derive(Default)]
+pub struct Notifications {
+    log: Vec<Notification>,
+    pub log_open: bool,
+    pub log_detached: bool,
+}
+
+impl Notifications {
+    pub fn push(&mut self, level: Level, message: impl Into<String>) {
+        let message = message.into();
+        match level {
+            Level::Info => tracing::info!("{}", message),
+            Level::Warning => tracing::warn!("{}", message),
+            Level::Error => tracing::error!("{}", message),
+        }
+        self.log.push(Notification {
+            level,
+            message,
+            time: Local::now(),
+            shown: Instant::now(),
+            dismissed: false,
+        });
+    }
+
+    pub fn error(&mut self, message: impl Into<String>) {
+        self.push(Level::Error, message);
+    }
+
+    pub fn warning(&mut self, message: impl Into<String>) {
+        self.push(Level::Warning, message);
+    }
+
+    pub fn info(&mut self, message: impl Into<String>) {
+        self.push(Level::Info, message);
+    }
+
+    pub fn error_count(&self) -> usize {
+        self.log
+            .iter()
+            .filter(|notification| notification.level == Level::Error)
+            .count()
+    }
+
+    /// Draws the current toasts in the bottom-right corner, newest at the bottom.
+    pub fn show_toasts(&mut self, ctx: &Context) {
+        let active: Vec<usize> = (0..self.log.len())
+            .filter(|&i| !self.log[i].expired())
+            .collect();
+        if active.is_empty() {
+            return;
+        }
+        let mut open_log = false;
+        egui::Area::new(Id::new("toasts"))
+            .anchor(Align2::RIGHT_BOTTOM, [-12.0, -36.0])
+            .show(ctx, |ui| {
+                for &i in active.iter().rev().take(MAX_TOASTS).rev() {
+                    let notification = &mut self.log[i];
+                    Frame::popup(ui.style()).show(ui, |ui| {
+                        ui.set_max_width(420.0);
+                        ui.horizontal(|ui| {
+                            let color = notification.level.color(ui);
+                            ui.colored_label(color, notification.level.icon());
+                            ui.label(RichText::new(notification.summary()).color(color));
+                            if ui.small_button("Details").clicked() {
+                                open_log = true;
+                            }
+                            if ui.small_button("✖").clicked() {
+                                notification.dismissed = true;
+                            }
+                        });
+                    });
+                }
+            });
+        if open_log {
+            self.log_open = true;
+        }
+        // Keep repainting while toasts are up so that they go away on time.
+        ctx.request_repaint_after(Duration::from_millis(250));
+    }
+
+    /// The log of every message this session, with the full text of each.
+    pub fn show_log(&mut self, ctx: &Context) {
+        let mut open = self.log_open;
+        let mut detached = self.log_detached;
+        let mut clear = false;
+        panels::show_panel(
+            ctx,
+            "Messages",
+            [520.0, 360.0],
+            &mut open,
+            &mut detached,
+            |ui| {
+                ui.horizontal(|ui| {
+                    if ui.button("Copy all").clicked() {
+                        let text: Vec<String> = self
+                            .log
+                            .iter()
+                            .map(|n| format!("{} {:?}: {}", n.timestamp(), n.level, n.message))
+                            .collect();
+                        ui.ctx().copy_text(text.join("\n"));
+                    }
+                    if ui.button("Clear").clicked() {
+                        clear = true;
+                    }
+                });
+                ui.separator();
+                if self.log.is_empty() {
+                    ui.weak("No messages");
+                    return;
+                }
+                ScrollArea::vertical()
+                    .auto_shrink([false, false])
+                    .show(ui, |ui| {
+                        for (i, notification) in self.log.iter().enumerate().rev() {
+                            let color = notification.level.color(ui);
+                            let title = RichText::new(format!(
+                                "{} {} {}",
+                                notification.timestamp(),
+                                notification.level.icon(),
+                                notification.summary()
+                            ))
+                            .color(color);
+                            CollapsingHeader::new(title)
+                                .id_salt(("message", i))
+                                .show(ui, |ui| {
+                                    let mut text = notification.message.as_str();
+                                    ui.add(
+                                        egui::TextEdit::multiline(&mut text)
+                                            .code_editor()
+                                            .desired_width(f32::INFINITY),
+                                    );
+                                    if ui.button("Copy").clicked() {
+                                        ui.ctx().copy_text(notification.message.clone());
+                                    }
+                                });
+                        }
+                    });
+            },
+        );
+        if clear {
+            self.log.clear();
+        }
+        self.log_open = open;
+        self.log_detached = detached;
+    }
+}
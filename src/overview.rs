@@ -0,0 +1,139 @@
+//! Dataset-level summary shown in the Overview tab.
+
+use crate::format::format_bytes;
+use eframe::egui::{self, Color32, Rect, Sense, Ui, Vec2};
+use polars::prelude::*;
+use std::collections::BTreeMap;
+
+/// Number of row ranges in the null heatmap.
+const SEGMENTS: usize = 60;
+
+pub struct ColumnOverview {
+    pub name: String,
+    pub dtype: String,
+    pub null_count: usize,
+    /// Share of null values in each of `SEGMENTS` equal row ranges.
+    pub null_segments: Vec<f32>,
+}
+
+pub struct Overview {
+    pub rows: usize,
+    pub columns: Vec<ColumnOverview>,
+    pub dtypes: Vec<(String, usize)>,
+    pub estimated_size: usize,
+}
+
+fn null_segments(column: &Column) -> Vec<f32> {
+    let height = column.len();
+    if height == 0 || column.null_count() == 0 {
+        return vec![0.0; SEGMENTS.min(height.max(1))];
+    }
+    let segments = SEGMENTS.min(height);
+    let mut nulls = vec![0usize; segments];
+    let mut sizes = vec![0usize; segments];
+    let is_null = column.is_null();
+    for (row, null) in is_null.into_iter().enumerate() {
+        let segment = row * segments / height;
+        sizes[segment] += 1;
+        if null == Some(true) {
+            nulls[segment] += 1;
+        }
+    }
+    nulls
+        .iter()
+        .zip(&sizes)
+        .map(|(n, size)| *n as f32 / (*size).max(1) as f32)
+        .collect()
+}
+
+/// Summarizes `df`, leaving out the `skip` column (the synthetic row index).
+pub fn compute(df: &DataFrame, skip: &str) -> Overview {
+    let mut dtypes: BTreeMap<String, usize> = BTreeMap::new();
+    let columns: Vec<ColumnOverview> = df
+        .get_columns()
+        .iter()
+        .filter(|column| column.name().as_str() != skip)
+        .map(|column| {
+            let dtype = column.dtype().to_string();
+            *dtypes.entry(dtype.clone()).or_default() += 1;
+            ColumnOverview {
+                name: column.name().to_string(),
+                dtype,
+                null_count: column.null_count(),
+                null_segments: null_segments(column),
+            }
+        })
+        .collect();
+    let mut dtypes: Vec<(String, usize)> = dtypes.into_iter().collect();
+    dtypes.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    Overview {
+        rows: df.height(),
+        columns,
+        dtypes,
+        estimated_size: df.estimated_size(),
+    }
+}
+
+fn heatmap_color(ui: &Ui, null_share: f32) -> Color32 {
+    if null_share == 0.0 {
+        ui.visuals().faint_bg_color
+    } else {
+        Color32::from_rgb(0xd0, 0x3a, 0x3a).gamma_multiply(0.25 + 0.75 * null_share)
+    }
+}
+
+pub fn show(ui: &mut Ui, overview: &Overview) {
+    egui::Grid::new("overview_totals").show(ui, |ui| {
+        ui.label("Rows");
+        ui.strong(overview.rows.to_string());
+        ui.end_row();
+        ui.label("Columns");
+        ui.strong(overview.columns.len().to_string());
+        ui.end_row();
+        ui.label("Memory (estimated)");
+        ui.strong(format_bytes(overview.estimated_size as f64));
+        ui.end_row();
+    });
+
+    ui.add_space(8.0);
+    ui.heading("Types");
+    egui::Grid::new("overview_dtypes")
+        .striped(true)
+        .show(ui, |ui| {
+            for (dtype, count) in &overview.dtypes {
+                ui.label(dtype);
+                ui.label(count.to_string());
+                ui.end_row();
+            }
+        });
+
+    ui.add_space(8.0);
+    ui.heading("Nulls");
+    ui.weak("Each bar spans the file top to bottom; red marks row ranges with nulls.");
+    egui::Grid::new("overview_nulls")
+        .striped(true)
+        .show(ui, |ui| {
+            ui.strong("Column");
+            ui.strong("Type");
+            ui.strong("Nulls");
+            ui.strong("");
+            ui.end_row();
+            for column in &overview.columns {
+                ui.label(&column.name);
+                ui.weak(&column.dtype);
+                let share = column.null_count as f64 / overview.rows.max(1) as f64;
+                ui.label(format!("{} ({:.1}%)", column.null_count, share * 100.0));
+                let (rect, _) = ui.allocate_exact_size(Vec2::new(300.0, 14.0), Sense::hover());
+                let width = rect.width() / column.null_segments.len().max(1) as f32;
+                for (i, null_share) in column.null_segments.iter().enumerate() {
+                    let cell = Rect::from_min_size(
+                        rect.min + Vec2::new(i as f32 * width, 0.0),
+                        Vec2::new(width, rect.height()),
+                    );
+                    ui.painter()
+                        .rect_filled(cell, 0.0, heatmap_color(ui, *null_share));
+                }
+                ui.end_row();
+            }
+        });
+}
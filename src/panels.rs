@@ -0,0 +1,59 @@
+use eframe::egui::{CentralPanel, Context, Ui, ViewportBuilder, ViewportId, Window};
+
+/// Shows a tool panel either as a window inside the main viewport or, when `detached`,
+/// as a separate OS window that can be moved to another monitor.
+///
+/// Closing the OS window closes the panel; the dock button puts it back into the main window.
+pub fn show_panel(
+    ctx: &Context,
+    title: &str,
+    default_size: [f32; 2],
+    open: &mut bool,
+    detached: &mut bool,
+    mut add_contents: impl FnMut(&mut Ui),
+) {
+    if !*open {
+        return;
+    }
+
+    if *detached {
+        ctx.show_viewport_immediate(
+            ViewportId::from_hash_of(title),
+            ViewportBuilder::default()
+                .with_title(title)
+                .with_inner_size(default_size),
+            |ctx, _class| {
+                CentralPanel::default().show(ctx, |ui| {
+                    if ui
+                        .button("⬋ Dock")
+                        .on_hover_text("Move back into the main window")
+                        .clicked()
+                    {
+                        *detached = false;
+                    }
+                    ui.separator();
+                    add_contents(ui);
+                });
+                if ctx.input(|i| i.viewport().close_requested()) {
+                    *open = false;
+                }
+            },
+        );
+    } else {
+        Window::new(title)
+            .default_size(default_size)
+            .collapsible(false)
+            .open(open)
+            .show(ctx, |ui| {
+                if ui
+                    .button("⬈ Pop out")
+                    .on_hover_text("Open in a separate window")
+                    .clicked()
+                {
+                    *detached = true;
+                }
+                ui.separator();
+                add_contents(ui);
+            });
+    }
+}
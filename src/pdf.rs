@@ -0,0 +1,162 @@
+use polars::prelude::*;
+use printpdf::{BuiltinFont, Line, Mm, PdfDocument, Point};
+use std::error::Error;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+// A4 landscape
+const PAGE_WIDTH: f32 = 297.0;
+const PAGE_HEIGHT: f32 = 210.0;
+const MARGIN: f32 = 12.0;
+
+const FONT_SIZE: f32 = 7.0;
+const LINE_HEIGHT: f32 = 4.0;
+/// Width of one Courier glyph at `FONT_SIZE`, in millimetres (0.6 em).
+const CHAR_WIDTH: f32 = FONT_SIZE * 0.6 * 25.4 / 72.0;
+const MAX_CELL_CHARS: usize = 40;
+const COLUMN_GAP_CHARS: usize = 2;
+
+/// Builtin PDF fonts only cover Latin-1, so anything outside it is replaced.
+fn printable(text: &str, max_chars: usize) -> String {
+    let mut out: String = text
+        .chars()
+        .map(|c| {
+            if (c as u32) < 0x100 && !c.is_control() {
+                c
+            } else {
+                '?'
+            }
+        })
+        .collect();
+    if out.chars().count() > max_chars {
+        out = out.chars().take(max_chars.saturating_sub(3)).collect();
+        out.push_str("...");
+    }
+    out
+}
+
+/// Splits columns into groups that each fit the page width; groups wider than the
+/// page are printed on consecutive pages, like a spreadsheet print-out.
+fn column_groups(widths: &[usize]) -> Vec<std::ops::Range<usize>> {
+    let max_chars = ((PAGE_WIDTH - 2.0 * MARGIN) / CHAR_WIDTH) as usize;
+    let mut groups = Vec::new();
+    let mut start = 0;
+    let mut used = 0;
+    for (i, width) in widths.iter().enumerate() {
+        let needed = width + COLUMN_GAP_CHARS;
+        if used + needed > max_chars && i > start {
+            groups.push(start..i);
+            start = i;
+            used = 0;
+        }
+        used += needed;
+    }
+    if start < widths.len() {
+        groups.push(start..widths.len());
+    }
+    groups
+}
+
+/// Renders `columns` of `df` into a paginated PDF with the header repeated on every
+/// page and page numbers in the footer.
+pub fn write_pdf(
+    df: &DataFrame,
+    columns: &[String],
+    title: &str,
+    path: &Path,
+) -> Result<(), Box<dyn Error>> {
+    let source_columns = columns
+        .iter()
+        .map(|name| df.column(name))
+        .collect::<PolarsResult<Vec<_>>>()?;
+    let rows: Vec<Vec<String>> = (0..df.height())
+        .map(|row| {
+            source_columns
+                .iter()
+                .map(|column| {
+                    let text = column.get(row).map(|v| v.to_string()).unwrap_or_default();
+                    printable(&text, MAX_CELL_CHARS)
+                })
+                .collect()
+        })
+        .collect();
+    let headers: Vec<String> = columns
+        .iter()
+        .map(|name| printable(name, MAX_CELL_CHARS))
+        .collect();
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
+    for cells in &rows {
+        for (width, cell) in widths.iter_mut().zip(cells) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let rows_per_page =
+        (((PAGE_HEIGHT - 2.0 * MARGIN - 3.0 * LINE_HEIGHT) / LINE_HEIGHT) as usize).max(1);
+    let row_pages = rows.len().div_ceil(rows_per_page).max(1);
+    let groups = column_groups(&widths);
+    let total_pages = row_pages * groups.len();
+
+    let (doc, first_page, first_layer) =
+        PdfDocument::new(title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Table");
+    let font = doc.add_builtin_font(BuiltinFont::Courier)?;
+    let bold = doc.add_builtin_font(BuiltinFont::CourierBold)?;
+
+    let mut page_number = 0;
+    for row_page in 0..row_pages {
+        for group in &groups {
+            let layer = if page_number == 0 {
+                doc.get_page(first_page).get_layer(first_layer)
+            } else {
+                let (page, layer) = doc.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Table");
+                doc.get_page(page).get_layer(layer)
+            };
+            page_number += 1;
+
+            let mut y = PAGE_HEIGHT - MARGIN;
+            layer.use_text(
+                printable(title, 120),
+                FONT_SIZE + 2.0,
+                Mm(MARGIN),
+                Mm(y),
+                &bold,
+            );
+            y -= 1.5 * LINE_HEIGHT;
+
+            let mut x = MARGIN;
+            for col in group.clone() {
+                layer.use_text(headers[col].clone(), FONT_SIZE, Mm(x), Mm(y), &bold);
+                x += (widths[col] + COLUMN_GAP_CHARS) as f32 * CHAR_WIDTH;
+            }
+            layer.add_line(Line {
+                points: vec![
+                    (Point::new(Mm(MARGIN), Mm(y - 1.0)), false),
+                    (Point::new(Mm(x), Mm(y - 1.0)), false),
+                ],
+                is_closed: false,
+            });
+            y -= LINE_HEIGHT;
+
+            for cells in rows
+                .iter()
+                .skip(row_page * rows_per_page)
+                .take(rows_per_page)
+            {
+                let mut x = MARGIN;
+                for col in group.clone() {
+                    layer.use_text(cells[col].clone(), FONT_SIZE, Mm(x), Mm(y), &font);
+                    x += (widths[col] + COLUMN_GAP_CHARS) as f32 * CHAR_WIDTH;
+                }
+                y -= LINE_HEIGHT;
+            }
+
+            let footer = format!("Page {} of {}", page_number, total_pages);
+            let footer_x = PAGE_WIDTH - MARGIN - footer.len() as f32 * CHAR_WIDTH;
+            layer.use_text(footer, FONT_SIZE, Mm(footer_x), Mm(MARGIN / 2.0), &font);
+        }
+    }
+
+    doc.save(&mut BufWriter::new(File::create(path)?))?;
+    Ok(())
+}
@@ -0,0 +1,154 @@
+use polars::prelude::*;
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Number of non-null values inspected per column when scanning.
+const SAMPLE_SIZE: usize = 1000;
+
+/// Share of sampled values that must match before a column is flagged.
+const MATCH_THRESHOLD: f64 = 0.5;
+
+static EMAIL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[\w.+-]+@[\w-]+(\.[\w-]+)+$").unwrap());
+static PHONE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\+?\(?[0-9][0-9 ().-]{5,18}[0-9]$").unwrap());
+static NATIONAL_ID: LazyLock<Regex> = LazyLock::new(|| {
+    // US SSN, UK National Insurance number
+    Regex::new(r"^([0-9]{3}-[0-9]{2}-[0-9]{4}|[A-Z]{2}[0-9]{6}[A-D])$").unwrap()
+});
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PiiKind {
+    Email,
+    Phone,
+    NationalId,
+}
+
+impl std::fmt::Display for PiiKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PiiKind::Email => write!(f, "Email"),
+            PiiKind::Phone => write!(f, "Phone number"),
+            PiiKind::NationalId => write!(f, "National ID"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PiiFinding {
+    pub column: String,
+    pub kind: PiiKind,
+    pub match_ratio: f64,
+}
+
+/// A bare run of digits is more often an ID, order number or timestamp than a phone
+/// number, so phone numbers need a leading `+` or separators between the digit groups.
+fn has_phone_punctuation(value: &str) -> bool {
+    value.starts_with('+')
+        || value.contains([' ', '(', ')', '-'])
+        || value.matches('.').count() >= 2
+}
+
+fn classify(value: &str) -> Option<PiiKind> {
+    let value = value.trim();
+    if EMAIL.is_match(value) {
+        Some(PiiKind::Email)
+    } else if NATIONAL_ID.is_match(value) {
+        Some(PiiKind::NationalId)
+    } else if PHONE.is_match(value)
+        && has_phone_punctuation(value)
+        && value.chars().filter(char::is_ascii_digit).count() >= 7
+    {
+        Some(PiiKind::Phone)
+    } else {
+        None
+    }
+}
+
+/// Flags string columns whose sampled values mostly look like personal data.
+pub fn scan(df: &DataFrame) -> Vec<PiiFinding> {
+    let mut findings = Vec::new();
+    for column in df.get_columns() {
+        let Ok(values) = column.str() else {
+            continue;
+        };
+
+        let mut sampled = 0;
+        let mut counts = [0usize; 3];
+        for value in values.into_iter().flatten().take(SAMPLE_SIZE) {
+            sampled += 1;
+            match classify(value) {
+                Some(PiiKind::Email) => counts[0] += 1,
+                Some(PiiKind::Phone) => counts[1] += 1,
+                Some(PiiKind::NationalId) => counts[2] += 1,
+                None => {}
+            }
+        }
+        if sampled == 0 {
+            continue;
+        }
+
+        let (kind, count) = [PiiKind::Email, PiiKind::Phone, PiiKind::NationalId]
+            .into_iter()
+            .zip(counts)
+            .max_by_key(|(_, count)| *count)
+            .unwrap();
+        let match_ratio = count as f64 / sampled as f64;
+        if match_ratio >= MATCH_THRESHOLD {
+            findings.push(PiiFinding {
+                column: column.name().to_string(),
+                kind,
+                match_ratio,
+            });
+        }
+    }
+    findings
+}
+
+/// Redacts a value while keeping its shape, e.g. `jane@mail.com` becomes `****@****.***`.
+pub fn mask_value(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| if c.is_alphanumeric() { '*' } else { c })
+        .collect()
+}
+
+/// Returns a copy of `df` with every value in `columns` redacted by [`mask_value`].
+pub fn mask_dataframe<'a>(
+    df: &DataFrame,
+    columns: impl IntoIterator<Item = &'a String>,
+) -> PolarsResult<DataFrame> {
+    let mut masked = df.clone();
+    for name in columns {
+        let Ok(column) = df.column(name) else {
+            continue;
+        };
+        let values = column.cast(&DataType::String)?;
+        let redacted: StringChunked = values
+            .str()?
+            .into_iter()
+            .map(|value| value.map(mask_value))
+            .collect();
+        masked.replace(name, redacted.with_name(name.into()).into_series())?;
+    }
+    Ok(masked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numeric_ids_are_not_phone_numbers() {
+        let df = df!(
+            "order_id" => ["10023456", "10023457", "1712345678", "99887766554"],
+            "phone" => ["+44 20 7946 0958", "(555) 123-4567", "555.123.4567", "+15551234567"],
+        )
+        .unwrap();
+        let findings = scan(&df);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].column, "phone");
+        assert_eq!(findings[0].kind, PiiKind::Phone);
+        assert_eq!(findings[0].match_ratio, 1.0);
+    }
+}
@@ -0,0 +1,41 @@
+//! Pivot tables: one row per combination of index values, one column per value of
+//! another column, each cell aggregating a value column.
+
+use crate::aggregate::Aggregation;
+use polars::lazy::frame::pivot::pivot_stable;
+use polars::prelude::*;
+
+/// Pivoting on a column with more distinct values than this would make an unreadably
+/// wide table, so it is refused.
+pub const MAX_PIVOT_COLUMNS: usize = 500;
+
+/// Pivots `df`: rows are the distinct `index` values, columns the distinct values of `on`,
+/// and each cell the `aggregation` of `values` for that pair. Rows are sorted by index.
+pub fn pivot(
+    df: &DataFrame,
+    index: &[String],
+    on: &str,
+    values: &str,
+    aggregation: Aggregation,
+) -> PolarsResult<DataFrame> {
+    if index.is_empty() {
+        polars_bail!(InvalidOperation: "select at least one row column");
+    }
+    let distinct = df.column(on)?.n_unique()?;
+    if distinct > MAX_PIVOT_COLUMNS {
+        polars_bail!(
+            InvalidOperation: "{} has {} distinct values; pivot on a column with at most {}",
+            on, distinct, MAX_PIVOT_COLUMNS
+        );
+    }
+    let result = pivot_stable(
+        df,
+        [on],
+        Some(index.iter().map(String::as_str)),
+        Some([values]),
+        true,
+        Some(aggregation.expr(values)),
+        None,
+    )?;
+    result.sort(index.to_vec(), SortMultipleOptions::default())
+}
@@ -0,0 +1,193 @@
+//! The pieces shared by the plots, which are drawn with egui_plot: their state between
+//! frames, brushing, tick labels and colours.
+
+use eframe::egui::{Color32, Stroke};
+use egui_plot::{GridMark, PlotPoint, PlotPoints, PlotUi, Polygon};
+use polars::prelude::{AnyValue, DataType, TimeUnit};
+use std::ops::RangeInclusive;
+
+/// A rectangle in data coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bounds {
+    pub min_x: f64,
+    pub max_x: f64,
+    pub min_y: f64,
+    pub max_y: f64,
+}
+
+impl Bounds {
+    /// The smallest bounds holding every finite point, or `None` if there are none.
+    pub fn from_points(points: impl IntoIterator<Item = [f64; 2]>) -> Option<Self> {
+        points
+            .into_iter()
+            .filter(|[x, y]| x.is_finite() && y.is_finite())
+            .fold(None, |bounds: Option<Bounds>, [x, y]| {
+                Some(match bounds {
+                    None => Bounds {
+                        min_x: x,
+                        max_x: x,
+                        min_y: y,
+                        max_y: y,
+                    },
+                    Some(b) => Bounds {
+                        min_x: b.min_x.min(x),
+                        max_x: b.max_x.max(x),
+                        min_y: b.min_y.min(y),
+                        max_y: b.max_y.max(y),
+                    },
+                })
+            })
+    }
+
+    pub fn width(&self) -> f64 {
+        self.max_x - self.min_x
+    }
+
+    pub fn height(&self) -> f64 {
+        self.max_y - self.min_y
+    }
+}
+
+/// What a plot needs kept between frames, besides the zoom and pan egui_plot
+/// remembers itself.
+#[derive(Debug, Default)]
+pub struct PlotState {
+    reset: bool,
+    brush_start: Option<PlotPoint>,
+}
+
+impl PlotState {
+    /// Fits the data again on the next frame, e.g. after switching columns.
+    pub fn reset(&mut self) {
+        self.reset = true;
+        self.brush_start = None;
+    }
+
+    /// `plot`, told to fit the data again if [`PlotState::reset`] was called.
+    pub fn apply<'a>(&mut self, plot: egui_plot::Plot<'a>) -> egui_plot::Plot<'a> {
+        if std::mem::take(&mut self.reset) {
+            plot.reset()
+        } else {
+            plot
+        }
+    }
+
+    /// Lets shift-drag select a region: draws it while dragging and returns it on the frame
+    /// the drag ends. Call inside `Plot::show`, from a plot that does not pan while shift
+    /// is held.
+    pub fn brush(&mut self, plot_ui: &mut PlotUi) -> Option<Bounds> {
+        let response = plot_ui.response();
+        let (started, stopped) = (response.drag_started(), response.drag_stopped());
+        if started && plot_ui.ctx().input(|i| i.modifiers.shift) {
+            self.brush_start = plot_ui.pointer_coordinate();
+        }
+        let start = self.brush_start?;
+        let end = plot_ui.pointer_coordinate().unwrap_or(start);
+        let color = plot_ui.ctx().style().visuals.selection.bg_fill;
+        let corners = vec![
+            [start.x, start.y],
+            [end.x, start.y],
+            [end.x, end.y],
+            [start.x, end.y],
+        ];
+        plot_ui.polygon(
+            Polygon::new(PlotPoints::new(corners))
+                .fill_color(color.gamma_multiply(0.25))
+                .stroke(Stroke::new(1.0, color))
+                .allow_hover(false),
+        );
+        if !stopped {
+            return None;
+        }
+        self.brush_start = None;
+        let size = plot_ui.screen_from_plot(start) - plot_ui.screen_from_plot(end);
+        if size.x.abs() <= 2.0 || size.y.abs() <= 2.0 {
+            return None;
+        }
+        Bounds::from_points([[start.x, start.y], [end.x, end.y]])
+    }
+}
+
+/// Tick label with as many decimals as the tick step needs.
+pub fn format_number(value: f64, step: f64) -> String {
+    let decimals = (-step.log10().floor()).max(0.0) as usize;
+    if value.abs() >= 1e6 || (value != 0.0 && value.abs() < 1e-4 && decimals > 6) {
+        format!("{:.2e}", value)
+    } else {
+        format!("{:.*}", decimals, value)
+    }
+}
+
+/// Tick labels for a column: dates and times as such, everything else as numbers.
+/// Temporal values are plotted as their physical integers.
+pub fn axis_format(dtype: &DataType) -> impl Fn(f64, f64) -> String + use<> {
+    // Milliseconds per unit of the physical value.
+    let scale = match dtype {
+        DataType::Date => Some(86_400_000.0),
+        DataType::Datetime(TimeUnit::Nanoseconds, _) => Some(1e-6),
+        DataType::Datetime(TimeUnit::Microseconds, _) => Some(1e-3),
+        DataType::Datetime(TimeUnit::Milliseconds, _) => Some(1.0),
+        _ => None,
+    };
+    move |value, step| {
+        let Some(time) =
+            scale.and_then(|scale| chrono::DateTime::from_timestamp_millis((value * scale) as i64))
+        else {
+            return format_number(value, step);
+        };
+        let step_millis = step * scale.unwrap_or(1.0);
+        let pattern = if step_millis >= 86_400_000.0 {
+            "%Y-%m-%d"
+        } else if step_millis >= 60_000.0 {
+            "%m-%d %H:%M"
+        } else {
+            "%H:%M:%S%.3f"
+        };
+        time.format(pattern).to_string()
+    }
+}
+
+/// [`axis_format`] as an egui_plot axis formatter.
+pub fn axis_formatter(
+    dtype: &DataType,
+) -> impl Fn(GridMark, &RangeInclusive<f64>) -> String + use<> {
+    let format = axis_format(dtype);
+    move |mark, _| format(mark.value, mark.step_size)
+}
+
+/// `label` cut to at most `max_chars` characters, for an axis; tooltips show it in full.
+pub fn truncate_label(label: &str, max_chars: usize) -> String {
+    if label.chars().count() > max_chars {
+        label.chars().take(max_chars - 1).chain(['…']).collect()
+    } else {
+        label.to_string()
+    }
+}
+
+/// One plotted value in full, for tooltips: dates and times as such, integers without
+/// decimals.
+pub fn format_value(dtype: &DataType, value: f64) -> String {
+    match dtype {
+        DataType::Date => AnyValue::Date(value as i32).to_string(),
+        DataType::Datetime(unit, _) => AnyValue::Datetime(value as i64, *unit, None).to_string(),
+        dtype if dtype.is_integer() => format!("{}", value as i64),
+        _ => format!("{}", value),
+    }
+}
+
+/// Distinct colours for categories, cycling after the last.
+pub fn palette(index: usize) -> Color32 {
+    const COLORS: [Color32; 10] = [
+        Color32::from_rgb(0x4e, 0x79, 0xa7),
+        Color32::from_rgb(0xf2, 0x8e, 0x2b),
+        Color32::from_rgb(0xe1, 0x57, 0x59),
+        Color32::from_rgb(0x76, 0xb7, 0xb2),
+        Color32::from_rgb(0x59, 0xa1, 0x4f),
+        Color32::from_rgb(0xed, 0xc9, 0x48),
+        Color32::from_rgb(0xb0, 0x7a, 0xa1),
+        Color32::from_rgb(0xff, 0x9d, 0xa7),
+        Color32::from_rgb(0x9c, 0x75, 0x5f),
+        Color32::from_rgb(0xba, 0xb0, 0xac),
+    ];
+    COLORS[index % COLORS.len()]
+}
@@ -0,0 +1,165 @@
+//! Per-column profile of a dataset, for a first look at unknown data.
+
+use crate::describe::min_max_text;
+use crate::minichart::{self, MiniChart};
+use eframe::egui::{self, Ui};
+use polars::prelude::*;
+use std::fmt::Write;
+
+const TOP_VALUES: usize = 5;
+
+pub struct ColumnProfile {
+    pub name: String,
+    pub dtype: String,
+    pub null_fraction: f64,
+    pub distinct: usize,
+    pub top_values: Vec<(String, usize)>,
+    pub min: Option<String>,
+    pub max: Option<String>,
+    /// Histogram or timeline for numeric and temporal columns.
+    pub chart: Option<MiniChart>,
+}
+
+/// Profiles every column of `df` except `skip`.
+pub fn profile(df: &DataFrame, skip: &str) -> Vec<ColumnProfile> {
+    df.get_columns()
+        .iter()
+        .filter(|column| column.name().as_str() != skip)
+        .map(|column| {
+            let dtype = column.dtype();
+            let charted = dtype.is_primitive_numeric() || dtype.is_temporal();
+            ColumnProfile {
+                name: column.name().to_string(),
+                dtype: dtype.to_string(),
+                null_fraction: if column.is_empty() {
+                    0.0
+                } else {
+                    column.null_count() as f64 / column.len() as f64
+                },
+                distinct: column.n_unique().unwrap_or(0),
+                top_values: minichart::top_values(column, TOP_VALUES),
+                min: min_max_text(column, false),
+                max: min_max_text(column, true),
+                chart: charted.then(|| minichart::compute(column)).flatten(),
+            }
+        })
+        .collect()
+}
+
+pub fn show(ui: &mut Ui, profiles: &[ColumnProfile]) {
+    egui::Grid::new("profile")
+        .striped(true)
+        .spacing([16.0, 8.0])
+        .show(ui, |ui| {
+            for header in ["Column", "Type", "Nulls", "Distinct", "Range", "Top values"] {
+                ui.strong(header);
+            }
+            ui.strong("Distribution");
+            ui.end_row();
+            for profile in profiles {
+                ui.label(&profile.name);
+                ui.label(&profile.dtype);
+                ui.label(format!("{:.1}%", profile.null_fraction * 100.0));
+                ui.label(profile.distinct.to_string());
+                ui.vertical(|ui| {
+                    if let (Some(min), Some(max)) = (&profile.min, &profile.max) {
+                        ui.label(min);
+                        ui.label(max);
+                    }
+                });
+                ui.vertical(|ui| {
+                    for (value, count) in &profile.top_values {
+                        ui.label(format!("{} ({})", value, count));
+                    }
+                });
+                match &profile.chart {
+                    Some(chart) => minichart::show(ui, chart),
+                    None => {
+                        ui.label("");
+                    }
+                }
+                ui.end_row();
+            }
+        });
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn svg_bars(counts: &[usize]) -> String {
+    const WIDTH: f64 = 160.0;
+    const HEIGHT: f64 = 40.0;
+    let max = counts.iter().copied().max().unwrap_or(0).max(1) as f64;
+    let bar_width = WIDTH / counts.len() as f64;
+    let mut svg = format!(
+        r#"<svg width="{}" height="{}" viewBox="0 0 {} {}">"#,
+        WIDTH, HEIGHT, WIDTH, HEIGHT
+    );
+    for (i, count) in counts.iter().enumerate() {
+        let height = HEIGHT * *count as f64 / max;
+        let _ = write!(
+            svg,
+            r##"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" fill="#4a7bd0"/>"##,
+            i as f64 * bar_width,
+            HEIGHT - height,
+            (bar_width - 1.0).max(0.5),
+            height
+        );
+    }
+    svg.push_str("</svg>");
+    svg
+}
+
+/// Renders the profile as a self-contained HTML page.
+pub fn to_html(title: &str, rows: usize, profiles: &[ColumnProfile]) -> String {
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>{title}</title>\n\
+         <style>body{{font-family:sans-serif}}table{{border-collapse:collapse}}\
+         td,th{{border:1px solid #ccc;padding:4px 8px;vertical-align:top;text-align:left}}\
+         .range,.top{{font-size:small}}</style></head><body>\n\
+         <h1>{title}</h1>\n<p>{rows} rows, {columns} columns</p>\n<table>\n\
+         <tr><th>Column</th><th>Type</th><th>Nulls</th><th>Distinct</th><th>Range</th>\
+         <th>Top values</th><th>Distribution</th></tr>\n",
+        title = escape(title),
+        rows = rows,
+        columns = profiles.len()
+    );
+    for profile in profiles {
+        let range = match (&profile.min, &profile.max) {
+            (Some(min), Some(max)) => format!("{}<br>{}", escape(min), escape(max)),
+            _ => String::new(),
+        };
+        let top = profile
+            .top_values
+            .iter()
+            .map(|(value, count)| format!("{} ({})", escape(value), count))
+            .collect::<Vec<_>>()
+            .join("<br>");
+        let chart = match &profile.chart {
+            Some(MiniChart::Histogram { counts, .. } | MiniChart::Timeline { counts, .. }) => {
+                svg_bars(counts)
+            }
+            _ => String::new(),
+        };
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td>{:.1}%</td><td>{}</td>\
+             <td class=\"range\">{}</td><td class=\"top\">{}</td><td>{}</td></tr>",
+            escape(&profile.name),
+            escape(&profile.dtype),
+            profile.null_fraction * 100.0,
+            profile.distinct,
+            range,
+            top,
+            chart
+        );
+    }
+    html.push_str("</table>\n</body></html>\n");
+    html
+}
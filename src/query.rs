@@ -0,0 +1,228 @@
+//! SQL over loaded files. Queries run on a [`QueryEngine`]: Polars SQL is built in,
+//! DuckDB, for window functions and CTEs Polars does not support, comes with the `duckdb`
+//! feature, and other engines can be plugged in by implementing the trait. The engine is
+//! picked with `tablr query --engine <name>` or, in the window, with the script console's
+//! `df.sql(sql, name)`.
+
+use polars::prelude::*;
+use polars::sql::SQLContext;
+use std::error::Error;
+
+/// Runs SQL against named tables.
+pub trait QueryEngine {
+    /// The name that picks the engine, e.g. with `tablr query --engine <name>`.
+    fn name(&self) -> &str;
+
+    /// Runs `sql` with each of `tables` queryable under its name. Tables are lazy, so
+    /// nothing has been read yet: an engine can collect them, or read the files behind a
+    /// plain scan itself.
+    fn execute(
+        &self,
+        tables: Vec<(String, LazyFrame)>,
+        sql: &str,
+    ) -> Result<DataFrame, Box<dyn Error>>;
+}
+
+/// Polars' own SQL dialect.
+pub struct PolarsSql;
+
+impl QueryEngine for PolarsSql {
+    fn name(&self) -> &str {
+        "polars"
+    }
+
+    fn execute(
+        &self,
+        tables: Vec<(String, LazyFrame)>,
+        sql: &str,
+    ) -> Result<DataFrame, Box<dyn Error>> {
+        let mut context = SQLContext::new();
+        for (name, table) in tables {
+            context.register(&name, table);
+        }
+        Ok(context.execute(sql)?.collect()?)
+    }
+}
+
+/// DuckDB, in memory. A table that is a plain scan of Parquet files is read from those
+/// files by DuckDB, which then reads only the columns and row groups the query needs.
+/// Other tables are handed over as Parquet files in a temporary directory, and the
+/// result comes back the same way, so that DuckDB and Polars need not agree on an Arrow
+/// version.
+#[cfg(all(feature = "duckdb", not(target_arch = "wasm32")))]
+pub struct DuckDb;
+
+#[cfg(all(feature = "duckdb", not(target_arch = "wasm32")))]
+impl QueryEngine for DuckDb {
+    fn name(&self) -> &str {
+        "duckdb"
+    }
+
+    fn execute(
+        &self,
+        tables: Vec<(String, LazyFrame)>,
+        sql: &str,
+    ) -> Result<DataFrame, Box<dyn Error>> {
+        use std::fs::{self, File};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        /// Removes the directory again however the query ends.
+        struct TempDir(std::path::PathBuf);
+        impl Drop for TempDir {
+            fn drop(&mut self) {
+                let _ = fs::remove_dir_all(&self.0);
+            }
+        }
+
+        static QUERIES: AtomicUsize = AtomicUsize::new(0);
+        let dir = TempDir(std::env::temp_dir().join(format!(
+            "tablr-duckdb-{}-{}",
+            std::process::id(),
+            QUERIES.fetch_add(1, Ordering::Relaxed)
+        )));
+        fs::create_dir_all(&dir.0)?;
+        let quote = |path: &std::path::Path| path.display().to_string().replace('\'', "''");
+        let connection = duckdb::Connection::open_in_memory()?;
+        for (i, (name, table)) in tables.into_iter().enumerate() {
+            let paths = match parquet_sources(&table) {
+                Some(paths) => paths,
+                None => {
+                    let path = dir.0.join(format!("{}.parquet", i));
+                    ParquetWriter::new(File::create(&path)?).finish(&mut table.collect()?)?;
+                    vec![path]
+                }
+            };
+            let paths: Vec<String> = paths
+                .iter()
+                .map(|path| format!("'{}'", quote(path)))
+                .collect();
+            connection.execute_batch(&format!(
+                "CREATE VIEW \"{}\" AS SELECT * FROM read_parquet([{}])",
+                name.replace('"', "\"\""),
+                paths.join(", ")
+            ))?;
+        }
+        let result = dir.0.join("result.parquet");
+        let query = sql.trim().trim_end_matches(';');
+        connection.execute_batch(&format!(
+            "COPY ({}) TO '{}' (FORMAT parquet)",
+            query,
+            quote(&result)
+        ))?;
+        Ok(ParquetReader::new(File::open(&result)?).finish()?)
+    }
+}
+
+/// The Parquet files `table` reads, when it does nothing else, as absolute paths.
+#[cfg(all(feature = "duckdb", not(target_arch = "wasm32")))]
+fn parquet_sources(table: &LazyFrame) -> Option<Vec<std::path::PathBuf>> {
+    match &table.logical_plan {
+        DslPlan::Scan {
+            sources: ScanSources::Paths(paths),
+            scan_type,
+            ..
+        } if matches!(**scan_type, FileScan::Parquet { .. }) => paths
+            .iter()
+            .map(|path| std::path::absolute(path).ok())
+            .collect(),
+        _ => None,
+    }
+}
+
+/// The engines queries can be run on. The first is the default; Polars SQL is always
+/// available.
+pub struct QueryEngines {
+    engines: Vec<Box<dyn QueryEngine>>,
+}
+
+impl Default for QueryEngines {
+    fn default() -> Self {
+        Self {
+            engines: vec![
+                Box::new(PolarsSql),
+                #[cfg(all(feature = "duckdb", not(target_arch = "wasm32")))]
+                Box::new(DuckDb),
+            ],
+        }
+    }
+}
+
+impl QueryEngines {
+    /// Adds `engine`, replacing one of the same name.
+    pub fn with(mut self, engine: impl QueryEngine + 'static) -> Self {
+        self.engines
+            .retain(|existing| existing.name() != engine.name());
+        self.engines.push(Box::new(engine));
+        self
+    }
+
+    /// The engine called `name`, or the default without one.
+    pub fn get(&self, name: Option<&str>) -> Result<&dyn QueryEngine, String> {
+        let engine = match name {
+            Some(name) => self.engines.iter().find(|engine| engine.name() == name),
+            None => self.engines.first(),
+        };
+        engine.map(|engine| engine.as_ref()).ok_or_else(|| {
+            format!(
+                "unknown query engine {}; available: {}",
+                name.unwrap_or_default(),
+                self.names().join(", ")
+            )
+        })
+    }
+
+    /// The names that pick an engine, the default first.
+    pub fn names(&self) -> Vec<&str> {
+        self.engines.iter().map(|engine| engine.name()).collect()
+    }
+}
+
+#[cfg(all(test, feature = "duckdb"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn duckdb_runs_window_functions_over_polars_tables() {
+        let df = df!("group" => ["a", "a", "b"], "price" => [3, 1, 2]).unwrap();
+        let result = DuckDb
+            .execute(
+                vec![("my table".to_string(), df.lazy())],
+                "SELECT \"group\", price, row_number() OVER (PARTITION BY \"group\" ORDER BY price) AS rank \
+                 FROM \"my table\" ORDER BY \"group\", price;",
+            )
+            .unwrap();
+        let ranks: Vec<Option<i64>> = result
+            .column("rank")
+            .unwrap()
+            .i64()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(ranks, [Some(1), Some(2), Some(1)]);
+    }
+
+    #[test]
+    fn duckdb_reads_scanned_parquet_files_itself() {
+        let path = std::env::temp_dir().join(format!("tablr-query-{}.parquet", std::process::id()));
+        let mut df = df!("a" => [1i64, 2, 3]).unwrap();
+        ParquetWriter::new(std::fs::File::create(&path).unwrap())
+            .finish(&mut df)
+            .unwrap();
+        let table = crate::loader::scan(std::slice::from_ref(&path)).unwrap();
+        assert!(parquet_sources(&table).is_some());
+        assert!(parquet_sources(&table.clone().select([col("a")])).is_none());
+        let result = DuckDb.execute(
+            vec![("t".to_string(), table)],
+            "SELECT sum(a) AS total FROM t",
+        );
+        std::fs::remove_file(&path).unwrap();
+        let total = result
+            .unwrap()
+            .column("total")
+            .unwrap()
+            .get(0)
+            .unwrap()
+            .extract::<i64>();
+        assert_eq!(total, Some(6));
+    }
+}
@@ -0,0 +1,66 @@
+//! Custom display for cell values, such as decoded geohashes or protobuf blobs, without
+//! changes to the grid itself.
+
+use eframe::egui::Ui;
+use polars::prelude::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Draws one cell. Only called for non-null values, and not for masked columns.
+///
+/// Closures taking `(ui, dtype, value)` are renderers too.
+pub trait CellRenderer {
+    fn render(&self, ui: &mut Ui, dtype: &DataType, value: &AnyValue);
+}
+
+impl<F> CellRenderer for F
+where
+    F: Fn(&mut Ui, &DataType, &AnyValue),
+{
+    fn render(&self, ui: &mut Ui, dtype: &DataType, value: &AnyValue) {
+        self(ui, dtype, value)
+    }
+}
+
+/// Renderers by column name and by column type. A column's own renderer wins over its
+/// type's; cells with neither are shown as text.
+#[derive(Clone, Default)]
+pub struct CellRenderers {
+    columns: HashMap<String, Arc<dyn CellRenderer>>,
+    dtypes: Vec<(DataType, Arc<dyn CellRenderer>)>,
+}
+
+impl CellRenderers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn for_column(mut self, name: &str, renderer: impl CellRenderer + 'static) -> Self {
+        self.columns.insert(name.to_string(), Arc::new(renderer));
+        self
+    }
+
+    /// Renders every column of type `dtype`, e.g. `DataType::Binary`. Types must match
+    /// exactly, including a datetime's unit and time zone.
+    pub fn for_dtype(mut self, dtype: DataType, renderer: impl CellRenderer + 'static) -> Self {
+        self.dtypes.retain(|(existing, _)| *existing != dtype);
+        self.dtypes.push((dtype, Arc::new(renderer)));
+        self
+    }
+
+    pub fn get(&self, column: &str, dtype: &DataType) -> Option<&dyn CellRenderer> {
+        self.columns
+            .get(column)
+            .or_else(|| {
+                self.dtypes
+                    .iter()
+                    .find(|(existing, _)| existing == dtype)
+                    .map(|(_, renderer)| renderer)
+            })
+            .map(|renderer| renderer.as_ref())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty() && self.dtypes.is_empty()
+    }
+}
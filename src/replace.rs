@@ -0,0 +1,90 @@
+use polars::prelude::*;
+use regex::{NoExpand, Regex};
+
+/// Number of changed rows listed in a preview.
+const PREVIEW_ROWS: usize = 20;
+
+pub struct ReplacePreview {
+    /// Number of values that the replacement changes.
+    pub affected: usize,
+    /// Row number, old value and new value for the first changed rows.
+    pub samples: Vec<(String, String, String)>,
+}
+
+/// A find-and-replace over a string column. With `regex` off, `find` is matched
+/// literally and `replacement` is inserted as is; with it on, `replacement` may refer to
+/// capture groups as `${1}` or `${name}`.
+pub struct Replace {
+    pattern: Regex,
+    replacement: String,
+    regex: bool,
+}
+
+impl Replace {
+    pub fn new(find: &str, replacement: &str, regex: bool) -> PolarsResult<Self> {
+        if find.is_empty() {
+            polars_bail!(ComputeError: "nothing to find");
+        }
+        let pattern = if regex {
+            Regex::new(find).map_err(|e| polars_err!(ComputeError: "{}", e))?
+        } else {
+            Regex::new(&regex::escape(find)).unwrap()
+        };
+        Ok(Self {
+            pattern,
+            replacement: replacement.to_string(),
+            regex,
+        })
+    }
+
+    /// The replaced text, or `None` when `text` has no match.
+    fn apply(&self, text: &str) -> Option<String> {
+        if !self.pattern.is_match(text) {
+            return None;
+        }
+        let replaced = if self.regex {
+            self.pattern.replace_all(text, self.replacement.as_str())
+        } else {
+            self.pattern.replace_all(text, NoExpand(&self.replacement))
+        };
+        Some(replaced.into_owned())
+    }
+
+    /// Lists the values in column `name` that would change, labelled by `row_index`.
+    pub fn preview(
+        &self,
+        df: &DataFrame,
+        name: &str,
+        row_index: &str,
+    ) -> PolarsResult<ReplacePreview> {
+        let values = df.column(name)?.str()?;
+        let rows = df.column(row_index)?;
+        let mut affected = 0;
+        let mut samples = Vec::new();
+        for (position, value) in values.into_iter().enumerate() {
+            let Some((before, after)) = value.and_then(|v| Some((v, self.apply(v)?))) else {
+                continue;
+            };
+            if after == before {
+                continue;
+            }
+            affected += 1;
+            if samples.len() < PREVIEW_ROWS {
+                samples.push((rows.get(position)?.to_string(), before.to_string(), after));
+            }
+        }
+        Ok(ReplacePreview { affected, samples })
+    }
+
+    /// Returns a copy of `df` with the replacement applied to every value of `name`.
+    pub fn apply_to(&self, df: &DataFrame, name: &str) -> PolarsResult<DataFrame> {
+        let values = df.column(name)?.str()?;
+        let replaced: StringChunked = values
+            .into_iter()
+            .map(|value| value.map(|v| self.apply(v).unwrap_or_else(|| v.to_string())))
+            .collect();
+        let mut df = df.clone();
+        df.replace(name, replaced.with_name(name.into()).into_series())?;
+        Ok(df)
+    }
+}
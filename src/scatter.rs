@@ -0,0 +1,165 @@
+//! Points for the scatter plot: two numeric or date columns, optionally coloured by a
+//! category column.
+
+use crate::minichart;
+use crate::plot::{self, Bounds};
+use eframe::egui::{Color32, Pos2};
+use egui_plot::{PlotPoint, PlotTransform, PlotUi, Points};
+use polars::prelude::*;
+use std::collections::HashMap;
+
+/// More points than this are sampled evenly, to keep painting fast.
+const MAX_POINTS: usize = 20_000;
+/// Categories past the most frequent ones share the "Other" colour.
+const MAX_CATEGORIES: usize = 9;
+const POINT_RADIUS: f32 = 2.5;
+
+pub struct ScatterData {
+    pub points: Vec<[f64; 2]>,
+    /// The row of each point in the data it was computed from.
+    pub rows: Vec<usize>,
+    /// Index into `categories` for each point; empty without a colour column.
+    pub groups: Vec<usize>,
+    pub categories: Vec<String>,
+    /// Whether the last category is "Other", for values past the most frequent.
+    pub has_other: bool,
+    /// Rows with both values, before sampling.
+    pub total: usize,
+}
+
+/// Whether a column can be an axis: numbers, dates and times.
+pub fn is_plottable(dtype: &DataType) -> bool {
+    dtype.is_primitive_numeric() || dtype.is_temporal()
+}
+
+/// Values as floats; dates and times as their physical integers.
+pub fn to_f64(column: &Column) -> PolarsResult<Float64Chunked> {
+    Ok(column
+        .to_physical_repr()
+        .cast(&DataType::Float64)?
+        .f64()?
+        .clone())
+}
+
+pub fn compute(df: &DataFrame, x: &str, y: &str, color: Option<&str>) -> PolarsResult<ScatterData> {
+    let xs = to_f64(df.column(x)?)?;
+    let ys = to_f64(df.column(y)?)?;
+    let rows: Vec<usize> = xs
+        .iter()
+        .zip(ys.iter())
+        .enumerate()
+        .filter(|(_, (x, y))| x.is_some_and(f64::is_finite) && y.is_some_and(f64::is_finite))
+        .map(|(row, _)| row)
+        .collect();
+    let total = rows.len();
+    let step = total.div_ceil(MAX_POINTS).max(1);
+    let rows: Vec<usize> = rows.into_iter().step_by(step).collect();
+    let points: Vec<[f64; 2]> = rows
+        .iter()
+        .map(|&row| [xs.get(row).unwrap_or(0.0), ys.get(row).unwrap_or(0.0)])
+        .collect();
+
+    let (groups, categories, has_other) = match color {
+        Some(color) => {
+            let column = df.column(color)?;
+            let mut categories: Vec<String> = minichart::top_values(column, MAX_CATEGORIES)
+                .into_iter()
+                .map(|(value, _)| value)
+                .collect();
+            let index: HashMap<String, usize> = categories
+                .iter()
+                .enumerate()
+                .map(|(i, value)| (value.clone(), i))
+                .collect();
+            let strings = column.cast(&DataType::String)?;
+            let strings = strings.str()?;
+            let other = categories.len();
+            let groups: Vec<usize> = rows
+                .iter()
+                .map(|&row| {
+                    strings
+                        .get(row)
+                        .and_then(|value| index.get(value).copied())
+                        .unwrap_or(other)
+                })
+                .collect();
+            let has_other = groups.contains(&other);
+            if has_other {
+                categories.push("Other".to_string());
+            }
+            (groups, categories, has_other)
+        }
+        None => (Vec::new(), Vec::new(), false),
+    };
+
+    Ok(ScatterData {
+        points,
+        rows,
+        groups,
+        categories,
+        has_other,
+        total,
+    })
+}
+
+impl ScatterData {
+    fn category_color(&self, group: usize) -> Color32 {
+        if self.has_other && group + 1 == self.categories.len() {
+            Color32::GRAY
+        } else {
+            plot::palette(group)
+        }
+    }
+
+    /// Adds the points, one series per category so that the legend names them.
+    pub fn show(&self, plot_ui: &mut PlotUi) {
+        if self.categories.is_empty() {
+            plot_ui.points(
+                Points::new(self.points.clone())
+                    .radius(POINT_RADIUS)
+                    .color(plot::palette(0).gamma_multiply(0.8)),
+            );
+            return;
+        }
+        for (group, category) in self.categories.iter().enumerate() {
+            let points: Vec<[f64; 2]> = self
+                .points
+                .iter()
+                .zip(&self.groups)
+                .filter(|(_, g)| **g == group)
+                .map(|(point, _)| *point)
+                .collect();
+            plot_ui.points(
+                Points::new(points)
+                    .radius(POINT_RADIUS)
+                    .color(self.category_color(group).gamma_multiply(0.8))
+                    .name(category),
+            );
+        }
+    }
+
+    /// The point closest to `pointer`, if it is within a few screen points.
+    pub fn nearest(&self, transform: &PlotTransform, pointer: Pos2) -> Option<usize> {
+        self.points
+            .iter()
+            .enumerate()
+            .map(|(i, &[x, y])| {
+                let pos = transform.position_from_point(&PlotPoint::new(x, y));
+                (i, pos.distance_sq(pointer))
+            })
+            .filter(|(_, distance)| *distance <= 36.0)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| i)
+    }
+}
+
+/// Keeps the rows inside `bounds`, comparing dates and times as their physical values
+/// like the plot does.
+pub fn brush_filter(x: &str, y: &str, bounds: &Bounds) -> Expr {
+    let physical = |name: &str| col(name).to_physical().cast(DataType::Float64);
+    physical(x)
+        .gt_eq(lit(bounds.min_x))
+        .and(physical(x).lt_eq(lit(bounds.max_x)))
+        .and(physical(y).gt_eq(lit(bounds.min_y)))
+        .and(physical(y).lt_eq(lit(bounds.max_y)))
+}
@@ -0,0 +1,54 @@
+//! Compares the schemas of several Parquet files from their footers, so mismatches can
+//! be seen before the files are read together.
+
+use polars::prelude::*;
+use std::path::PathBuf;
+
+/// One column and its type in each file, `None` where the file lacks it.
+pub struct ColumnComparison {
+    pub name: String,
+    pub types: Vec<Option<DataType>>,
+}
+
+impl ColumnComparison {
+    pub fn is_missing(&self, file: usize) -> bool {
+        self.types[file].is_none()
+    }
+
+    /// A type that differs from the type of the first file that has the column.
+    pub fn is_mismatched(&self, file: usize) -> bool {
+        let first = self.types.iter().flatten().next();
+        self.types[file]
+            .as_ref()
+            .is_some_and(|dtype| Some(dtype) != first)
+    }
+
+    pub fn is_consistent(&self) -> bool {
+        (0..self.types.len()).all(|file| !self.is_missing(file) && !self.is_mismatched(file))
+    }
+}
+
+/// Lists every column of the given files in order of first appearance.
+pub fn compare(paths: &[PathBuf]) -> PolarsResult<Vec<ColumnComparison>> {
+    let schemas = paths
+        .iter()
+        .map(|path| LazyFrame::scan_parquet(path, ScanArgsParquet::default())?.collect_schema())
+        .collect::<PolarsResult<Vec<_>>>()?;
+    let mut columns: Vec<ColumnComparison> = Vec::new();
+    for (file, schema) in schemas.iter().enumerate() {
+        for (name, dtype) in schema.iter() {
+            let position = match columns.iter().position(|column| column.name == *name) {
+                Some(position) => position,
+                None => {
+                    columns.push(ColumnComparison {
+                        name: name.to_string(),
+                        types: vec![None; paths.len()],
+                    });
+                    columns.len() - 1
+                }
+            };
+            columns[position].types[file] = Some(dtype.clone());
+        }
+    }
+    Ok(columns)
+}
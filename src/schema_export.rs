@@ -0,0 +1,180 @@
+//! Writes a frame's schema as JSON or as a `CREATE TABLE` statement for loading the same
+//! data into a database or warehouse.
+
+use crate::dictionary::DataDictionary;
+use polars::prelude::*;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SqlDialect {
+    PostgreSql,
+    MySql,
+    Sqlite,
+    DuckDb,
+    BigQuery,
+}
+
+impl SqlDialect {
+    pub const ALL: [SqlDialect; 5] = [
+        SqlDialect::PostgreSql,
+        SqlDialect::MySql,
+        SqlDialect::Sqlite,
+        SqlDialect::DuckDb,
+        SqlDialect::BigQuery,
+    ];
+
+    fn quote(self, identifier: &str) -> String {
+        match self {
+            SqlDialect::MySql | SqlDialect::BigQuery => {
+                format!("`{}`", identifier.replace('`', "``"))
+            }
+            _ => format!("\"{}\"", identifier.replace('"', "\"\"")),
+        }
+    }
+
+    fn text(self) -> &'static str {
+        match self {
+            SqlDialect::BigQuery => "STRING",
+            SqlDialect::DuckDb => "VARCHAR",
+            _ => "TEXT",
+        }
+    }
+
+    /// The closest column type, falling back to text for types the dialect lacks.
+    fn column_type(self, dtype: &DataType) -> String {
+        use SqlDialect::*;
+        let name = match (self, dtype) {
+            (Sqlite, DataType::Boolean) => "INTEGER",
+            (BigQuery, DataType::Boolean) => "BOOL",
+            (_, DataType::Boolean) => "BOOLEAN",
+            (Sqlite, dtype) if dtype.is_integer() => "INTEGER",
+            (BigQuery, dtype) if dtype.is_integer() => "INT64",
+            (MySql, DataType::UInt64) => "BIGINT UNSIGNED",
+            (DuckDb, DataType::UInt64) => "UBIGINT",
+            (_, DataType::UInt64) => "NUMERIC(20, 0)",
+            (_, DataType::Int8 | DataType::Int16 | DataType::UInt8) => "SMALLINT",
+            (_, DataType::Int32 | DataType::UInt16) => "INTEGER",
+            (_, DataType::Int64 | DataType::UInt32) => "BIGINT",
+            (_, DataType::Int128) => "NUMERIC(39, 0)",
+            (BigQuery, DataType::Float32 | DataType::Float64) => "FLOAT64",
+            (Sqlite, DataType::Float32 | DataType::Float64) => "REAL",
+            (MySql, DataType::Float32) => "FLOAT",
+            (MySql | DuckDb, DataType::Float64) => "DOUBLE",
+            (_, DataType::Float32) => "REAL",
+            (_, DataType::Float64) => "DOUBLE PRECISION",
+            (Sqlite, DataType::Decimal(..)) => "NUMERIC",
+            (_, DataType::Decimal(precision, scale)) => {
+                return format!(
+                    "NUMERIC({}, {})",
+                    precision.unwrap_or(38),
+                    scale.unwrap_or(0)
+                );
+            }
+            (PostgreSql, DataType::Binary) => "BYTEA",
+            (BigQuery, DataType::Binary) => "BYTES",
+            (_, DataType::Binary) => "BLOB",
+            (Sqlite, DataType::Date | DataType::Datetime(..) | DataType::Time) => "TEXT",
+            (_, DataType::Date) => "DATE",
+            (_, DataType::Time) => "TIME",
+            (MySql, DataType::Datetime(_, None)) => "DATETIME",
+            (BigQuery, DataType::Datetime(_, None)) => "DATETIME",
+            (PostgreSql | DuckDb, DataType::Datetime(_, Some(_))) => "TIMESTAMP WITH TIME ZONE",
+            (_, DataType::Datetime(..)) => "TIMESTAMP",
+            (Sqlite | MySql, DataType::Duration(_)) => "BIGINT",
+            (_, DataType::Duration(_)) => "INTERVAL",
+            (PostgreSql | DuckDb, DataType::List(inner) | DataType::Array(inner, _)) => {
+                return format!("{}[]", self.column_type(inner));
+            }
+            (BigQuery, DataType::List(inner) | DataType::Array(inner, _)) => {
+                return format!("ARRAY<{}>", self.column_type(inner));
+            }
+            (PostgreSql, DataType::Struct(_)) => "JSONB",
+            (Sqlite, DataType::List(_) | DataType::Array(..) | DataType::Struct(_)) => "TEXT",
+            (_, DataType::List(_) | DataType::Array(..) | DataType::Struct(_)) => "JSON",
+            _ => self.text(),
+        };
+        name.to_string()
+    }
+}
+
+impl std::fmt::Display for SqlDialect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SqlDialect::PostgreSql => write!(f, "PostgreSQL"),
+            SqlDialect::MySql => write!(f, "MySQL"),
+            SqlDialect::Sqlite => write!(f, "SQLite"),
+            SqlDialect::DuckDb => write!(f, "DuckDB"),
+            SqlDialect::BigQuery => write!(f, "BigQuery"),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonColumn<'a> {
+    name: &'a str,
+    #[serde(rename = "type")]
+    dtype: String,
+    nullable: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unit: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct JsonSchema<'a> {
+    columns: Vec<JsonColumn<'a>>,
+}
+
+/// Lists the columns of `df` with their Polars types, whether they contain nulls, and
+/// their data dictionary entries. `skip` names a column to leave out.
+pub fn to_json(df: &DataFrame, dictionary: &DataDictionary, skip: &str) -> String {
+    let columns: Vec<JsonColumn> = df
+        .get_columns()
+        .iter()
+        .filter(|column| column.name().as_str() != skip)
+        .map(|column| {
+            let doc = dictionary.get(column.name());
+            JsonColumn {
+                name: column.name(),
+                dtype: column.dtype().to_string(),
+                nullable: column.null_count() > 0,
+                description: doc
+                    .map(|doc| doc.description.as_str())
+                    .filter(|text| !text.is_empty()),
+                unit: doc
+                    .map(|doc| doc.unit.as_str())
+                    .filter(|text| !text.is_empty()),
+            }
+        })
+        .collect();
+    serde_json::to_string_pretty(&JsonSchema { columns }).unwrap()
+}
+
+/// A `CREATE TABLE` statement for `df` in the given dialect. Columns without nulls are
+/// declared `NOT NULL`; `skip` names a column to leave out.
+pub fn to_sql(df: &DataFrame, table: &str, dialect: SqlDialect, skip: &str) -> String {
+    let columns: Vec<String> = df
+        .get_columns()
+        .iter()
+        .filter(|column| column.name().as_str() != skip)
+        .map(|column| {
+            let not_null = if column.null_count() == 0 {
+                " NOT NULL"
+            } else {
+                ""
+            };
+            format!(
+                "    {} {}{}",
+                dialect.quote(column.name()),
+                dialect.column_type(column.dtype()),
+                not_null
+            )
+        })
+        .collect();
+    format!(
+        "CREATE TABLE {} (\n{}\n);\n",
+        dialect.quote(table),
+        columns.join(",\n")
+    )
+}
@@ -0,0 +1,228 @@
+//! The scripting console: Rhai scripts that transform the working data through a `df`
+//! variable holding it as a lazy frame, e.g.
+//!
+//! ```text
+//! let cheap = df.filter("col(\"price\") < 10");
+//! cheap.group_by(["category"], #{ price: "mean" }).sort("rows", true)
+//! ```
+//!
+//! Expressions are written as for computed columns. Nothing is read until the result is
+//! collected, so steps can be chained freely.
+
+use crate::aggregate::Aggregation;
+use crate::expression;
+use crate::query::QueryEngines;
+use polars::prelude::*;
+use rhai::{Array, Dynamic, Engine, EvalAltResult, INT, Scope};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Listed in the console for reference.
+pub const HELP: &str = "\
+df.filter(\"col(\\\"price\\\") > 10\")     keep matching rows
+df.with_column(\"name\", \"expression\")  add or replace a column
+df.select([\"a\", \"b\"]), df.drop([\"c\"])
+df.rename(\"old\", \"new\")
+df.sort(\"a\"), df.sort(\"a\", true)      ascending, descending
+df.head(n), df.tail(n), df.unique()
+df.group_by([\"key\"], #{ price: \"mean\" })
+    count, sum, mean, min, max, n_unique
+df.sql(\"SELECT … FROM df\")
+df.sql(\"SELECT … FROM df\", \"duckdb\")  on another query engine
+df.columns(), df.height(), print(df)";
+
+/// Stops runaway scripts, such as an endless loop, before they hang the window.
+const MAX_OPERATIONS: u64 = 10_000_000;
+
+type ScriptResult<T> = Result<T, Box<EvalAltResult>>;
+
+/// The working data as seen by a script.
+#[derive(Clone)]
+struct Frame(LazyFrame);
+
+fn polars_err(e: PolarsError) -> Box<EvalAltResult> {
+    e.to_string().into()
+}
+
+fn strings(values: Array) -> ScriptResult<Vec<String>> {
+    values
+        .into_iter()
+        .map(|value| {
+            value
+                .into_string()
+                .map_err(|dtype| format!("expected a column name, got {}", dtype).into())
+        })
+        .collect()
+}
+
+fn row_count(n: INT) -> ScriptResult<IdxSize> {
+    IdxSize::try_from(n).map_err(|_| format!("invalid row count {}", n).into())
+}
+
+fn aggregation(name: &str) -> ScriptResult<Aggregation> {
+    Aggregation::ALL
+        .into_iter()
+        .find(|aggregation| aggregation.suffix() == name)
+        .ok_or_else(|| {
+            let names: Vec<&str> = Aggregation::ALL.iter().map(|a| a.suffix()).collect();
+            format!("unknown aggregation \"{}\"; use {}", name, names.join(", ")).into()
+        })
+}
+
+/// Runs `query` with the frame as table `df`, on the named query engine or the default.
+fn sql(frame: &Frame, query: &str, engine: Option<&str>) -> ScriptResult<Frame> {
+    let engines = QueryEngines::default();
+    let engine = engines.get(engine)?;
+    let tables = vec![("df".to_string(), frame.0.clone())];
+    let df = engine.execute(tables, query).map_err(|e| e.to_string())?;
+    Ok(Frame(df.lazy()))
+}
+
+fn engine(printed: Rc<RefCell<Vec<String>>>) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.on_print(move |text| printed.borrow_mut().push(text.to_string()));
+    engine
+        .register_type_with_name::<Frame>("Frame")
+        .register_fn("filter", |frame: &mut Frame, predicate: &str| {
+            let expr = expression::parse(predicate).map_err(polars_err)?;
+            Ok::<_, Box<EvalAltResult>>(Frame(frame.0.clone().filter(expr)))
+        })
+        .register_fn(
+            "with_column",
+            |frame: &mut Frame, name: &str, definition: &str| {
+                let expr = expression::parse(definition).map_err(polars_err)?;
+                Ok::<_, Box<EvalAltResult>>(Frame(frame.0.clone().with_column(expr.alias(name))))
+            },
+        )
+        .register_fn("select", |frame: &mut Frame, names: Array| {
+            let names = strings(names)?;
+            Ok::<_, Box<EvalAltResult>>(Frame(
+                frame.0.clone().select(
+                    names
+                        .iter()
+                        .map(|name| col(name.as_str()))
+                        .collect::<Vec<_>>(),
+                ),
+            ))
+        })
+        .register_fn("drop", |frame: &mut Frame, names: Array| {
+            let names = strings(names)?;
+            Ok::<_, Box<EvalAltResult>>(Frame(frame.0.clone().drop(names)))
+        })
+        .register_fn("rename", |frame: &mut Frame, from: &str, to: &str| {
+            Frame(frame.0.clone().rename([from], [to], true))
+        })
+        .register_fn("sort", |frame: &mut Frame, name: &str| {
+            Frame(frame.0.clone().sort([name], SortMultipleOptions::default()))
+        })
+        .register_fn("sort", |frame: &mut Frame, name: &str, descending: bool| {
+            Frame(frame.0.clone().sort(
+                [name],
+                SortMultipleOptions::new().with_order_descending(descending),
+            ))
+        })
+        .register_fn("head", |frame: &mut Frame, n: INT| {
+            Ok::<_, Box<EvalAltResult>>(Frame(frame.0.clone().limit(row_count(n)?)))
+        })
+        .register_fn("tail", |frame: &mut Frame, n: INT| {
+            Ok::<_, Box<EvalAltResult>>(Frame(frame.0.clone().tail(row_count(n)?)))
+        })
+        .register_fn("unique", |frame: &mut Frame| {
+            Frame(
+                frame
+                    .0
+                    .clone()
+                    .unique_stable(None, UniqueKeepStrategy::First),
+            )
+        })
+        .register_fn(
+            "group_by",
+            |frame: &mut Frame, keys: Array, aggregations: rhai::Map| {
+                let keys = strings(keys)?;
+                let mut exprs = vec![len().alias(crate::group_by::ROWS_COLUMN)];
+                for (column, name) in aggregations {
+                    let name = name
+                        .into_string()
+                        .map_err(|dtype| format!("expected an aggregation name, got {}", dtype))?;
+                    let aggregation = aggregation(&name)?;
+                    exprs.push(aggregation.expr(&column).alias(format!(
+                        "{}_{}",
+                        column,
+                        aggregation.suffix()
+                    )));
+                }
+                let key_exprs: Vec<Expr> = keys.iter().map(|key| col(key.as_str())).collect();
+                Ok::<_, Box<EvalAltResult>>(Frame(
+                    frame
+                        .0
+                        .clone()
+                        .group_by(key_exprs)
+                        .agg(exprs)
+                        .sort(keys, SortMultipleOptions::default()),
+                ))
+            },
+        )
+        .register_fn("sql", |frame: &mut Frame, query: &str| {
+            sql(frame, query, None)
+        })
+        .register_fn("sql", |frame: &mut Frame, query: &str, engine: &str| {
+            sql(frame, query, Some(engine))
+        })
+        .register_fn("columns", |frame: &mut Frame| {
+            let schema = frame.0.collect_schema().map_err(polars_err)?;
+            Ok::<_, Box<EvalAltResult>>(
+                schema
+                    .iter_names()
+                    .map(|name| Dynamic::from(name.to_string()))
+                    .collect::<Array>(),
+            )
+        })
+        .register_fn("height", |frame: &mut Frame| {
+            let df = frame
+                .0
+                .clone()
+                .select([len()])
+                .collect()
+                .map_err(polars_err)?;
+            let height = df.column("len").and_then(|c| c.u32().map(|c| c.get(0)));
+            Ok::<_, Box<EvalAltResult>>(height.map_err(polars_err)?.unwrap_or(0) as INT)
+        })
+        .register_fn("to_string", |frame: &mut Frame| {
+            match frame.0.clone().limit(10).collect() {
+                Ok(df) => df.to_string(),
+                Err(e) => e.to_string(),
+            }
+        });
+    engine
+}
+
+/// What a script produced: the transformed data, if any, and anything it printed.
+pub struct ScriptOutput {
+    pub frame: Option<DataFrame>,
+    pub printed: Vec<String>,
+}
+
+/// Runs `script` on `df`. The result is the script's last value if it is a frame,
+/// otherwise whatever `df` holds at the end; a script that changes neither only prints.
+pub fn run(script: &str, df: &DataFrame) -> Result<ScriptOutput, String> {
+    let printed = Rc::new(RefCell::new(Vec::new()));
+    let engine = engine(printed.clone());
+    let input = Frame(df.clone().lazy());
+    let mut scope = Scope::new();
+    scope.push("df", input.clone());
+    let value: Dynamic = engine
+        .eval_with_scope(&mut scope, script)
+        .map_err(|e| e.to_string())?;
+    let printed = printed.take();
+    let result = value
+        .try_cast::<Frame>()
+        .or_else(|| scope.get_value::<Frame>("df"));
+    let frame = match result {
+        Some(frame) => Some(frame.0.collect().map_err(|e| e.to_string())?),
+        None => None,
+    };
+    // An unchanged `df` is not a transformation.
+    let frame = frame.filter(|frame| !frame.equals_missing(df));
+    Ok(ScriptOutput { frame, printed })
+}
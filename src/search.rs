@@ -0,0 +1,63 @@
+use polars::prelude::*;
+
+/// Finds cells in `columns` whose text contains `query`, ignoring case.
+///
+/// Matches are returned as `(row, column)` pairs in reading order, where `column` is the
+/// position within `columns`.
+pub fn find_matches(
+    df: &DataFrame,
+    columns: &[String],
+    query: &str,
+) -> PolarsResult<Vec<(usize, usize)>> {
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+    find_regex_matches(df, columns, &format!("(?i){}", regex::escape(query)))
+}
+
+/// Finds cells in `columns` whose text matches the regular expression `pattern`,
+/// returned like [`find_matches`].
+pub fn find_regex_matches(
+    df: &DataFrame,
+    columns: &[String],
+    pattern: &str,
+) -> PolarsResult<Vec<(usize, usize)>> {
+    if pattern.is_empty() || columns.is_empty() {
+        return Ok(Vec::new());
+    }
+    if let Err(e) = regex::Regex::new(pattern) {
+        polars_bail!(ComputeError: "{}", e);
+    }
+    let hits = df
+        .clone()
+        .lazy()
+        .select(
+            columns
+                .iter()
+                .map(|name| {
+                    col(name.as_str())
+                        .cast(DataType::String)
+                        .str()
+                        .contains(lit(pattern), true)
+                        .fill_null(false)
+                })
+                .collect::<Vec<_>>(),
+        )
+        .collect()?;
+
+    let masks = hits
+        .get_columns()
+        .iter()
+        .map(|column| column.bool().cloned())
+        .collect::<PolarsResult<Vec<_>>>()?;
+    let mut matches = Vec::new();
+    for (col_pos, mask) in masks.iter().enumerate() {
+        for (row, hit) in mask.into_iter().enumerate() {
+            if hit == Some(true) {
+                matches.push((row, col_pos));
+            }
+        }
+    }
+    matches.sort_unstable();
+    Ok(matches)
+}
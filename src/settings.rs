@@ -0,0 +1,126 @@
+use crate::export::FileFormat;
+use crate::keymap::Keybindings;
+use eframe::egui::ThemePreference;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// Directory holding Tablr's settings and remembered layouts.
+pub fn config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("tablr"))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    #[default]
+    System,
+    Dark,
+    Light,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 3] = [Theme::System, Theme::Dark, Theme::Light];
+
+    pub fn preference(self) -> ThemePreference {
+        match self {
+            Theme::System => ThemePreference::System,
+            Theme::Dark => ThemePreference::Dark,
+            Theme::Light => ThemePreference::Light,
+        }
+    }
+}
+
+impl std::fmt::Display for Theme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Theme::System => write!(f, "System"),
+            Theme::Dark => write!(f, "Dark"),
+            Theme::Light => write!(f, "Light"),
+        }
+    }
+}
+
+/// Shell commands run after events, receiving the affected file paths as arguments.
+/// Empty commands are skipped.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Hooks {
+    pub after_load: String,
+    pub after_export: String,
+}
+
+/// User preferences stored as `settings.toml` in the config directory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub theme: Theme,
+    pub font_size: f32,
+    /// egui zoom factor applied on top of the display's native scaling.
+    pub ui_scale: f32,
+    /// First month (1-12) of the fiscal year used for quarter and year buckets.
+    pub fiscal_year_start_month: u32,
+    /// Render http(s) URLs in string cells as links that open the browser.
+    pub clickable_links: bool,
+    /// Hand files opened from the file manager to the window that is already running.
+    pub single_instance: bool,
+    /// Row height the grid starts with; the Display dialog changes it for the session.
+    pub row_height: f32,
+    /// chrono `strftime` patterns for date and datetime cells; empty shows Polars' format.
+    pub date_format: String,
+    pub datetime_format: String,
+    /// Listed first in the Export dialog.
+    pub export_format: FileFormat,
+    pub hooks: Hooks,
+    pub keybindings: Keybindings,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            theme: Theme::default(),
+            font_size: 18.0,
+            ui_scale: 1.0,
+            fiscal_year_start_month: 1,
+            clickable_links: true,
+            single_instance: false,
+            row_height: 20.0,
+            date_format: "%Y-%m-%d".to_string(),
+            datetime_format: "%Y-%m-%d %H:%M:%S%.f".to_string(),
+            export_format: FileFormat::Csv,
+            hooks: Hooks::default(),
+            keybindings: Keybindings::default(),
+        }
+    }
+}
+
+impl Settings {
+    fn path() -> Option<PathBuf> {
+        config_dir().map(|dir| dir.join("settings.toml"))
+    }
+
+    /// Loads the settings file, or the defaults when there is none. A file that cannot be
+    /// read or parsed is an error, so that it is not silently replaced by the defaults.
+    pub fn load() -> Result<Self, String> {
+        let Some(path) = Self::path() else {
+            return Ok(Self::default());
+        };
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(format!("Could not read {}: {}", path.display(), e)),
+        };
+        toml::from_str(&contents).map_err(|e| format!("Invalid {}: {}", path.display(), e))
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no config directory"))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let contents = toml::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(path, contents)
+    }
+}
@@ -0,0 +1,125 @@
+use polars::prelude::*;
+
+/// Number of rows compared per column pair.
+const SAMPLE_SIZE: usize = 10_000;
+
+/// Share of equal values above which two columns are reported as near-identical.
+const NEAR_IDENTICAL: f64 = 0.98;
+
+/// Absolute Pearson correlation above which two numeric columns are reported.
+const CORRELATED: f64 = 0.9999;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Similarity {
+    Identical,
+    NearIdentical(f64),
+    Correlated(f64),
+}
+
+impl std::fmt::Display for Similarity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Similarity::Identical => write!(f, "Identical"),
+            Similarity::NearIdentical(ratio) => write!(f, "{:.1}% equal", ratio * 100.0),
+            Similarity::Correlated(r) => write!(f, "Correlated (r = {:.4})", r),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ColumnPair {
+    pub left: String,
+    pub right: String,
+    pub similarity: Similarity,
+}
+
+/// Pearson correlation over the rows where both values are present, or `None` when
+/// there are too few rows or a column is constant.
+pub fn pearson(a: &[Option<f64>], b: &[Option<f64>]) -> Option<f64> {
+    let pairs: Vec<(f64, f64)> = a
+        .iter()
+        .zip(b)
+        .filter_map(|(x, y)| Some(((*x)?, (*y)?)))
+        .collect();
+    if pairs.len() < 3 {
+        return None;
+    }
+    let n = pairs.len() as f64;
+    let mean_x = pairs.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = pairs.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let (mut cov, mut var_x, mut var_y) = (0.0, 0.0, 0.0);
+    for (x, y) in &pairs {
+        cov += (x - mean_x) * (y - mean_y);
+        var_x += (x - mean_x).powi(2);
+        var_y += (y - mean_y).powi(2);
+    }
+    if var_x == 0.0 || var_y == 0.0 {
+        return None;
+    }
+    Some(cov / (var_x * var_y).sqrt())
+}
+
+/// Flags pairs of columns with identical or near-identical values, and numeric pairs
+/// that are perfectly correlated. Only the first rows are compared; `skip` is ignored.
+pub fn find_similar_columns(df: &DataFrame, skip: &str) -> PolarsResult<Vec<ColumnPair>> {
+    let sample = df.head(Some(SAMPLE_SIZE));
+    let columns: Vec<&Column> = sample
+        .get_columns()
+        .iter()
+        .filter(|column| column.name().as_str() != skip)
+        .collect();
+    let mut as_text = Vec::with_capacity(columns.len());
+    let mut as_number = Vec::with_capacity(columns.len());
+    for column in &columns {
+        let text: Vec<Option<String>> = column
+            .cast(&DataType::String)?
+            .str()?
+            .into_iter()
+            .map(|v| v.map(str::to_string))
+            .collect();
+        as_text.push(text);
+        as_number.push(if column.dtype().is_primitive_numeric() {
+            Some(
+                column
+                    .cast(&DataType::Float64)?
+                    .f64()?
+                    .into_iter()
+                    .collect::<Vec<_>>(),
+            )
+        } else {
+            None
+        });
+    }
+
+    let rows = sample.height();
+    let mut pairs = Vec::new();
+    for i in 0..columns.len() {
+        for j in (i + 1)..columns.len() {
+            let equal = as_text[i]
+                .iter()
+                .zip(&as_text[j])
+                .filter(|(a, b)| a == b)
+                .count();
+            let ratio = equal as f64 / rows.max(1) as f64;
+            let similarity = if rows > 0 && equal == rows {
+                Some(Similarity::Identical)
+            } else if ratio >= NEAR_IDENTICAL {
+                Some(Similarity::NearIdentical(ratio))
+            } else if let (Some(a), Some(b)) = (&as_number[i], &as_number[j]) {
+                pearson(a, b)
+                    .filter(|r| r.abs() >= CORRELATED)
+                    .map(Similarity::Correlated)
+            } else {
+                None
+            };
+            if let Some(similarity) = similarity {
+                pairs.push(ColumnPair {
+                    left: columns[i].name().to_string(),
+                    right: columns[j].name().to_string(),
+                    similarity,
+                });
+            }
+        }
+    }
+    Ok(pairs)
+}
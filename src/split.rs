@@ -0,0 +1,50 @@
+use polars::prelude::*;
+use regex::Regex;
+
+/// Names of the columns produced by splitting `column` into `parts`.
+pub fn part_names(column: &str, parts: usize) -> Vec<String> {
+    (1..=parts).map(|i| format!("{}_{}", column, i)).collect()
+}
+
+/// Returns a copy of `df` with the string column `name` split on `delimiter` (a regular
+/// expression when `regex` is set) into `parts` new columns placed after it.
+///
+/// The last column keeps the unsplit remainder; values with fewer pieces get nulls.
+pub fn split_column(
+    df: &DataFrame,
+    name: &str,
+    delimiter: &str,
+    regex: bool,
+    parts: usize,
+) -> PolarsResult<DataFrame> {
+    if delimiter.is_empty() {
+        polars_bail!(ComputeError: "the delimiter is empty");
+    }
+    let pattern = if regex {
+        Regex::new(delimiter).map_err(|e| polars_err!(ComputeError: "{}", e))?
+    } else {
+        Regex::new(&regex::escape(delimiter)).unwrap()
+    };
+    let parts = parts.max(1);
+    let mut columns: Vec<Vec<Option<String>>> = vec![Vec::with_capacity(df.height()); parts];
+    for value in df.column(name)?.str()? {
+        let mut pieces = value
+            .into_iter()
+            .flat_map(|text| pattern.splitn(text, parts));
+        for column in &mut columns {
+            column.push(pieces.next().map(str::to_string));
+        }
+    }
+    let names = part_names(name, parts);
+    for new in &names {
+        if df.get_column_index(new).is_some() {
+            polars_bail!(Duplicate: "column {} already exists", new);
+        }
+    }
+    let mut df = df.clone();
+    let source = df.get_column_index(name).unwrap();
+    for (offset, (new, values)) in names.into_iter().zip(columns).enumerate() {
+        df.insert_column(source + 1 + offset, Column::new(new.into(), values))?;
+    }
+    Ok(df)
+}
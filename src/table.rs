@@ -0,0 +1,594 @@
+//! A sortable, filterable DataFrame grid that can be dropped into any egui app. The
+//! viewer uses it too: for its main grid through a [`TableDelegate`], and as is for the
+//! split view and the derived result tables.
+
+use crate::filter::{self, FilterType};
+use crate::render::CellRenderers;
+use eframe::egui::{
+    Align, ComboBox, CursorIcon, Id, Label, Response, RichText, ScrollArea, Sense, TextEdit,
+    TextWrapMode, Ui,
+};
+use egui_extras::{Column, TableBuilder};
+use polars::prelude::*;
+use std::collections::HashSet;
+use std::hash::Hash;
+
+/// Grid over a DataFrame with a filter bar, click-to-sort headers and hideable columns.
+/// It keeps the frame it was given and shows a filtered and sorted copy of it.
+///
+/// Keep one per frame you show and call [`DataFrameTable::show`] every frame. Clicking a
+/// row selects it. For cells beyond text and [`CellRenderers`], draw them yourself with
+/// [`DataFrameTable::show_with`].
+pub struct DataFrameTable {
+    title: String,
+    original: DataFrame,
+    view: DataFrame,
+    filter_column: Option<String>,
+    filter_type: FilterType,
+    filter_text: String,
+    sort: Option<(String, bool)>,
+    /// Columns to show in this order instead of the frame's, when set.
+    columns: Option<Vec<String>>,
+    hidden_columns: HashSet<String>,
+    show_filter_bar: bool,
+    renderers: CellRenderers,
+    error_message: Option<String>,
+    selected_row: Option<usize>,
+    grid: Grid,
+}
+
+/// Layout and scroll state of the grid, apart from the data so that a delegate can
+/// borrow the data while the grid draws.
+struct Grid {
+    id_salt: Id,
+    row_height: f32,
+    first_visible_row: usize,
+    scroll_to_row: Option<(usize, Option<Align>)>,
+    widths: Vec<f32>,
+}
+
+/// A column of the grid: one of the delegate's own in front, by position, or a column of
+/// the frame, by name.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GridColumn<'a> {
+    Leading(usize),
+    Data(&'a str),
+}
+
+/// Draws the headers and cells of a [`DataFrameTable`] for [`DataFrameTable::show_with`].
+/// The table still lays out, sizes and scrolls the grid. Rows are counted in the rows
+/// shown, i.e. in [`DataFrameTable::view`].
+pub trait TableDelegate {
+    /// Columns drawn in front of the frame's columns, such as row numbers.
+    fn leading_columns(&self) -> usize {
+        0
+    }
+
+    /// Width a column starts at; `None` fits it to the content.
+    fn initial_width(&self, _column: &str) -> Option<f32> {
+        None
+    }
+
+    /// Whether a column cuts its cells off at its width instead of growing to fit them.
+    fn clip(&self, _column: &str) -> bool {
+        true
+    }
+
+    fn header(&mut self, ui: &mut Ui, column: GridColumn<'_>);
+
+    fn cell(&mut self, ui: &mut Ui, row: usize, column: GridColumn<'_>);
+
+    /// Called after each cell is drawn, e.g. to react to clicks on it.
+    fn cell_response(&mut self, _row: usize, _column: GridColumn<'_>, _response: &Response) {}
+
+    fn is_row_selected(&self, _row: usize) -> bool {
+        false
+    }
+
+    /// Height kept free below the rows for [`TableDelegate::footer`].
+    fn footer_height(&self) -> f32 {
+        0.0
+    }
+
+    /// Drawn below the rows and scrolled sideways with them. `widths` are those of the
+    /// columns in the last frame, leading ones first.
+    fn footer(&mut self, _ui: &mut Ui, _widths: &[f32]) {}
+}
+
+/// A table without data, for filling in later with [`DataFrameTable::set_data`].
+impl Default for DataFrameTable {
+    fn default() -> Self {
+        Self::new(DataFrame::empty())
+    }
+}
+
+impl DataFrameTable {
+    pub fn new(df: DataFrame) -> Self {
+        Self {
+            title: String::new(),
+            view: df.clone(),
+            original: df,
+            filter_column: None,
+            filter_type: FilterType::Contains,
+            filter_text: String::new(),
+            sort: None,
+            columns: None,
+            hidden_columns: HashSet::new(),
+            show_filter_bar: true,
+            renderers: CellRenderers::default(),
+            error_message: None,
+            selected_row: None,
+            grid: Grid {
+                id_salt: Id::new("dataframe_table"),
+                row_height: 22.0,
+                first_visible_row: 0,
+                scroll_to_row: None,
+                widths: Vec::new(),
+            },
+        }
+    }
+
+    /// Needed when several tables are shown in the same `Ui`.
+    pub fn id_salt(mut self, id_salt: impl Hash) -> Self {
+        self.set_id_salt(id_salt);
+        self
+    }
+
+    /// Changes the id after construction. Column widths are kept per id and column list,
+    /// so a new id starts the columns at their initial widths again.
+    pub fn set_id_salt(&mut self, id_salt: impl Hash) {
+        self.grid.id_salt = Id::new(id_salt);
+    }
+
+    pub fn row_height(mut self, row_height: f32) -> Self {
+        self.grid.row_height = row_height;
+        self
+    }
+
+    /// Changes the row height after construction, e.g. when the text size changes.
+    pub fn set_row_height(&mut self, row_height: f32) {
+        self.grid.row_height = row_height;
+    }
+
+    pub fn show_filter_bar(mut self, show: bool) -> Self {
+        self.show_filter_bar = show;
+        self
+    }
+
+    /// Custom display for some columns or column types; other cells are shown as text.
+    pub fn renderers(mut self, renderers: CellRenderers) -> Self {
+        self.renderers = renderers;
+        self
+    }
+
+    /// Replaces the data, keeping the filter, sort and hidden columns where the new
+    /// frame still has those columns.
+    pub fn set_data(&mut self, df: DataFrame) {
+        let schema = df.schema();
+        if self
+            .filter_column
+            .as_ref()
+            .is_some_and(|name| !schema.contains(name))
+        {
+            self.filter_column = None;
+        }
+        if self
+            .sort
+            .as_ref()
+            .is_some_and(|(name, _)| !schema.contains(name))
+        {
+            self.sort = None;
+        }
+        self.original = df;
+        self.refresh();
+    }
+
+    /// Drops the data, keeping the title and settings.
+    pub fn clear(&mut self) {
+        self.original = DataFrame::empty();
+        self.view = DataFrame::empty();
+        self.selected_row = None;
+        self.error_message = None;
+    }
+
+    pub fn has_data(&self) -> bool {
+        self.original.width() > 0
+    }
+
+    /// A caption for the caller to show with the table; the table does not draw it.
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn set_title(&mut self, title: impl Into<String>) {
+        self.title = title.into();
+    }
+
+    /// The frame as given, before filtering and sorting.
+    pub fn data(&self) -> &DataFrame {
+        &self.original
+    }
+
+    /// The rows as currently shown, including hidden columns.
+    pub fn view(&self) -> &DataFrame {
+        &self.view
+    }
+
+    /// Keeps the rows whose `column` matches `text`; an empty `text` keeps every row.
+    pub fn set_filter(&mut self, column: &str, filter_type: FilterType, text: &str) {
+        self.filter_column = Some(column.to_string());
+        self.filter_type = filter_type;
+        self.filter_text = text.to_string();
+        self.refresh();
+    }
+
+    pub fn clear_filter(&mut self) {
+        self.filter_text.clear();
+        self.refresh();
+    }
+
+    /// Sorts by `column`, or restores the original order with `None`.
+    pub fn set_sort(&mut self, sort: Option<(&str, bool)>) {
+        self.sort = sort.map(|(name, descending)| (name.to_string(), descending));
+        self.refresh();
+    }
+
+    pub fn sort(&self) -> Option<(&str, bool)> {
+        self.sort
+            .as_ref()
+            .map(|(name, descending)| (name.as_str(), *descending))
+    }
+
+    pub fn set_column_visible(&mut self, column: &str, visible: bool) {
+        if visible {
+            self.hidden_columns.remove(column);
+        } else {
+            self.hidden_columns.insert(column.to_string());
+        }
+    }
+
+    pub fn is_column_visible(&self, column: &str) -> bool {
+        !self.hidden_columns.contains(column)
+    }
+
+    /// Shows these columns in this order rather than the frame's; `None` goes back to
+    /// the frame's order. Hidden columns stay hidden either way.
+    pub fn set_columns(&mut self, columns: Option<Vec<String>>) {
+        self.columns = columns;
+    }
+
+    /// Index of the first row drawn in the last frame, e.g. to keep two tables in step.
+    pub fn first_visible_row(&self) -> usize {
+        self.grid.first_visible_row
+    }
+
+    /// Widths of the columns drawn in the last frame, leading ones first.
+    pub fn column_widths(&self) -> &[f32] {
+        &self.grid.widths
+    }
+
+    /// Scrolls `row` of the shown rows to the top in the next frame.
+    pub fn scroll_to_row(&mut self, row: usize) {
+        self.scroll_to_row_aligned(row, Some(Align::TOP));
+    }
+
+    /// Scrolls `row` of the shown rows to `align` in the next frame, or just into view
+    /// with `None`.
+    pub fn scroll_to_row_aligned(&mut self, row: usize, align: Option<Align>) {
+        self.grid.scroll_to_row = Some((row, align));
+    }
+
+    /// Values of `columns` in the selected row, if a row is selected.
+    pub fn selected_values(&self, columns: &[String]) -> Option<Vec<AnyValue<'static>>> {
+        let row = self.selected_row?;
+        columns
+            .iter()
+            .map(|name| Some(self.view.column(name).ok()?.get(row).ok()?.into_static()))
+            .collect()
+    }
+
+    /// The last filter or sort failure, e.g. a sort on a column type Polars can't order.
+    pub fn error(&self) -> Option<&str> {
+        self.error_message.as_deref()
+    }
+
+    /// Filters the original frame, then sorts the result.
+    fn refresh(&mut self) {
+        self.selected_row = None;
+        let filtered = match &self.filter_column {
+            Some(name) => {
+                filter::filter_frame(&self.original, name, self.filter_type, &self.filter_text)
+            }
+            None => Ok(self.original.clone()),
+        };
+        let result = filtered.and_then(|df| match &self.sort {
+            Some((name, descending)) => df.sort(
+                [name.as_str()],
+                SortMultipleOptions::new().with_order_descending(*descending),
+            ),
+            None => Ok(df),
+        });
+        match result {
+            Ok(df) => {
+                self.view = df;
+                self.error_message = None;
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Table error: {}", e));
+                self.view = self.original.clone();
+            }
+        }
+    }
+
+    fn render_filter_bar(&mut self, ui: &mut Ui) {
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            ComboBox::from_id_salt(self.grid.id_salt.with("filter_column"))
+                .selected_text(self.filter_column.as_deref().unwrap_or("Select column"))
+                .show_ui(ui, |ui| {
+                    changed |= ui
+                        .selectable_value(&mut self.filter_column, None, "None")
+                        .clicked();
+                    for name in self.original.get_column_names() {
+                        changed |= ui
+                            .selectable_value(
+                                &mut self.filter_column,
+                                Some(name.to_string()),
+                                name.as_str(),
+                            )
+                            .clicked();
+                    }
+                });
+            changed |= filter::filter_type_combo(
+                ui,
+                &format!("{:?}_filter_type", self.grid.id_salt),
+                &mut self.filter_type,
+            );
+            changed |= ui
+                .add_enabled(
+                    self.filter_column.is_some(),
+                    TextEdit::singleline(&mut self.filter_text).hint_text("Filter"),
+                )
+                .changed();
+            ui.label(format!(
+                "{} of {} rows",
+                self.view.height(),
+                self.original.height()
+            ));
+        });
+        if changed {
+            self.refresh();
+        }
+    }
+
+    /// The columns to draw, in order.
+    fn shown_columns(&self) -> Vec<String> {
+        let present = self.view.schema();
+        match &self.columns {
+            Some(columns) => columns
+                .iter()
+                .filter(|name| present.contains(name))
+                .filter(|name| !self.hidden_columns.contains(*name))
+                .cloned()
+                .collect(),
+            None => self
+                .view
+                .get_column_names()
+                .iter()
+                .map(|name| name.to_string())
+                .filter(|name| !self.hidden_columns.contains(name))
+                .collect(),
+        }
+    }
+
+    fn show_bars(&mut self, ui: &mut Ui) {
+        if self.show_filter_bar {
+            self.render_filter_bar(ui);
+        }
+        if let Some(error) = &self.error_message {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut Ui) {
+        self.show_bars(ui);
+        let columns = self.shown_columns();
+        let mut cells = PlainCells {
+            view: &self.view,
+            renderers: &self.renderers,
+            sort: self
+                .sort
+                .as_ref()
+                .map(|(name, descending)| (name.as_str(), *descending)),
+            selected_row: self.selected_row,
+            clicked_header: None,
+            clicked_row: None,
+        };
+        self.grid.show(ui, &columns, self.view.height(), &mut cells);
+        let (clicked_header, clicked_row) = (cells.clicked_header, cells.clicked_row);
+        if clicked_row.is_some() {
+            self.selected_row = clicked_row;
+        }
+        // Same cycle as the viewer's headers: ascending, then descending.
+        if let Some(name) = clicked_header {
+            let descending = matches!(self.sort(), Some((sorted, false)) if sorted == name);
+            self.set_sort(Some((&name, descending)));
+        }
+    }
+
+    /// Like [`DataFrameTable::show`], with `delegate` drawing the headers and cells. The
+    /// delegate handles clicks itself, so headers no longer sort and rows are not selected.
+    pub fn show_with(&mut self, ui: &mut Ui, delegate: &mut impl TableDelegate) {
+        self.show_bars(ui);
+        let columns = self.shown_columns();
+        self.grid.show(ui, &columns, self.view.height(), delegate);
+    }
+}
+
+impl Grid {
+    fn show(
+        &mut self,
+        ui: &mut Ui,
+        columns: &[String],
+        num_rows: usize,
+        delegate: &mut impl TableDelegate,
+    ) {
+        let leading = delegate.leading_columns();
+        let mut first_visible_row = None;
+        ScrollArea::horizontal()
+            .id_salt(self.id_salt.with("hscroll"))
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                let available_height = ui.available_height();
+                // Initial widths only apply to fresh table state, so key it by the layout.
+                let mut table = TableBuilder::new(ui)
+                    .id_salt(self.id_salt.with((columns, leading)))
+                    .striped(true)
+                    .resizable(true)
+                    .columns(Column::auto().resizable(true), leading);
+                for name in columns {
+                    let column = match delegate.initial_width(name) {
+                        Some(width) => Column::initial(width),
+                        None => Column::auto(),
+                    };
+                    table = table.column(column.resizable(true).clip(delegate.clip(name)));
+                }
+                if let Some((row, align)) = self.scroll_to_row.take() {
+                    table = table.scroll_to_row(row, align);
+                }
+                let footer_height = delegate.footer_height();
+                if footer_height > 0.0 {
+                    let height = (available_height - footer_height).max(self.row_height);
+                    table = table.max_scroll_height(height);
+                }
+                let all_columns = || {
+                    (0..leading)
+                        .map(GridColumn::Leading)
+                        .chain(columns.iter().map(|name| GridColumn::Data(name)))
+                };
+                table
+                    .sense(Sense::click())
+                    .header(20.0, |mut header| {
+                        for column in all_columns() {
+                            header.col(|ui| delegate.header(ui, column));
+                        }
+                    })
+                    .body(|body| {
+                        self.widths = body.widths().to_vec();
+                        body.rows(self.row_height, num_rows, |mut row| {
+                            let index = row.index();
+                            first_visible_row.get_or_insert(index);
+                            row.set_selected(delegate.is_row_selected(index));
+                            for column in all_columns() {
+                                let (_, response) = row.col(|ui| delegate.cell(ui, index, column));
+                                delegate.cell_response(index, column, &response);
+                            }
+                        });
+                    });
+                delegate.footer(ui, &self.widths);
+            });
+        if let Some(row) = first_visible_row {
+            self.first_visible_row = row;
+        }
+    }
+}
+
+/// Cells as text or through [`CellRenderers`], with click-to-sort headers.
+struct PlainCells<'a> {
+    view: &'a DataFrame,
+    renderers: &'a CellRenderers,
+    sort: Option<(&'a str, bool)>,
+    selected_row: Option<usize>,
+    clicked_header: Option<String>,
+    clicked_row: Option<usize>,
+}
+
+impl TableDelegate for PlainCells<'_> {
+    fn header(&mut self, ui: &mut Ui, column: GridColumn<'_>) {
+        let GridColumn::Data(name) = column else {
+            return;
+        };
+        let marker = match self.sort {
+            Some((sorted, false)) if sorted == name => " ⬆",
+            Some((sorted, true)) if sorted == name => " ⬇",
+            _ => "",
+        };
+        let response = ui
+            .add(
+                Label::new(RichText::new(format!("{}{}", name, marker)).strong())
+                    .wrap_mode(TextWrapMode::Extend),
+            )
+            .on_hover_cursor(CursorIcon::Default);
+        if response.clicked() {
+            self.clicked_header = Some(name.to_string());
+        }
+    }
+
+    fn cell(&mut self, ui: &mut Ui, row: usize, column: GridColumn<'_>) {
+        let GridColumn::Data(name) = column else {
+            return;
+        };
+        let column = self.view.column(name).ok();
+        let value = column
+            .and_then(|column| column.get(row).ok())
+            .unwrap_or(AnyValue::Null);
+        let renderer = column.filter(|_| !value.is_null()).and_then(|column| {
+            let dtype = column.dtype();
+            self.renderers
+                .get(name, dtype)
+                .map(|renderer| (renderer, dtype))
+        });
+        match renderer {
+            Some((renderer, dtype)) => renderer.render(ui, dtype, &value),
+            None => {
+                ui.add(Label::new(value.str_value()).truncate());
+            }
+        }
+    }
+
+    fn cell_response(&mut self, row: usize, _column: GridColumn<'_>, response: &Response) {
+        if response.clicked() {
+            self.clicked_row = Some(row);
+        }
+    }
+
+    fn is_row_selected(&self, row: usize) -> bool {
+        self.selected_row == Some(row)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filters_sorts_and_orders_columns() {
+        let df = df!(
+            "name" => ["b", "a", "c", "ab"],
+            "n" => [2, 1, 3, 4],
+            "extra" => [0, 0, 0, 0],
+        )
+        .unwrap();
+        let mut table = DataFrameTable::new(df);
+        table.set_filter("name", FilterType::Contains, "b");
+        table.set_sort(Some(("n", true)));
+        let names: Vec<_> = table
+            .view()
+            .column("name")
+            .unwrap()
+            .str()
+            .unwrap()
+            .into_no_null_iter()
+            .collect();
+        assert_eq!(names, ["ab", "b"]);
+
+        table.set_columns(Some(vec!["n".into(), "gone".into(), "name".into()]));
+        table.set_column_visible("name", false);
+        assert_eq!(table.shown_columns(), ["n"]);
+
+        // New data without the sorted column drops the sort but keeps the filter.
+        table.set_data(df!("name" => ["b", "c"]).unwrap());
+        assert_eq!(table.sort(), None);
+        assert_eq!(table.view().height(), 1);
+    }
+}
@@ -0,0 +1,301 @@
+//! A terminal grid for machines without a display, using the same loading, filtering
+//! and sorting as the window.
+
+use crate::ROW_INDEX_COLUMN;
+use crate::filter::{self, FilterType};
+use crate::loader::{self, RowLimit};
+use polars::prelude::*;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Row, Table, TableState};
+use ratatui::{DefaultTerminal, Frame};
+use std::error::Error;
+use std::io;
+use std::path::PathBuf;
+
+/// Widest a column is drawn, in characters.
+const MAX_COLUMN_WIDTH: usize = 30;
+
+/// Space between columns, in characters.
+const COLUMN_SPACING: usize = 2;
+
+const HELP: &str =
+    "↑↓←→ move  PgUp/PgDn page  g/G top/bottom  / contains  = equals  s sort  q quit";
+
+struct Viewer {
+    title: String,
+    original: DataFrame,
+    view: DataFrame,
+    row: usize,
+    column: usize,
+    top: usize,
+    left: usize,
+    /// Rows that fit on screen, as of the last draw.
+    page: usize,
+    sort: Option<(usize, bool)>,
+    filter: Option<(usize, FilterType, String)>,
+    /// Filter text being typed, with the kind of filter.
+    input: Option<(FilterType, String)>,
+    message: Option<String>,
+}
+
+fn cell_text(column: &Column, row: usize) -> String {
+    let text = match column.get(row) {
+        Ok(value) => value
+            .get_str()
+            .map_or_else(|| value.to_string(), str::to_string),
+        Err(_) => "Error".to_string(),
+    };
+    text.replace(['\n', '\r', '\t'], " ")
+}
+
+fn fit(text: &str, width: usize) -> String {
+    if text.chars().count() > width {
+        let cut: String = text.chars().take(width.saturating_sub(1)).collect();
+        format!("{}…", cut)
+    } else {
+        format!("{:<width$}", text)
+    }
+}
+
+impl Viewer {
+    fn column_names(&self) -> Vec<String> {
+        self.view
+            .get_column_names()
+            .iter()
+            .map(|name| name.to_string())
+            .collect()
+    }
+
+    /// Filters the loaded rows, then sorts them, as the window does.
+    fn refresh(&mut self) {
+        let names = self.column_names();
+        let filtered = match &self.filter {
+            Some((col, filter_type, text)) => {
+                filter::filter_frame(&self.original, &names[*col], *filter_type, text)
+            }
+            None => Ok(self.original.clone()),
+        };
+        let result = filtered.and_then(|df| match self.sort {
+            Some((col, descending)) => df.sort(
+                [names[col].as_str()],
+                SortMultipleOptions::new().with_order_descending(descending),
+            ),
+            None => Ok(df),
+        });
+        match result {
+            Ok(df) => {
+                self.view = df;
+                self.message = None;
+            }
+            Err(e) => self.message = Some(e.to_string()),
+        }
+        self.row = self.row.min(self.view.height().saturating_sub(1));
+    }
+
+    /// Handles a key; returns false when the viewer should close.
+    fn handle(&mut self, key: KeyEvent) -> bool {
+        let interrupt =
+            key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c');
+        if let Some((_, text)) = &mut self.input {
+            match key.code {
+                _ if interrupt => self.input = None,
+                KeyCode::Char(c) => text.push(c),
+                KeyCode::Backspace => {
+                    text.pop();
+                }
+                KeyCode::Enter => {
+                    let (filter_type, text) = self.input.take().unwrap();
+                    self.filter = (!text.is_empty()).then_some((self.column, filter_type, text));
+                    self.refresh();
+                }
+                KeyCode::Esc => self.input = None,
+                _ => {}
+            }
+            return true;
+        }
+        let last_row = self.view.height().saturating_sub(1);
+        let last_column = self.view.width().saturating_sub(1);
+        match key.code {
+            _ if interrupt => return false,
+            KeyCode::Char('q') => return false,
+            KeyCode::Up | KeyCode::Char('k') => self.row = self.row.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.row = (self.row + 1).min(last_row),
+            KeyCode::Left | KeyCode::Char('h') => self.column = self.column.saturating_sub(1),
+            KeyCode::Right | KeyCode::Char('l') => self.column = (self.column + 1).min(last_column),
+            KeyCode::PageUp => self.row = self.row.saturating_sub(self.page),
+            KeyCode::PageDown => self.row = (self.row + self.page).min(last_row),
+            KeyCode::Home | KeyCode::Char('g') => self.row = 0,
+            KeyCode::End | KeyCode::Char('G') => self.row = last_row,
+            KeyCode::Char('/') => self.input = Some((FilterType::Contains, String::new())),
+            KeyCode::Char('=') => self.input = Some((FilterType::Equals, String::new())),
+            KeyCode::Char('s') => {
+                self.sort = match self.sort {
+                    Some((col, false)) if col == self.column => Some((col, true)),
+                    Some((col, true)) if col == self.column => None,
+                    _ => Some((self.column, false)),
+                };
+                self.refresh();
+            }
+            _ => {}
+        }
+        true
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [table_area, status_area, footer_area] = Layout::vertical([
+            Constraint::Min(2),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        let width = table_area.width as usize;
+        // The header takes the first line.
+        self.page = (table_area.height as usize).saturating_sub(1).max(1);
+        if self.row < self.top {
+            self.top = self.row;
+        } else if self.row >= self.top + self.page {
+            self.top = self.row + 1 - self.page;
+        }
+        let rows = self.top..(self.top + self.page).min(self.view.height());
+        let columns = self.view.get_columns();
+        let widths: Vec<usize> = columns
+            .iter()
+            .map(|column| {
+                rows.clone()
+                    .map(|row| cell_text(column, row).chars().count())
+                    .chain([column.name().chars().count() + 1])
+                    .max()
+                    .unwrap_or(0)
+                    .min(MAX_COLUMN_WIDTH)
+            })
+            .collect();
+        // Scroll horizontally so the current column is on screen.
+        self.left = self.left.min(self.column);
+        while self.left < self.column
+            && widths[self.left..=self.column]
+                .iter()
+                .map(|w| w + COLUMN_SPACING)
+                .sum::<usize>()
+                > width + COLUMN_SPACING
+        {
+            self.left += 1;
+        }
+
+        let header = Row::new(
+            columns
+                .iter()
+                .enumerate()
+                .skip(self.left)
+                .map(|(col, column)| {
+                    let marker = match self.sort {
+                        Some((sorted, false)) if sorted == col => "↑",
+                        Some((sorted, true)) if sorted == col => "↓",
+                        _ => "",
+                    };
+                    fit(&format!("{}{}", column.name(), marker), widths[col])
+                }),
+        )
+        .style(Style::new().add_modifier(Modifier::REVERSED));
+        let body = rows.clone().map(|row| {
+            Row::new(
+                columns
+                    .iter()
+                    .enumerate()
+                    .skip(self.left)
+                    .map(|(col, column)| fit(&cell_text(column, row), widths[col])),
+            )
+        });
+        let table = Table::new(
+            body,
+            widths[self.left..]
+                .iter()
+                .map(|&width| Constraint::Length(width as u16)),
+        )
+        .header(header)
+        .column_spacing(COLUMN_SPACING as u16)
+        .cell_highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        let mut state = TableState::default()
+            .with_selected_cell((self.row - self.top, self.column - self.left));
+        frame.render_stateful_widget(table, table_area, &mut state);
+
+        let mut status = format!(
+            "{}  row {} of {}",
+            self.title,
+            (self.row + 1).min(self.view.height()),
+            self.view.height()
+        );
+        if self.view.height() != self.original.height() {
+            status.push_str(&format!(" (filtered from {})", self.original.height()));
+        }
+        if let Some((col, filter_type, text)) = &self.filter {
+            status.push_str(&format!(
+                "  {} {} \"{}\"",
+                self.view.get_column_names()[*col],
+                filter_type.to_string().to_lowercase(),
+                text
+            ));
+        }
+        frame.render_widget(Line::from(status), status_area);
+        let footer = match (&self.input, &self.message) {
+            (Some((filter_type, text)), _) => format!(
+                "{} {}: {}_",
+                self.column_names()[self.column],
+                filter_type.to_string().to_lowercase(),
+                text
+            ),
+            (None, Some(message)) => message.clone(),
+            (None, None) => HELP.to_string(),
+        };
+        frame.render_widget(Line::from(footer), footer_area);
+    }
+
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            // Windows also reports key releases; resizes only need the redraw above.
+            if let Event::Key(key) = event::read()?
+                && key.kind == KeyEventKind::Press
+                && !self.handle(key)
+            {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// `tablr tui <file>...`: shows the files in the terminal until `q` is pressed.
+pub fn run(paths: &[PathBuf]) -> Result<(), Box<dyn Error>> {
+    if paths.is_empty() {
+        return Err("usage: tablr tui <file>...".into());
+    }
+    let df = loader::read_parquet_indexed(paths, ROW_INDEX_COLUMN, &RowLimit::All)?;
+    let title = match paths {
+        [path] => path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string(),
+        paths => format!("{} files", paths.len()),
+    };
+    let mut viewer = Viewer {
+        title,
+        view: df.clone(),
+        original: df,
+        row: 0,
+        column: 0,
+        top: 0,
+        left: 0,
+        page: 1,
+        sort: None,
+        filter: None,
+        input: None,
+        message: None,
+    };
+    let mut terminal = ratatui::try_init()?;
+    let result = viewer.run(&mut terminal);
+    ratatui::restore();
+    Ok(result?)
+}
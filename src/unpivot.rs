@@ -0,0 +1,27 @@
+use polars::prelude::*;
+
+/// Reshapes `df` from wide to long: one row per original row and value column, holding
+/// the column's name in `variable_name` and its value in `value_name`.
+///
+/// `row_index` is kept as an id column so each long row traces back to its wide row.
+/// Value columns of different types are combined under a common type.
+pub fn unpivot(
+    df: &DataFrame,
+    index: &[String],
+    on: &[String],
+    variable_name: &str,
+    value_name: &str,
+    row_index: &str,
+) -> PolarsResult<DataFrame> {
+    if on.is_empty() {
+        polars_bail!(ComputeError: "no value columns selected");
+    }
+    let mut ids: Vec<PlSmallStr> = vec![row_index.into()];
+    ids.extend(index.iter().map(|name| PlSmallStr::from(name.as_str())));
+    df.unpivot2(UnpivotArgsIR {
+        on: on.iter().map(|name| name.as_str().into()).collect(),
+        index: ids,
+        variable_name: Some(variable_name.into()),
+        value_name: Some(value_name.into()),
+    })
+}
@@ -0,0 +1,94 @@
+//! Notices when loaded files are rewritten on disk, from file system notifications.
+
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::time::Duration;
+use web_time::Instant;
+
+/// How long the files must stay unchanged before they are read again, so that a file
+/// still being written is not read half-way.
+pub const SETTLE_TIME: Duration = Duration::from_millis(500);
+
+pub struct FileWatch {
+    paths: Vec<PathBuf>,
+    /// Kept alive for as long as events should arrive.
+    _watcher: RecommendedWatcher,
+    events: Receiver<notify::Result<Event>>,
+    /// When a watched file last changed, while that change has not been reported.
+    changed: Option<Instant>,
+}
+
+impl FileWatch {
+    /// Starts watching `paths`; `notify` is called from another thread on every change.
+    pub fn new(paths: &[PathBuf], notify: impl Fn() + Send + 'static) -> notify::Result<Self> {
+        let (sender, events) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(move |event| {
+            if sender.send(event).is_ok() {
+                notify();
+            }
+        })?;
+        let paths: Vec<PathBuf> = paths.iter().map(|path| absolute(path)).collect();
+        // Editors and writers often replace a file by renaming a new one over it, which
+        // only the containing directory sees.
+        let directories: HashSet<&Path> = paths.iter().filter_map(|path| path.parent()).collect();
+        for directory in directories {
+            watcher.watch(directory, RecursiveMode::NonRecursive)?;
+        }
+        Ok(Self {
+            paths,
+            _watcher: watcher,
+            events,
+            changed: None,
+        })
+    }
+
+    pub fn watches(&self, paths: &[PathBuf]) -> bool {
+        self.paths.len() == paths.len()
+            && self
+                .paths
+                .iter()
+                .zip(paths)
+                .all(|(watched, path)| *watched == absolute(path))
+    }
+
+    /// True when the files have changed and then stayed unchanged for [`SETTLE_TIME`].
+    pub fn poll(&mut self) -> bool {
+        for event in self.events.try_iter() {
+            let touches_file = match event {
+                Ok(event) => {
+                    !event.kind.is_access()
+                        && event
+                            .paths
+                            .iter()
+                            .any(|path| self.paths.contains(&absolute(path)))
+                }
+                // Missed events may have included ours.
+                Err(_) => true,
+            };
+            if touches_file {
+                self.changed = Some(Instant::now());
+            }
+        }
+        if self
+            .changed
+            .is_some_and(|changed| changed.elapsed() >= SETTLE_TIME)
+        {
+            self.changed = None;
+            return true;
+        }
+        false
+    }
+
+    /// How long until a change that is still settling should be reported.
+    pub fn settling(&self) -> Option<Duration> {
+        self.changed
+            .map(|changed| SETTLE_TIME.saturating_sub(changed.elapsed()))
+    }
+}
+
+/// Events name files by absolute path, while the files may have been opened relative.
+fn absolute(path: &Path) -> PathBuf {
+    std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf())
+}
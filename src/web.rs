@@ -0,0 +1,72 @@
+//! Browser support: files are picked or dropped as bytes, since a page has no paths to
+//! read from or save to.
+
+use eframe::egui::Context;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::HtmlCanvasElement;
+
+/// Stands in for the native file dialog, whose blocking calls a browser can't make.
+/// Every dialog is cancelled, so opening other files, exporting and saving are
+/// desktop-only.
+#[derive(Default)]
+pub struct FileDialog;
+
+impl FileDialog {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn add_filter(self, _name: impl Into<String>, _extensions: &[&str]) -> Self {
+        self
+    }
+
+    pub fn set_file_name(self, _name: impl Into<String>) -> Self {
+        self
+    }
+
+    pub fn set_directory(self, _path: impl AsRef<Path>) -> Self {
+        self
+    }
+
+    pub fn pick_file(self) -> Option<PathBuf> {
+        None
+    }
+
+    pub fn pick_files(self) -> Option<Vec<PathBuf>> {
+        None
+    }
+
+    pub fn save_file(self) -> Option<PathBuf> {
+        None
+    }
+}
+
+/// Shows the browser's file picker. The chosen file's name and contents arrive on the
+/// returned channel once read; nothing arrives if the picker is cancelled.
+pub fn pick_parquet(ctx: &Context) -> Receiver<(String, Vec<u8>)> {
+    let (sender, receiver) = mpsc::channel();
+    let ctx = ctx.clone();
+    wasm_bindgen_futures::spawn_local(async move {
+        if let Some(file) = rfd::AsyncFileDialog::new()
+            .add_filter("Parquet files", &["parquet"])
+            .pick_file()
+            .await
+        {
+            let bytes = file.read().await;
+            let _ = sender.send((file.file_name(), bytes));
+            ctx.request_repaint();
+        }
+    });
+    receiver
+}
+
+/// The `<canvas>` element with the id `canvas_id`.
+pub fn canvas(canvas_id: &str) -> Result<HtmlCanvasElement, JsValue> {
+    web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.get_element_by_id(canvas_id))
+        .and_then(|element| element.dyn_into::<HtmlCanvasElement>().ok())
+        .ok_or_else(|| JsValue::from_str(&format!("No canvas with the id {}", canvas_id)))
+}
@@ -0,0 +1,93 @@
+use crate::filter::FilterType;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Which tool panels are open and whether they live in their own OS window.
+///
+/// Window positions and sizes inside the main viewport are kept by egui's own memory,
+/// which eframe persists alongside this struct.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Workspace {
+    pub filter_open: bool,
+    pub pii_open: bool,
+    pub pii_detached: bool,
+    pub json_detached: bool,
+    pub export_open: bool,
+    pub display_open: bool,
+    pub settings_open: bool,
+    pub search_open: bool,
+    pub schema_open: bool,
+    pub schema_detached: bool,
+    pub notes_open: bool,
+    pub notes_detached: bool,
+    pub diagnostics_open: bool,
+    pub diagnostics_detached: bool,
+    pub script_open: bool,
+    pub script_detached: bool,
+    pub diff_open: bool,
+    pub diff_detached: bool,
+    pub pivot_open: bool,
+    pub pivot_detached: bool,
+    pub group_open: bool,
+    pub group_detached: bool,
+    pub correlation_open: bool,
+    pub correlation_detached: bool,
+    pub scatter_open: bool,
+    pub scatter_detached: bool,
+    pub line_open: bool,
+    pub line_detached: bool,
+    pub histogram_open: bool,
+    pub histogram_detached: bool,
+    pub bar_open: bool,
+    pub bar_detached: bool,
+    pub box_open: bool,
+    pub box_detached: bool,
+    pub profile_open: bool,
+    pub profile_detached: bool,
+    pub describe_open: bool,
+    pub describe_detached: bool,
+    pub similarity_open: bool,
+    pub similarity_detached: bool,
+    pub metadata_open: bool,
+    pub metadata_detached: bool,
+    /// Files compared in the split view.
+    pub split_files: Vec<PathBuf>,
+    /// The minimap is on by default, so the stored flag is the opposite.
+    pub minimap_hidden: bool,
+    /// Free-form notes per dataset, keyed by [`dataset_key`].
+    pub notes: HashMap<String, String>,
+    /// What was open at the last exit, offered for restoring at the next start.
+    pub session: Option<Session>,
+}
+
+/// The loaded files and how they were being looked at. Column order, widths and hidden
+/// columns are not included, as they are remembered per schema by the column layout.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    pub files: Vec<PathBuf>,
+    pub filter_column: Option<String>,
+    pub filter_type: FilterType,
+    pub filter_text: String,
+    pub filter_expression: String,
+    pub sort: Option<(String, bool)>,
+    pub masked_columns: Vec<String>,
+    pub first_visible_row: usize,
+}
+
+/// Identifies a dataset by the files it was loaded from, independent of their order.
+pub fn dataset_key(paths: &[PathBuf]) -> String {
+    let mut paths: Vec<String> = paths
+        .iter()
+        .map(|path| {
+            path.canonicalize()
+                .unwrap_or_else(|_| path.clone())
+                .display()
+                .to_string()
+        })
+        .collect();
+    paths.sort();
+    paths.join("\n")
+}
@@ -0,0 +1,140 @@
+//! Excel export: one worksheet with a bold, frozen header row, typed cells and an
+//! optional auto-filter.
+
+use polars::prelude::*;
+use rust_xlsxwriter::{ColNum, Format, RowNum, Workbook, Worksheet, XlsxError};
+use std::path::Path;
+
+/// Rows and columns a worksheet can hold.
+const MAX_ROWS: usize = 1_048_576;
+const MAX_COLUMNS: usize = 16_384;
+
+const SHEET_NAME: &str = "Data";
+
+/// Days between Excel's day zero (1899-12-30) and the Unix epoch.
+const UNIX_EPOCH_SERIAL: f64 = 25_569.0;
+
+fn to_compute_err(e: XlsxError) -> PolarsError {
+    polars_err!(ComputeError: "{}", e)
+}
+
+struct Formats {
+    header: Format,
+    date: Format,
+    datetime: Format,
+}
+
+/// Writes one cell; nulls stay empty.
+fn write_cell(
+    sheet: &mut Worksheet,
+    row: RowNum,
+    column: ColNum,
+    value: &AnyValue,
+    formats: &Formats,
+) -> Result<(), XlsxError> {
+    match value {
+        AnyValue::Null => {}
+        AnyValue::Boolean(flag) => {
+            sheet.write_boolean(row, column, *flag)?;
+        }
+        AnyValue::Date(days) => {
+            let serial = *days as f64 + UNIX_EPOCH_SERIAL;
+            sheet.write_number_with_format(row, column, serial, &formats.date)?;
+        }
+        AnyValue::Datetime(ticks, unit, _) | AnyValue::DatetimeOwned(ticks, unit, _) => {
+            let per_day = match unit {
+                TimeUnit::Nanoseconds => 86_400e9,
+                TimeUnit::Microseconds => 86_400e6,
+                TimeUnit::Milliseconds => 86_400e3,
+            };
+            let serial = *ticks as f64 / per_day + UNIX_EPOCH_SERIAL;
+            sheet.write_number_with_format(row, column, serial, &formats.datetime)?;
+        }
+        value if value.is_primitive_numeric() => {
+            sheet.write_number(row, column, value.extract::<f64>().unwrap_or(f64::NAN))?;
+        }
+        AnyValue::String(text) => {
+            sheet.write_string(row, column, *text)?;
+        }
+        AnyValue::StringOwned(text) => {
+            sheet.write_string(row, column, text.as_str())?;
+        }
+        value => {
+            sheet.write_string(row, column, value.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+fn workbook(df: &DataFrame, autofilter: bool) -> PolarsResult<Workbook> {
+    let formats = Formats {
+        header: Format::new().set_bold(),
+        date: Format::new().set_num_format("yyyy-mm-dd"),
+        datetime: Format::new().set_num_format("yyyy-mm-dd hh:mm:ss"),
+    };
+    let mut workbook = Workbook::new();
+    let sheet = workbook.add_worksheet();
+    sheet.set_name(SHEET_NAME).map_err(to_compute_err)?;
+    sheet.set_freeze_panes(1, 0).map_err(to_compute_err)?;
+    for (index, column) in df.get_columns().iter().enumerate() {
+        let index = index as ColNum;
+        sheet
+            .write_string_with_format(0, index, column.name().as_str(), &formats.header)
+            .map_err(to_compute_err)?;
+        for row in 0..df.height() {
+            write_cell(sheet, row as RowNum + 1, index, &column.get(row)?, &formats)
+                .map_err(to_compute_err)?;
+        }
+    }
+    if autofilter && df.width() > 0 {
+        sheet
+            .autofilter(0, 0, df.height() as RowNum, (df.width() - 1) as ColNum)
+            .map_err(to_compute_err)?;
+    }
+    Ok(workbook)
+}
+
+/// Writes the frame as a single-sheet Excel workbook. Numbers, booleans, dates and
+/// datetimes become typed cells; everything else is written as text.
+pub fn write_xlsx(df: &DataFrame, path: &Path, autofilter: bool) -> PolarsResult<()> {
+    if df.height() + 1 > MAX_ROWS || df.width() > MAX_COLUMNS {
+        polars_bail!(
+            ComputeError: "Excel sheets hold at most {} rows and {} columns",
+            MAX_ROWS - 1,
+            MAX_COLUMNS
+        );
+    }
+    workbook(df, autofilter)?.save(path).map_err(to_compute_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_typed_cells_into_a_zip_container() {
+        let df = df!(
+            "flag" => [Some(true), None],
+            "amount" => [1.5, f64::NAN],
+            "name" => ["a & <b>", "\u{1}"],
+        )
+        .unwrap()
+        .lazy()
+        .with_column(lit(chrono::NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()).alias("day"))
+        .collect()
+        .unwrap();
+        let bytes = workbook(&df, true).unwrap().save_to_buffer().unwrap();
+        assert_eq!(&bytes[..4], b"PK\x03\x04");
+    }
+
+    #[test]
+    fn refuses_frames_larger_than_a_sheet() {
+        let columns: Vec<Column> = (0..=MAX_COLUMNS)
+            .map(|i| Column::new(format!("c{}", i).into(), [0i32]))
+            .collect();
+        let df = DataFrame::new(columns).unwrap();
+        let path = std::env::temp_dir().join("tablr-too-wide.xlsx");
+        assert!(write_xlsx(&df, &path, false).is_err());
+        assert!(!path.exists());
+    }
+}